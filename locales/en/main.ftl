@@ -0,0 +1,373 @@
+# The English text of the UI, which every other language falls back to for messages it hasn't translated.
+# Messages are grouped by the part of the UI they are in. See https://projectfluent.org/ for the syntax.
+
+## Shared
+
+skip-to-content = Skip to main content
+loading = Loading...
+loading-more-posts = Loading more posts...
+error-loading-post = Error loading this post:
+error-loading-sidecard = Error loading sidecard:
+error-loading-comments = Error loading these comments:
+error-loading-page = Error loading this page:
+error-loading-profile = Error loading this user's posts and comments
+error-loading-communities = Error loading the communities
+error-loading-banned-users = Error loading the banned users. Only admins can view this page.
+error-custom-emojis-admins-only = Only admins can manage the custom emojis of this site.
+close = Close
+comments-count =
+    { $count ->
+        [one] { $count } Comment
+       *[other] { $count } Comments
+    }
+cancel = Cancel
+confirm = Confirm
+edit = Edit
+delete = Delete
+confirm-delete = Confirm Delete
+
+## Header
+
+nav-toggle = Toggle navigation
+logo-alt = { $name } logo
+nav-menu = Menu
+nav-explore-communities = Explore Communities
+nav-create-post = Create Post
+nav-create-community = Create Community
+nav-sign-in = Sign In/Create Account
+nav-profile = Profile
+nav-settings = Settings
+nav-sign-out = Sign out
+search-label = Search Lemmy
+search-placeholder = Enter search text here...
+search = Search
+notifications = Notifications
+theme = Theme
+theme-light = Light
+theme-dark = Dark
+theme-auto = Auto
+language = Language
+
+## Footer
+
+footer-modlog = Modlog
+footer-federation = Federation
+footer-documentation = Documentation
+footer-source-code = Source Code
+footer-lemmy-info = Lemmy Info
+footer-shortcuts = Keyboard Shortcuts
+
+## Feed
+
+feed = Posts
+post-upvote = Upvote
+post-downvote = Downvote
+post-save = Save
+post-cross-post = Cross-post
+post-report = Report
+post-pinned = Pinned
+post-score =
+    { $count ->
+        [one] { $count } point
+       *[other] { $count } points
+    }
+layout = Feed layout
+layout-list = List
+layout-compact = Compact
+layout-cards = Cards
+layout-infinite-scroll = Infinite scroll
+page-navigation = Page navigation
+page-previous = Previous
+page-next = Next
+page-number = Page: { $page }
+feed-end = You've reached the end of the feed
+listing-all = All
+listing-local = Local
+listing-subscribed = Subscribed
+sort-active = Active
+sort-hot = Hot
+sort-new = New
+sort-old = Old
+sort-most-comments = Most Comments
+sort-new-comments = New Comments
+sort-top-hour = Top - Hour
+sort-top-six-hour = Top - 6 Hours
+sort-top-twelve-hour = Top - 12 Hours
+sort-top-day = Top - Day
+sort-top-week = Top - Week
+sort-top-month = Top - Month
+sort-top-three-months = Top - 3 Months
+sort-top-six-months = Top - 6 Months
+sort-top-nine-months = Top - 9 Months
+sort-top-year = Top - Year
+sort-top-all = Top - All Time
+nsfw-badge = NSFW
+nsfw-hidden = NSFW content is hidden
+nsfw-reveal = Reveal NSFW content
+nsfw-click-to-reveal = NSFW: Click to reveal
+media-view-full-image = View full image
+media-play-video = Play video
+media-open-link = Open link
+media-collapse = Collapse media
+media-expand = Expand media
+
+## Posts
+
+post-removed-by-moderator = This post has been removed by a moderator
+post-deleted-by-creator = This post has been deleted by its creator
+post-locked = This post is locked, so no new comments can be added
+post-copied = Copied
+post-copy-link = Copy link
+post-permalink = Permalink
+post-view-original = View original
+post-cross-posted-to = Cross-posted to
+
+## Comments
+
+comment-collapse = Collapse comment
+comment-expand = Expand comment
+
+## Markdown editor
+
+editor-write = Write
+editor-preview = Preview
+editor-side-by-side = Side by Side
+editor-formatting = Formatting
+editor-bold = Bold (Ctrl+B)
+editor-italic = Italic (Ctrl+I)
+editor-strikethrough = Strikethrough
+editor-superscript = Superscript
+editor-subscript = Subscript
+editor-link = Link (Ctrl+K)
+editor-quote = Quote
+editor-code = Code (Ctrl+E)
+editor-spoiler = Spoiler
+editor-list = List
+editor-header = Header
+# The text that is selected in the editor when a format is applied without selecting anything
+editor-bold-placeholder = bold text
+editor-italic-placeholder = italic text
+editor-strikethrough-placeholder = strikethrough text
+editor-superscript-placeholder = superscript
+editor-subscript-placeholder = subscript
+editor-link-placeholder = link text
+editor-code-placeholder = code
+editor-spoiler-placeholder = hidden text
+upload-image = Upload Image
+upload-image-title = Upload an image
+upload-paste-or-drop = or paste or drop an image
+upload-progress = Image upload progress
+upload-uploaded-image = Uploaded image
+upload-delete = Delete uploaded image
+upload-no-delete-token = This image has no delete token
+emoji-picker-insert = Insert a custom emoji
+emoji-picker-search = Search emojis
+emoji-picker-no-matches = No matching emojis
+
+## Keyboard shortcuts
+
+shortcuts-title = Keyboard shortcuts
+shortcuts-enabled = Use keyboard shortcuts
+shortcuts-typing = Shortcuts don't work while typing in a text box, so they never get in the way. Press Esc to leave the text box.
+shortcut-next = Next post or comment
+shortcut-previous = Previous post or comment
+shortcut-open-link = Open the post's link
+shortcut-open-comments = Open the post's comments
+shortcut-collapse = Collapse or expand the comment
+shortcut-home = Go to the front page
+shortcut-search = Search
+shortcut-submit = Send what you have written
+shortcut-help = Show or hide this help
+
+## Communities
+
+communities-title = Communities
+communities-description = The communities on { $name }
+communities-filter = Filter communities
+communities-filter-placeholder = Filter communities...
+communities-name = Name
+communities-subscribers = Subscribers
+communities-monthly-active-users = Monthly Active Users
+communities-posts = Posts
+communities-comments = Comments
+communities-new = New
+communities-subscription = Subscription
+communities-no-matches = No communities match these filters
+communities-nsfw = NSFW
+community-mods-only = Only moderators can post in this community
+community-removed = This community has been removed by an admin
+community-deleted = This community has been deleted
+subscribe-subscribe = Subscribe
+subscribe-unsubscribe = Unsubscribe
+subscribe-pending = Pending
+subscribe-log-in = Log in to subscribe to communities
+subscribers-count =
+    { $count ->
+        [one] { $count } subscriber
+       *[other] { $count } subscribers
+    }
+
+## Sidecards
+
+sidecard-about = About
+sidecard-statistics = Statistics
+sidecard-trending-communities = Trending Communities
+sidecard-admins = Admins
+sidecard-moderators = Moderators
+sidecard-moderates = Moderates
+sidecard-languages = Languages
+sidecard-any-language = Any language
+sidecard-subscribers = Subscribers
+sidecard-posts = Posts
+sidecard-comments = Comments
+sidecard-active-users = Active Users
+sidecard-day = Day
+sidecard-week = Week
+sidecard-month = Month
+sidecard-half-year = Half Year
+sidecard-users = Users
+sidecard-activity = Activity
+sidecard-karma = Karma
+sidecard-users-count =
+    { $count ->
+        [one] { $count } User
+       *[other] { $count } Users
+    }
+sidecard-communities-count =
+    { $count ->
+        [one] { $count } Community
+       *[other] { $count } Communities
+    }
+sidecard-posts-count =
+    { $count ->
+        [one] { $count } Post
+       *[other] { $count } Posts
+    }
+sidecard-moderators-count =
+    { $count ->
+        [one] { $count } Moderator
+       *[other] { $count } Moderators
+    }
+sidecard-karma-count = { $count } Karma
+sidecard-per-day = { $count } / Day
+sidecard-per-month = { $count } / Month
+
+## Profiles
+
+profile-overview = Overview
+profile-posts = Posts
+profile-comments = Comments
+profile-saved = Saved
+profile-saved-private = Only this user can see what they have saved.
+profile-empty = Nothing here yet.
+# Shown before the post and the community a comment was made in
+profile-comment-on = On
+profile-comment-in = in
+profile-comment-context = Context
+
+## Admin tools
+
+admin-tools = Admin Tools
+admin-view-banned = View all banned users
+admin-manage-emojis = Manage custom emojis
+admin-reason = Reason (shown in the mod log)
+admin-expires = Expires after (days, leave blank for a permanent ban)
+admin-remove-data = Also remove all of their posts and comments
+admin-ban = Ban
+admin-unban = Unban
+admin-purge = Purge
+admin-restore = Restore
+admin-remove = Remove
+admin-ban-user = Ban User
+admin-ban-user-title = Ban User From Site
+admin-unban-user = Unban User
+admin-purge-user = Purge User
+admin-remove-admin = Remove Admin
+admin-appoint-admin = Appoint As Admin
+admin-leave = Leave Admin Team
+admin-restore-community = Restore Community
+admin-remove-community = Remove Community
+admin-purge-community = Purge Community
+admin-purge-post = Purge Post
+admin-purge-comment = Purge Comment
+admin-ban-warning = The user will be banned from the whole site. Removed content can't be restored by unbanning them later.
+admin-unban-warning = The user will be able to post and comment on this site again.
+admin-purge-user-warning = Purging permanently deletes the user and all of their posts, comments, and uploads from the database. This can't be undone.
+admin-remove-admin-warning = The user will lose all admin permissions on this site.
+admin-appoint-admin-warning = The user will have full admin permissions on this site, including purging content and banning other users.
+admin-leave-warning = You will lose all admin permissions on this site. Only another admin can make you an admin again.
+admin-restore-community-warning = The community and its posts will be visible again.
+admin-remove-community-warning = The community will be hidden from everyone. It can be restored later from this sidecard.
+admin-purge-community-warning = Purging permanently deletes the community and all of its posts and comments from the database. This can't be undone.
+admin-purge-post-warning = Purging permanently deletes the post and all of its comments from the database. This can't be undone.
+admin-purge-comment-warning = Purging permanently deletes the comment from the database. This can't be undone.
+admin-user-banned = The user has been banned from the site.
+admin-user-unbanned = The user has been unbanned.
+admin-user-purged = The user and all of their content have been purged.
+admin-left = You are no longer an admin of this site.
+admin-count =
+    { $count ->
+        [one] The site now has { $count } admin.
+       *[other] The site now has { $count } admins.
+    }
+admin-community-removed = The community has been removed.
+admin-community-restored = The community has been restored.
+admin-community-purged = The community and all of its posts have been purged.
+admin-post-purged = The post and its comments have been purged.
+admin-comment-purged = The comment has been purged.
+banned-title = Banned Users
+banned-name = Name
+banned-expires = Ban Expires
+banned-never = Never
+banned-unban-warning = The user will be able to post and comment on this site again. Content removed with the ban won't be restored.
+emojis-add-title = Add a Custom Emoji
+emojis-none = This site doesn't have any custom emojis yet.
+emojis-emoji = Emoji
+emojis-shortcode = Shortcode
+emojis-image-url = Image URL
+emojis-category = Category
+emojis-alt-text = Alt Text
+emojis-keywords = Keywords
+emojis-keywords-input = Keywords (comma separated)
+emojis-manage = Manage
+emojis-save = Save
+emojis-add = Add Emoji
+emojis-saved = The emoji has been saved.
+emojis-added = The emoji has been added.
+
+## Federated instances
+
+instances-federated = Federated Instances
+instances-blocked = Blocked Instances
+instances-search = Search instances by domain
+instances-search-placeholder = Search by domain...
+instances-all-software = All software
+instances-domain = Domain Name
+instances-software = Software
+instances-version = Version
+instances-last-seen = Last seen
+instances-first-seen = First seen
+instances-unknown-version = Unknown
+instances-no-matches = No instances match these filters
+instances-visit = Visit this instance
+instances-visit-domain = Visit { $domain }
+instances-all = All instances
+instances-not-federated = This instance hasn't federated with us.
+instances-blocked-badge = Blocked
+instances-known-communities = Known Communities
+instances-error-communities = Error loading the communities from this instance
+instances-no-communities = No communities from this instance are known here.
+instances-no-more-communities = There are no more communities from this instance.
+instances-no-communities-looked-through = None of the communities looked through for this page are from this instance.
+
+## Times
+# $time is a relative time such as "3h ago"
+
+time-just-now = just now
+time-minutes-ago = { $count }m ago
+time-hours-ago = { $count }h ago
+time-days-ago = { $count }d ago
+time-months-ago = { $count }mo ago
+time-years-ago = { $count }y ago
+time-edited = edited { $time }
+time-joined = Joined { $time }
@@ -0,0 +1,377 @@
+# Le texte de l'interface en français. Les messages qui manquent ici sont affichés en anglais.
+
+## Shared
+
+skip-to-content = Aller au contenu principal
+loading = Chargement…
+loading-more-posts = Chargement d'autres publications…
+error-loading-post = Erreur lors du chargement de cette publication :
+error-loading-sidecard = Erreur lors du chargement du panneau latéral :
+error-loading-comments = Erreur lors du chargement des commentaires :
+error-loading-page = Erreur lors du chargement de cette page :
+error-loading-profile = Erreur lors du chargement des publications et commentaires de cet utilisateur
+error-loading-communities = Erreur lors du chargement des communautés
+error-loading-banned-users = Erreur lors du chargement des utilisateurs bannis. Seuls les administrateurs peuvent voir cette page.
+error-custom-emojis-admins-only = Seuls les administrateurs peuvent gérer les émojis personnalisés de ce site.
+close = Fermer
+comments-count =
+    { $count ->
+        [one] { $count } commentaire
+       *[other] { $count } commentaires
+    }
+cancel = Annuler
+confirm = Confirmer
+edit = Modifier
+delete = Supprimer
+confirm-delete = Confirmer la suppression
+
+## Header
+
+nav-toggle = Afficher la navigation
+logo-alt = Logo de { $name }
+nav-menu = Menu
+nav-explore-communities = Explorer les communautés
+nav-create-post = Créer une publication
+nav-create-community = Créer une communauté
+nav-sign-in = Se connecter/Créer un compte
+nav-profile = Profil
+nav-settings = Paramètres
+nav-sign-out = Se déconnecter
+search-label = Rechercher sur Lemmy
+search-placeholder = Saisissez votre recherche…
+search = Rechercher
+notifications = Notifications
+theme = Thème
+theme-light = Clair
+theme-dark = Sombre
+theme-auto = Automatique
+language = Langue
+
+## Footer
+
+footer-modlog = Journal de modération
+footer-federation = Fédération
+footer-documentation = Documentation
+footer-source-code = Code source
+footer-lemmy-info = À propos de Lemmy
+footer-shortcuts = Raccourcis clavier
+
+## Feed
+
+feed = Publications
+post-upvote = Voter pour
+post-downvote = Voter contre
+post-save = Enregistrer
+post-cross-post = Republier
+post-report = Signaler
+post-pinned = Épinglée
+post-score =
+    { $count ->
+        [one] { $count } point
+       *[other] { $count } points
+    }
+layout = Disposition du fil
+layout-list = Liste
+layout-compact = Compacte
+layout-cards = Cartes
+layout-infinite-scroll = Défilement infini
+page-navigation = Navigation entre les pages
+page-previous = Précédente
+page-next = Suivante
+page-number = Page : { $page }
+feed-end = Vous avez atteint la fin du fil
+listing-all = Tout
+listing-local = Local
+listing-subscribed = Abonnements
+sort-active = Actif
+sort-hot = Populaire
+sort-new = Nouveau
+sort-old = Ancien
+sort-most-comments = Plus commentés
+sort-new-comments = Nouveaux commentaires
+sort-top-hour = Top - Heure
+sort-top-six-hour = Top - 6 heures
+sort-top-twelve-hour = Top - 12 heures
+sort-top-day = Top - Jour
+sort-top-week = Top - Semaine
+sort-top-month = Top - Mois
+sort-top-three-months = Top - 3 mois
+sort-top-six-months = Top - 6 mois
+sort-top-nine-months = Top - 9 mois
+sort-top-year = Top - Année
+sort-top-all = Top - Depuis toujours
+nsfw-badge = NSFW
+nsfw-hidden = Le contenu NSFW est masqué
+nsfw-reveal = Afficher le contenu NSFW
+nsfw-click-to-reveal = NSFW : cliquez pour afficher
+media-view-full-image = Voir l'image en entier
+media-play-video = Lire la vidéo
+media-open-link = Ouvrir le lien
+media-collapse = Replier le média
+media-expand = Déplier le média
+
+## Posts
+
+post-removed-by-moderator = Cette publication a été retirée par un modérateur
+post-deleted-by-creator = Cette publication a été supprimée par son auteur
+post-locked = Cette publication est verrouillée, aucun nouveau commentaire ne peut être ajouté
+post-copied = Copié
+post-copy-link = Copier le lien
+post-permalink = Lien permanent
+post-view-original = Voir l'original
+post-cross-posted-to = Republié dans
+
+## Comments
+
+comment-collapse = Replier le commentaire
+comment-expand = Déplier le commentaire
+
+## Markdown editor
+
+editor-write = Écrire
+editor-preview = Aperçu
+editor-side-by-side = Côte à côte
+editor-formatting = Mise en forme
+editor-bold = Gras (Ctrl+B)
+editor-italic = Italique (Ctrl+I)
+editor-strikethrough = Barré
+editor-superscript = Exposant
+editor-subscript = Indice
+editor-link = Lien (Ctrl+K)
+editor-quote = Citation
+editor-code = Code (Ctrl+E)
+editor-spoiler = Spoiler
+editor-list = Liste
+editor-header = Titre
+editor-bold-placeholder = texte en gras
+editor-italic-placeholder = texte en italique
+editor-strikethrough-placeholder = texte barré
+editor-superscript-placeholder = exposant
+editor-subscript-placeholder = indice
+editor-link-placeholder = texte du lien
+editor-code-placeholder = code
+editor-spoiler-placeholder = texte masqué
+upload-image = Téléverser une image
+upload-image-title = Téléverser une image
+upload-paste-or-drop = ou collez ou déposez une image
+upload-progress = Progression du téléversement
+upload-uploaded-image = Image téléversée
+upload-delete = Supprimer l'image téléversée
+upload-no-delete-token = Cette image n'a pas de jeton de suppression
+emoji-picker-insert = Insérer un émoji personnalisé
+emoji-picker-search = Rechercher des émojis
+emoji-picker-no-matches = Aucun émoji ne correspond
+
+## Keyboard shortcuts
+
+shortcuts-title = Raccourcis clavier
+shortcuts-enabled = Utiliser les raccourcis clavier
+shortcuts-typing = Les raccourcis ne fonctionnent pas pendant la saisie dans un champ de texte, pour ne jamais gêner. Appuyez sur Échap pour quitter le champ.
+shortcut-next = Publication ou commentaire suivant
+shortcut-previous = Publication ou commentaire précédent
+shortcut-open-link = Ouvrir le lien de la publication
+shortcut-open-comments = Ouvrir les commentaires de la publication
+shortcut-collapse = Replier ou déplier le commentaire
+shortcut-home = Aller à la page d'accueil
+shortcut-search = Rechercher
+shortcut-submit = Envoyer ce que vous avez écrit
+shortcut-help = Afficher ou masquer cette aide
+
+## Communities
+
+communities-title = Communautés
+communities-description = Les communautés de { $name }
+communities-filter = Filtrer les communautés
+communities-filter-placeholder = Filtrer les communautés…
+communities-name = Nom
+communities-subscribers = Abonnés
+communities-monthly-active-users = Utilisateurs actifs par mois
+communities-posts = Publications
+communities-comments = Commentaires
+communities-new = Nouvelles
+communities-subscription = Abonnement
+communities-no-matches = Aucune communauté ne correspond à ces filtres
+communities-nsfw = NSFW
+community-mods-only = Seuls les modérateurs peuvent publier dans cette communauté
+community-removed = Cette communauté a été retirée par un administrateur
+community-deleted = Cette communauté a été supprimée
+subscribe-subscribe = S'abonner
+subscribe-unsubscribe = Se désabonner
+subscribe-pending = En attente
+subscribe-log-in = Connectez-vous pour vous abonner aux communautés
+subscribers-count =
+    { $count ->
+        [one] { $count } abonné
+       *[other] { $count } abonnés
+    }
+
+## Sidecards
+
+sidecard-about = À propos
+sidecard-statistics = Statistiques
+sidecard-trending-communities = Communautés tendance
+sidecard-admins = Administrateurs
+sidecard-moderators = Modérateurs
+sidecard-moderates = Modère
+sidecard-languages = Langues
+sidecard-any-language = Toutes les langues
+sidecard-subscribers = Abonnés
+sidecard-posts = Publications
+sidecard-comments = Commentaires
+sidecard-active-users = Utilisateurs actifs
+sidecard-day = Jour
+sidecard-week = Semaine
+sidecard-month = Mois
+sidecard-half-year = Six mois
+sidecard-users = Utilisateurs
+sidecard-activity = Activité
+sidecard-karma = Karma
+sidecard-users-count =
+    { $count ->
+        [one] { $count } utilisateur
+       *[other] { $count } utilisateurs
+    }
+sidecard-communities-count =
+    { $count ->
+        [one] { $count } communauté
+       *[other] { $count } communautés
+    }
+sidecard-posts-count =
+    { $count ->
+        [one] { $count } publication
+       *[other] { $count } publications
+    }
+sidecard-moderators-count =
+    { $count ->
+        [one] { $count } modérateur
+       *[other] { $count } modérateurs
+    }
+sidecard-karma-count = { $count } de karma
+sidecard-per-day = { $count } / jour
+sidecard-per-month = { $count } / mois
+
+## Profiles
+
+profile-overview = Aperçu
+profile-posts = Publications
+profile-comments = Commentaires
+profile-saved = Enregistrés
+profile-saved-private = Seul cet utilisateur peut voir ce qu'il a enregistré.
+profile-empty = Rien pour l'instant.
+profile-comment-on = Sur
+profile-comment-in = dans
+profile-comment-context = Contexte
+
+## Admin tools
+
+admin-tools = Outils d'administration
+admin-view-banned = Voir tous les utilisateurs bannis
+admin-manage-emojis = Gérer les émojis personnalisés
+admin-reason = Raison (affichée dans le journal de modération)
+admin-expires = Expire après (en jours, laissez vide pour un bannissement définitif)
+admin-remove-data = Retirer aussi toutes ses publications et tous ses commentaires
+admin-ban = Bannir
+admin-unban = Débannir
+admin-purge = Purger
+admin-restore = Restaurer
+admin-remove = Retirer
+admin-ban-user = Bannir l'utilisateur
+admin-ban-user-title = Bannir l'utilisateur du site
+admin-unban-user = Débannir l'utilisateur
+admin-purge-user = Purger l'utilisateur
+admin-remove-admin = Retirer des administrateurs
+admin-appoint-admin = Nommer administrateur
+admin-leave = Quitter l'équipe d'administration
+admin-restore-community = Restaurer la communauté
+admin-remove-community = Retirer la communauté
+admin-purge-community = Purger la communauté
+admin-purge-post = Purger la publication
+admin-purge-comment = Purger le commentaire
+admin-ban-warning = L'utilisateur sera banni de tout le site. Le contenu retiré ne pourra pas être restauré en le débannissant plus tard.
+admin-unban-warning = L'utilisateur pourra de nouveau publier et commenter sur ce site.
+admin-purge-user-warning = La purge supprime définitivement l'utilisateur et toutes ses publications, tous ses commentaires et tous ses téléversements de la base de données. Cette action est irréversible.
+admin-remove-admin-warning = L'utilisateur perdra toutes ses permissions d'administration sur ce site.
+admin-appoint-admin-warning = L'utilisateur aura toutes les permissions d'administration sur ce site, y compris purger du contenu et bannir d'autres utilisateurs.
+admin-leave-warning = Vous perdrez toutes vos permissions d'administration sur ce site. Seul un autre administrateur pourra vous nommer de nouveau.
+admin-restore-community-warning = La communauté et ses publications seront de nouveau visibles.
+admin-remove-community-warning = La communauté sera masquée pour tout le monde. Elle pourra être restaurée plus tard depuis ce panneau.
+admin-purge-community-warning = La purge supprime définitivement la communauté et toutes ses publications et commentaires de la base de données. Cette action est irréversible.
+admin-purge-post-warning = La purge supprime définitivement la publication et tous ses commentaires de la base de données. Cette action est irréversible.
+admin-purge-comment-warning = La purge supprime définitivement le commentaire de la base de données. Cette action est irréversible.
+admin-user-banned = L'utilisateur a été banni du site.
+admin-user-unbanned = L'utilisateur a été débanni.
+admin-user-purged = L'utilisateur et tout son contenu ont été purgés.
+admin-left = Vous n'êtes plus administrateur de ce site.
+admin-count =
+    { $count ->
+        [one] Le site a maintenant { $count } administrateur.
+       *[other] Le site a maintenant { $count } administrateurs.
+    }
+admin-community-removed = La communauté a été retirée.
+admin-community-restored = La communauté a été restaurée.
+admin-community-purged = La communauté et toutes ses publications ont été purgées.
+admin-post-purged = La publication et ses commentaires ont été purgés.
+admin-comment-purged = Le commentaire a été purgé.
+banned-title = Utilisateurs bannis
+banned-name = Nom
+banned-expires = Fin du bannissement
+banned-never = Jamais
+banned-unban-warning = L'utilisateur pourra de nouveau publier et commenter sur ce site. Le contenu retiré avec le bannissement ne sera pas restauré.
+emojis-add-title = Ajouter un émoji personnalisé
+emojis-none = Ce site n'a pas encore d'émojis personnalisés.
+emojis-emoji = Émoji
+emojis-shortcode = Code court
+emojis-image-url = URL de l'image
+emojis-category = Catégorie
+emojis-alt-text = Texte alternatif
+emojis-keywords = Mots-clés
+emojis-keywords-input = Mots-clés (séparés par des virgules)
+emojis-manage = Gérer
+emojis-save = Enregistrer
+emojis-add = Ajouter l'émoji
+emojis-saved = L'émoji a été enregistré.
+emojis-added = L'émoji a été ajouté.
+
+## Federated instances
+
+instances-federated = Instances fédérées
+instances-blocked = Instances bloquées
+instances-search = Rechercher des instances par domaine
+instances-search-placeholder = Rechercher par domaine…
+instances-all-software = Tous les logiciels
+instances-domain = Nom de domaine
+instances-software = Logiciel
+instances-version = Version
+instances-last-seen = Vue pour la dernière fois
+instances-first-seen = Vue pour la première fois
+instances-unknown-version = Inconnue
+instances-no-matches = Aucune instance ne correspond à ces filtres
+instances-visit = Visiter cette instance
+instances-visit-domain = Visiter { $domain }
+instances-all = Toutes les instances
+instances-not-federated = Cette instance n'a pas fédéré avec nous.
+instances-blocked-badge = Bloquée
+instances-known-communities = Communautés connues
+instances-error-communities = Erreur lors du chargement des communautés de cette instance
+instances-no-communities = Aucune communauté de cette instance n'est connue ici.
+instances-no-more-communities = Il n'y a plus de communautés de cette instance.
+instances-no-communities-looked-through = Aucune des communautés parcourues pour cette page ne vient de cette instance.
+
+## Times
+
+time-just-now = à l'instant
+time-minutes-ago = il y a { $count } min
+time-hours-ago = il y a { $count } h
+time-days-ago =
+    { $count ->
+        [one] il y a { $count } jour
+       *[other] il y a { $count } jours
+    }
+time-months-ago = il y a { $count } mois
+time-years-ago =
+    { $count ->
+        [one] il y a { $count } an
+       *[other] il y a { $count } ans
+    }
+time-edited = modifié { $time }
+time-joined = Inscrit { $time }
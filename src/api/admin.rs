@@ -1 +1,53 @@
+use anyhow::Result;
+use leptos::Scope;
 
+use crate::api::structs::*;
+use crate::api::*;
+
+// These are the helper functions for the admin-only endpoints, which all require the auth token of an admin.
+// Each of the actions taken here are recorded in the mod log by Lemmy, along with the reason given for them.
+
+pub async fn ban_person(cx: Scope, endpoint: &str, form: &BanPerson) -> Result<BanPersonResponse> {
+    api_post::<BanPersonResponse, BanPerson>(cx, endpoint, form).await
+}
+
+pub async fn get_banned_persons(cx: Scope, endpoint: &str) -> Result<BannedPersonsResponse> {
+    api_get::<BannedPersonsResponse>(cx, endpoint).await
+}
+
+pub async fn purge_person(
+    cx: Scope,
+    endpoint: &str,
+    form: &PurgePerson,
+) -> Result<PurgeItemResponse> {
+    api_post::<PurgeItemResponse, PurgePerson>(cx, endpoint, form).await
+}
+
+pub async fn purge_community(
+    cx: Scope,
+    endpoint: &str,
+    form: &PurgeCommunity,
+) -> Result<PurgeItemResponse> {
+    api_post::<PurgeItemResponse, PurgeCommunity>(cx, endpoint, form).await
+}
+
+pub async fn purge_post(cx: Scope, endpoint: &str, form: &PurgePost) -> Result<PurgeItemResponse> {
+    api_post::<PurgeItemResponse, PurgePost>(cx, endpoint, form).await
+}
+
+pub async fn purge_comment(
+    cx: Scope,
+    endpoint: &str,
+    form: &PurgeComment,
+) -> Result<PurgeItemResponse> {
+    api_post::<PurgeItemResponse, PurgeComment>(cx, endpoint, form).await
+}
+
+pub async fn add_admin(cx: Scope, endpoint: &str, form: &AddAdmin) -> Result<AddAdminResponse> {
+    api_post::<AddAdminResponse, AddAdmin>(cx, endpoint, form).await
+}
+
+// Lemmy returns the updated site after an admin steps down, since the admin list has changed
+pub async fn leave_admin(cx: Scope, endpoint: &str, form: &LeaveAdmin) -> Result<GetSiteResponse> {
+    api_post::<GetSiteResponse, LeaveAdmin>(cx, endpoint, form).await
+}
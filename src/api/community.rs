@@ -12,3 +12,21 @@ pub async fn list_communities(cx: Scope, endpoint: &str) -> Result<ListCommuniti
 pub async fn get_community(cx: Scope, endpoint: &str) -> Result<GetCommunityResponse> {
     api_get::<GetCommunityResponse>(cx, endpoint).await
 }
+
+// Removing a community is a moderation action for admins, and can be undone by sending `removed: false`
+pub async fn remove_community(
+    cx: Scope,
+    endpoint: &str,
+    form: &RemoveCommunity,
+) -> Result<CommunityResponse> {
+    api_post::<CommunityResponse, RemoveCommunity>(cx, endpoint, form).await
+}
+
+// Subscribes to or unsubscribes from a community, which needs the auth token of the user doing it
+pub async fn follow_community(
+    cx: Scope,
+    endpoint: &str,
+    form: &FollowCommunity,
+) -> Result<CommunityResponse> {
+    api_post::<CommunityResponse, FollowCommunity>(cx, endpoint, form).await
+}
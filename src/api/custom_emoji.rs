@@ -0,0 +1,32 @@
+use anyhow::Result;
+use leptos::Scope;
+
+use crate::api::structs::*;
+use crate::api::*;
+
+// These are the helper functions for managing the site's custom emojis, which require the auth token of an admin.
+// The emojis themselves are returned to everyone as part of the GetSiteResponse, so there is no separate GET here.
+
+pub async fn create_custom_emoji(
+    cx: Scope,
+    endpoint: &str,
+    form: &CreateCustomEmoji,
+) -> Result<CustomEmojiResponse> {
+    api_post::<CustomEmojiResponse, CreateCustomEmoji>(cx, endpoint, form).await
+}
+
+pub async fn edit_custom_emoji(
+    cx: Scope,
+    endpoint: &str,
+    form: &EditCustomEmoji,
+) -> Result<CustomEmojiResponse> {
+    api_put::<CustomEmojiResponse, EditCustomEmoji>(cx, endpoint, form).await
+}
+
+pub async fn delete_custom_emoji(
+    cx: Scope,
+    endpoint: &str,
+    form: &DeleteCustomEmoji,
+) -> Result<DeleteCustomEmojiResponse> {
+    api_post::<DeleteCustomEmojiResponse, DeleteCustomEmoji>(cx, endpoint, form).await
+}
@@ -0,0 +1,124 @@
+use anyhow::{anyhow, Result};
+
+use crate::api::structs::*;
+
+// Images are uploaded to pictrs through the Wolfrs server rather than straight to the instance, so that the `jwt` cookie applies and the browser doesn't run into CORS.
+// See pictrs.rs for the server side of this.
+pub fn pictrs_proxy_path() -> String {
+    format!("/{}", api_endpoints::PostEndpoint::UPLOAD_IMAGE)
+}
+
+// The path on the Wolfrs server for deleting an uploaded image with its delete token
+pub fn pictrs_delete_proxy_path(image_file: &ImageFile) -> String {
+    format!(
+        "{}/delete/{}/{}",
+        pictrs_proxy_path(),
+        image_file.delete_token,
+        image_file.file
+    )
+}
+
+// Pictrs only returns the file name and delete token, so the full URLs are filled in the same way Lemmy-UI does it
+#[cfg(not(feature = "ssr"))]
+fn with_image_urls(mut response: UploadImageResponse) -> Result<UploadImageResponse> {
+    let instance_url = crate::components::instance::get_instance_details()
+        .unwrap()
+        .url;
+    let image_file = response
+        .files
+        .first()
+        .ok_or_else(|| anyhow!("No image was returned by pictrs: {}", response.msg))?;
+
+    response.url = Some(format!(
+        "{}/{}/{}",
+        instance_url,
+        api_endpoints::PostEndpoint::UPLOAD_IMAGE,
+        image_file.file
+    ));
+    response.delete_url = Some(format!(
+        "{}/{}/delete/{}/{}",
+        instance_url,
+        api_endpoints::PostEndpoint::UPLOAD_IMAGE,
+        image_file.delete_token,
+        image_file.file
+    ));
+
+    Ok(response)
+}
+
+// Uploads an image to pictrs as multipart form data, calling on_progress with the percentage uploaded so far.
+// This uses XMLHttpRequest instead of gloo-net, since fetch doesn't report the progress of an upload.
+#[cfg(not(feature = "ssr"))]
+pub async fn upload_image(
+    file: web_sys::File,
+    on_progress: impl Fn(u32) + 'static,
+) -> Result<UploadImageResponse> {
+    use leptos::Serializable;
+    use wasm_bindgen::{closure::Closure, JsCast};
+
+    let js_error = |err: wasm_bindgen::JsValue| anyhow!("{:?}", err);
+
+    let form_data = web_sys::FormData::new().map_err(js_error)?;
+    form_data
+        .append_with_blob("images[]", &file)
+        .map_err(js_error)?;
+
+    let xhr = web_sys::XmlHttpRequest::new().map_err(js_error)?;
+    xhr.open("POST", &pictrs_proxy_path()).map_err(js_error)?;
+
+    let progress_callback =
+        Closure::<dyn FnMut(web_sys::ProgressEvent)>::new(move |ev: web_sys::ProgressEvent| {
+            if ev.length_computable() && ev.total() > 0.0 {
+                on_progress((ev.loaded() / ev.total() * 100.0) as u32);
+            }
+        });
+    xhr.upload()
+        .map_err(js_error)?
+        .set_onprogress(Some(progress_callback.as_ref().unchecked_ref()));
+
+    // Wraps the load and error events of the request in a promise, so it can be awaited
+    let request = xhr.clone();
+    let finished = js_sys::Promise::new(&mut |resolve, reject| {
+        request.set_onload(Some(&resolve));
+        request.set_onerror(Some(&reject));
+    });
+
+    xhr.send_with_opt_form_data(Some(&form_data))
+        .map_err(js_error)?;
+    wasm_bindgen_futures::JsFuture::from(finished)
+        .await
+        .map_err(|_| anyhow!("The image upload failed"))?;
+    drop(progress_callback);
+
+    let json = xhr.response_text().map_err(js_error)?.unwrap_or_default();
+    let response = UploadImageResponse::de(&json).map_err(|_| anyhow!(json.clone()))?;
+
+    with_image_urls(response)
+}
+
+#[cfg(feature = "ssr")]
+pub async fn upload_image(
+    _file: web_sys::File,
+    _on_progress: impl Fn(u32) + 'static,
+) -> Result<UploadImageResponse> {
+    Err(anyhow!("Images can only be uploaded from the browser"))
+}
+
+// Deletes an uploaded image from pictrs using the delete token it was uploaded with
+#[cfg(not(feature = "ssr"))]
+pub async fn delete_image(image_file: &ImageFile) -> Result<()> {
+    let response = gloo_net::http::Request::delete(&pictrs_delete_proxy_path(image_file))
+        .send()
+        .await?;
+
+    if response.ok() {
+        Ok(())
+    } else {
+        Err(anyhow!(response.text().await?))
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub async fn delete_image(_image_file: &ImageFile) -> Result<()> {
+    Err(anyhow!("Images can only be deleted from the browser"))
+}
@@ -2,7 +2,9 @@
 pub mod admin;
 pub mod comment;
 pub mod community;
+pub mod custom_emoji;
 pub mod federation;
+pub mod image;
 pub mod modlog;
 pub mod posts;
 pub mod private_message;
@@ -35,6 +37,67 @@ pub fn api_url_builder<T: Serializable + serde::Serialize>(
     )
 }
 
+// This function is used to construct the url for POST and PUT requests, which send their form as a JSON body instead of as query params
+pub fn api_post_url_builder(url_constructor: ApiUrlConstructor) -> String {
+    format!(
+        "{}/api/{}/{}",
+        get_instance_details().unwrap().url,
+        get_instance_details().unwrap().api_version,
+        url_constructor.endpoint,
+    )
+}
+
+// Reads the Lemmy auth token from the `jwt` cookie, which is the same cookie the Lemmy UI stores it in.
+// There is no login page yet, so this returns None unless the cookie was set by another UI on the same domain.
+pub fn get_auth_token(cx: Scope) -> Option<String> {
+    get_cookie(cx, "jwt")
+}
+
+// Reads a cookie from the browser, or from the request when rendering on the server, so both render the same thing
+#[cfg(not(feature = "ssr"))]
+pub fn get_cookie(_cx: Scope, name: &str) -> Option<String> {
+    use wasm_bindgen::JsCast;
+
+    let document = leptos::document()
+        .dyn_into::<web_sys::HtmlDocument>()
+        .ok()?;
+    let cookies = document.cookie().ok()?;
+
+    cookie_from_cookies(&cookies, name)
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_cookie(cx: Scope, name: &str) -> Option<String> {
+    let request = leptos::use_context::<leptos_axum::RequestParts>(cx)?;
+    let cookies = request.headers.get(http::header::COOKIE)?.to_str().ok()?;
+
+    cookie_from_cookies(cookies, name)
+}
+
+// Saves a cookie for a year, which is how Wolfrs remembers preferences for users without an account
+#[cfg(not(feature = "ssr"))]
+pub fn set_cookie(name: &str, value: &str) {
+    use wasm_bindgen::JsCast;
+
+    if let Ok(document) = leptos::document().dyn_into::<web_sys::HtmlDocument>() {
+        let _ = document.set_cookie(&format!(
+            "{}={}; path=/; max-age=31536000; samesite=lax",
+            name, value
+        ));
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub fn set_cookie(_name: &str, _value: &str) {}
+
+pub(crate) fn cookie_from_cookies(cookies: &str, name: &str) -> Option<String> {
+    cookies
+        .split(';')
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .find(|(cookie_name, _)| *cookie_name == name)
+        .map(|(_, value)| value.to_string())
+}
+
 // These API functions originate with the Leptos `hackernews_axum` example from the GitHub repo. The Lemy devs simply altered them to work with the Lemmy API, and that's what I have done as well.
 // POST and PUT functionality may be built into these functions as well, or made their own seperate functions. Will determine later on
 #[cfg(not(feature = "ssr"))]
@@ -63,27 +126,81 @@ where
     //Use this to test JSON outputs before unwrapping in case of errors
     //leptos::log!("Test in CSR mod.rs: {:#?}", json.clone());
 
-    //This really isn't good... It has no error handling, but the way it was handled below kept returning false(?) Err even though the data was there
-    Ok(Response::de(&json).unwrap())
-
-    //Return the error response json as an error
-    //Response::de(&json).map_err(|_| anyhow!(json.clone()))
+    // Return the error response json as an error
+    Response::de(&json).map_err(|_| anyhow!(json.clone()))
 }
 
+// Anonymous requests are served from the API cache when their endpoint has a TTL set in Instance.toml
 #[cfg(feature = "ssr")]
-pub async fn api_get<Response>(_cx: Scope, path: &str) -> Result<Response>
+pub async fn api_get<Response>(cx: Scope, path: &str) -> Result<Response>
 where
     Response: Serializable,
 {
-    let client = reqwest::Client::new();
+    let authenticated = get_auth_token(cx).is_some() || path.contains("auth=");
 
-    let json = client.get(path).send().await?.text().await?;
+    let json = crate::api_cache::cached_get(path, authenticated).await?;
 
     //leptos::log!("Test in SSR mod.rs: {:#?}", json.clone());
 
-    // This really isn't good... It has no error handling, but the way it was handled below kept returning false(?) Err even though the data was there
-    Ok(Response::de(&json).unwrap())
-
     // Return the error response json as an error
-    //Response::de(&json).map_err(|_| anyhow!(json.clone()))
+    Response::de(&json).map_err(|_| anyhow!(json.clone()))
+}
+
+// The POST counterparts to api_get, which send the form as JSON and return the error json from Lemmy as an error if the response can't be parsed.
+// Unlike api_get these are not aborted on cleanup, since they are user actions that should finish even if the user navigates away.
+#[cfg(not(feature = "ssr"))]
+pub async fn api_post<Response, Form>(_cx: Scope, path: &str, form: &Form) -> Result<Response>
+where
+    Response: Serializable,
+    Form: serde::Serialize,
+{
+    let json = gloo_net::http::Request::post(path)
+        .json(form)?
+        .send()
+        .await?
+        .text()
+        .await?;
+
+    Response::de(&json).map_err(|_| anyhow!(json.clone()))
+}
+
+#[cfg(feature = "ssr")]
+pub async fn api_post<Response, Form>(_cx: Scope, path: &str, form: &Form) -> Result<Response>
+where
+    Response: Serializable,
+    Form: serde::Serialize,
+{
+    let client = crate::api_cache::http_client();
+    let json = client.post(path).json(form).send().await?.text().await?;
+
+    Response::de(&json).map_err(|_| anyhow!(json.clone()))
+}
+
+// The PUT counterparts to api_post, which Lemmy uses for edits
+#[cfg(not(feature = "ssr"))]
+pub async fn api_put<Response, Form>(_cx: Scope, path: &str, form: &Form) -> Result<Response>
+where
+    Response: Serializable,
+    Form: serde::Serialize,
+{
+    let json = gloo_net::http::Request::put(path)
+        .json(form)?
+        .send()
+        .await?
+        .text()
+        .await?;
+
+    Response::de(&json).map_err(|_| anyhow!(json.clone()))
+}
+
+#[cfg(feature = "ssr")]
+pub async fn api_put<Response, Form>(_cx: Scope, path: &str, form: &Form) -> Result<Response>
+where
+    Response: Serializable,
+    Form: serde::Serialize,
+{
+    let client = crate::api_cache::http_client();
+    let json = client.put(path).json(form).send().await?.text().await?;
+
+    Response::de(&json).map_err(|_| anyhow!(json.clone()))
 }
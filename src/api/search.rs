@@ -0,0 +1,10 @@
+use anyhow::Result;
+use leptos::Scope;
+
+use crate::api::structs::*;
+use crate::api::*;
+
+// This is a helper function for api_get that then returns the SearchResponse from the API Result.
+pub async fn search(cx: Scope, endpoint: &str) -> Result<SearchResponse> {
+    api_get::<SearchResponse>(cx, endpoint).await
+}
@@ -88,7 +88,8 @@ pub mod api_endpoints {
         pub const REMOVE_COMMUNITY: &str = "community/remove";
         pub const REMOVE_POST: &str = "post/remove";
         pub const TRANSFER_COMMUNITY: &str = "community/transfer";
-        pub const UPLOAD_IMAGE: &str = "TBD?";
+        // This is served by the instance's pictrs proxy rather than the API, so it is relative to the root of the instance instead of /api/{version}
+        pub const UPLOAD_IMAGE: &str = "pictrs/image";
         pub const VERIFY_EMAIL: &str = "user/verify_email";
     }
 
@@ -151,7 +152,7 @@ pub struct AddAdmin {
 
 #[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
 pub struct AddAdminResponse {
-    pub admins: PersonView,
+    pub admins: Vec<PersonView>,
 }
 
 #[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
@@ -287,7 +288,7 @@ pub struct BanPersonResponse {
 
 #[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
 pub struct BannedPersonsResponse {
-    pub banned: PersonView,
+    pub banned: Vec<PersonView>,
 }
 
 #[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
@@ -1169,6 +1170,9 @@ pub struct GetPosts {
     pub limit: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub page: Option<i32>,
+    // Only supported from Lemmy 0.19, older versions ignore it and use page instead
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_cursor: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub post_id: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1182,6 +1186,9 @@ pub struct GetPosts {
 #[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
 pub struct GetPostsResponse {
     pub posts: Vec<PostView>,
+    // The page_cursor for the next page, which is only sent from Lemmy 0.19
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_page: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
@@ -2092,6 +2099,7 @@ pub struct PurgeCommunity {
     pub reason: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
 pub struct PurgeItemResponse {
     pub success: bool,
 }
@@ -8,3 +8,12 @@ use crate::api::*;
 pub async fn get_person_details(cx: Scope, endpoint: &str) -> Result<GetPersonDetailsResponse> {
     api_get::<GetPersonDetailsResponse>(cx, endpoint).await
 }
+
+// Saves the logged in user's settings, where fields that are left as None aren't changed
+pub async fn save_user_settings(
+    cx: Scope,
+    endpoint: &str,
+    form: &SaveUserSettings,
+) -> Result<LoginResponse> {
+    api_put::<LoginResponse, SaveUserSettings>(cx, endpoint, form).await
+}
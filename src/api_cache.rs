@@ -0,0 +1,182 @@
+use cfg_if::cfg_if;
+
+// TODO - api_cache.rs:
+// Share one request between everyone waiting on the same uncached response, instead of each of them fetching it
+// Purge the cache when an admin changes something, such as the site sidebar
+
+// Anonymous page views all make the same GetSite, GetPosts, ListCommunities, and GetFederatedInstances calls.
+// Their responses are cached in memory for a short time, set per endpoint in Instance.toml, so a spike of visitors to the front page doesn't turn into a spike of requests to Lemmy.
+// Requests with an auth token are never cached, since their responses are different for every user.
+
+cfg_if! { if #[cfg(feature = "ssr")] {
+    use anyhow::{anyhow, Result};
+    use axum::{
+        http::{header, StatusCode},
+        response::{IntoResponse, Response},
+    };
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Mutex, OnceLock};
+    use std::time::{Duration, Instant};
+
+    use crate::components::instance::*;
+
+    // The most responses kept at once, which stops pages of every community from filling up the server's memory
+    const MAX_CACHE_ENTRIES: usize = 1000;
+
+    struct CachedResponse {
+        body: String,
+        expires: Instant,
+    }
+
+    #[derive(Default)]
+    struct CacheMetrics {
+        hits: AtomicU64,
+        misses: AtomicU64,
+        // Requests that skipped the cache, because they had an auth token or their endpoint isn't cached
+        bypasses: AtomicU64,
+    }
+
+    static CACHE: OnceLock<Mutex<HashMap<String, CachedResponse>>> = OnceLock::new();
+    static METRICS: OnceLock<CacheMetrics> = OnceLock::new();
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+
+    fn cache() -> &'static Mutex<HashMap<String, CachedResponse>> {
+        CACHE.get_or_init(Default::default)
+    }
+
+    fn metrics() -> &'static CacheMetrics {
+        METRICS.get_or_init(Default::default)
+    }
+
+    // The client shared by every request to Lemmy, so connections to the instance are pooled instead of opened for each request
+    pub fn http_client() -> &'static reqwest::Client {
+        CLIENT.get_or_init(reqwest::Client::new)
+    }
+
+    // The endpoint is the part of the URL after /api/{version}/, such as post/list
+    fn endpoint_of(url: &str) -> Option<&str> {
+        let instance = get_instance_details().ok()?;
+        let prefix = format!("{}/api/{}/", instance.url, instance.api_version);
+        let path = url.strip_prefix(&prefix)?;
+        Some(path.split_once('?').map_or(path, |(endpoint, _)| endpoint))
+    }
+
+    // How long responses from the endpoint are cached for, which is nothing for endpoints missing from Instance.toml
+    fn ttl_for(endpoint: &str) -> Option<Duration> {
+        get_instance_details()
+            .ok()?
+            .api_cache_ttls
+            .get(endpoint)
+            .copied()
+            .filter(|seconds| *seconds > 0)
+            .map(Duration::from_secs)
+    }
+
+    // Lemmy's errors come back as json with an error status, which is returned as the error so it is never cached or parsed as a response
+    async fn fetch(url: &str) -> Result<String> {
+        let response = http_client().get(url).send().await?;
+        let status = response.status();
+        let body = response.text().await?;
+        if status.is_success() {
+            Ok(body)
+        } else if body.is_empty() {
+            Err(anyhow!("Lemmy responded with {}", status))
+        } else {
+            Err(anyhow!(body))
+        }
+    }
+
+    fn insert(url: &str, body: String, ttl: Duration) {
+        let Ok(mut cache) = cache().lock() else {
+            return;
+        };
+
+        if cache.len() >= MAX_CACHE_ENTRIES {
+            let now = Instant::now();
+            cache.retain(|_, cached| cached.expires > now);
+        }
+        // If everything is still fresh, the response closest to expiring makes room
+        if cache.len() >= MAX_CACHE_ENTRIES {
+            if let Some(oldest) = cache
+                .iter()
+                .min_by_key(|(_, cached)| cached.expires)
+                .map(|(key, _)| key.clone())
+            {
+                cache.remove(&oldest);
+            }
+        }
+
+        cache.insert(
+            url.to_string(),
+            CachedResponse {
+                body,
+                expires: Instant::now() + ttl,
+            },
+        );
+    }
+
+    // Fetches the body of a GET request to Lemmy, from the cache when it can be.
+    // The full URL is the key, so each endpoint and query string is cached separately. Error responses are returned as errors and never cached.
+    pub async fn cached_get(url: &str, authenticated: bool) -> Result<String> {
+        let ttl = endpoint_of(url).and_then(ttl_for);
+        let Some(ttl) = ttl.filter(|_| !authenticated) else {
+            metrics().bypasses.fetch_add(1, Ordering::Relaxed);
+            return fetch(url).await;
+        };
+
+        let cached = cache()
+            .lock()
+            .map_err(|_| anyhow!("The API cache lock was poisoned"))?
+            .get(url)
+            .filter(|cached| cached.expires > Instant::now())
+            .map(|cached| cached.body.clone());
+        if let Some(body) = cached {
+            metrics().hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(body);
+        }
+
+        metrics().misses.fetch_add(1, Ordering::Relaxed);
+        let body = fetch(url).await?;
+        insert(url, body.clone(), ttl);
+
+        Ok(body)
+    }
+
+    // Reports how well the cache is working, in the Prometheus text format so it can be scraped.
+    // It's only served when api_cache_metrics is turned on in Instance.toml.
+    pub async fn api_cache_metrics() -> Response {
+        let metrics_enabled = get_instance_details()
+            .map(|instance| instance.api_cache_metrics)
+            .unwrap_or_default();
+        if !metrics_enabled {
+            return StatusCode::NOT_FOUND.into_response();
+        }
+
+        let entries = cache().lock().map(|cache| cache.len()).unwrap_or_default();
+        let metrics = metrics();
+
+        (
+            [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+            format!(
+                "# HELP wolfrs_api_cache_hits_total Anonymous API requests served from the cache\n\
+                 # TYPE wolfrs_api_cache_hits_total counter\n\
+                 wolfrs_api_cache_hits_total {}\n\
+                 # HELP wolfrs_api_cache_misses_total Anonymous API requests that had to be fetched from Lemmy\n\
+                 # TYPE wolfrs_api_cache_misses_total counter\n\
+                 wolfrs_api_cache_misses_total {}\n\
+                 # HELP wolfrs_api_cache_bypasses_total API requests that skipped the cache\n\
+                 # TYPE wolfrs_api_cache_bypasses_total counter\n\
+                 wolfrs_api_cache_bypasses_total {}\n\
+                 # HELP wolfrs_api_cache_entries Responses currently held in the cache\n\
+                 # TYPE wolfrs_api_cache_entries gauge\n\
+                 wolfrs_api_cache_entries {}\n",
+                metrics.hits.load(Ordering::Relaxed),
+                metrics.misses.load(Ordering::Relaxed),
+                metrics.bypasses.load(Ordering::Relaxed),
+                entries,
+            ),
+        )
+            .into_response()
+    }
+}}
@@ -4,12 +4,25 @@ use leptos::*;
 use leptos_meta::*;
 use leptos_router::*;
 
-use crate::components::{footer::Footer, header::Header, instance::*};
+use crate::components::{
+    a11y::{FocusOnNavigate, SkipLink, MAIN_CONTENT_ID},
+    footer::Footer,
+    header::Header,
+    i18n::{provide_i18n, use_locale},
+    instance::*,
+    seo::{page_title, InstanceRobots},
+    shortcuts::{provide_shortcuts, KeyboardShortcuts},
+    site::provide_site,
+    theme::{provide_theme, theme_attributes, ThemeHead},
+    time_ago::provide_clock,
+};
 
 // Remove in favour of the boilerplate import below once all pages are ready
+use crate::pages::banned_users::BannedUsers;
 use crate::pages::communities::Communities;
 use crate::pages::community::Community;
-use crate::pages::federated_instances::FederatedInstancesList;
+use crate::pages::custom_emojis::ManageCustomEmojis;
+use crate::pages::federated_instances::{FederatedInstance, FederatedInstancesList};
 use crate::pages::home::Home;
 use crate::pages::mod_log::ModLog;
 use crate::pages::post::Post;
@@ -27,14 +40,29 @@ pub fn App(cx: Scope) -> impl IntoView {
     // Provides context that manages stylesheets, titles, meta tags, etc.
     provide_meta_context(cx);
 
+    // Provides the site from GetSite to everything that needs it, such as the custom emojis for Markdown and the current user's NSFW setting
+    provide_site(cx);
+
+    // Provides the light, dark, or auto theme, which is read from a cookie and from the logged in user's settings
+    provide_theme(cx);
+
+    // Provides the language of the UI, which is picked from the user's settings, a cookie, or the Accept-Language header
+    provide_i18n(cx);
+
+    // Provides the clock that keeps relative times such as "3h ago" up to date while the page is open
+    provide_clock(cx);
+
+    // Provides whether the keyboard shortcuts are turned on, which is read from a cookie
+    provide_shortcuts(cx);
+
     // Get the instance details from the Instance.toml file and assign them to variables for use in the template
     let favicon = get_instance_details().unwrap().favicon_name;
     let title = get_instance_details().unwrap().name;
     let slogan = get_instance_details().unwrap().slogan;
 
     view! { cx,
-        // Sets the Bootstrap dark-theme attribute
-        <Html attributes=AdditionalAttributes::from(vec![("data-bs-theme", "dark")])/>
+        // Sets the language of the page, and the Bootstrap theme attribute from the user's chosen theme
+        <Html lang=move || use_locale(cx).code().to_string() attributes=theme_attributes(cx)/>
 
         // Sets the favicon, which seems to work best as an .ico file
         <Link rel="icon" type_="image/x-icon" href=format!("/assets/{}", favicon)/>
@@ -47,11 +75,24 @@ pub fn App(cx: Scope) -> impl IntoView {
             href="https://cdn.jsdelivr.net/npm/bootstrap@5.3.0/dist/css/bootstrap.min.css"
         />
         <Style>
-            "body {display: flex; min-height: 100vh; flex-direction: column;} main {flex: 1 0 auto;} .markdown>p>img {height: auto; max-width: 100%;}"
+            "body {display: flex; min-height: 100vh; flex-direction: column;} main {flex: 1 0 auto;} .markdown>p>img {height: auto; max-width: 100%;} .markdown img.custom-emoji {height: 1.5em; width: auto; vertical-align: middle;} .markdown blockquote {border-left: .25rem solid var(--bs-border-color); padding-left: 1rem; color: var(--bs-secondary-color);} .markdown details {margin-bottom: 1rem;} .markdown summary {cursor: pointer;} .nsfw-blur {filter: blur(1.5rem); pointer-events: none;} .nsfw-hidden {min-height: 100px;} td .nsfw-hidden {min-height: 0; width: 32px; height: 32px;} main:focus {outline: none;} [data-feed-item]:focus-visible, [data-comment]:focus-visible {outline: 2px solid var(--bs-primary); outline-offset: 2px;} @media (prefers-reduced-motion: reduce) {*, *::before, *::after {animation-duration: 0.01ms !important; animation-iteration-count: 1 !important; transition-duration: 0.01ms !important; scroll-behavior: auto !important;}}"
         </Style>
 
-        // Dynamically sets the title of the page based on the name and slogan defined in the Instance.toml file
-        <Title text=format!("{}: {}", title, slogan)/>
+        // Adds the instance's name to the title each page sets, or uses the name and slogan defined in the Instance.toml file for pages that don't set one
+        <Title formatter=move |text: String| page_title(&text, &title, &slogan)/>
+
+        // Keeps search engines out of private instances
+        <InstanceRobots/>
+
+        // Applies the Auto theme before anything is shown, and the instance's colours and font from Instance.toml
+        <ThemeHead/>
+
+        // The instance's configuration, which the browser reads from here instead of having it compiled into the WASM
+        <script
+            id=INSTANCE_ELEMENT_ID
+            type="application/json"
+            inner_html=instance_details_json()
+        ></script>
 
         // Primary Router for the site
         <Router fallback=|cx| {
@@ -60,31 +101,42 @@ pub fn App(cx: Scope) -> impl IntoView {
             view! { cx, <ErrorTemplate outside_errors/> }.into_view(cx)
         }>
 
+            // Lets keyboard users jump past the header, and moves focus to the new page after navigating
+            <SkipLink/>
+            <FocusOnNavigate/>
+            // The keyboard shortcuts, and their help when ? is pressed
+            <KeyboardShortcuts/>
             <Header/>
-            <main>
+            // Focusable so the skip link and FocusOnNavigate can move focus to it, without it being a stop when tabbing
+            <main id=MAIN_CONTENT_ID tabindex="-1">
+                // The pages about one post, community, or user wait for it to load before being sent, so their title and link preview tags are in the <head>
                 <Routes>
                     <Route path="/" view=|cx| view! { cx, <Home/> }/>
+                    <Route path="/admin/emojis" view=|cx| view! { cx, <ManageCustomEmojis/> }/>
+                    <Route path="/banned" view=|cx| view! { cx, <BannedUsers/> }/>
                     <Route path="/communities" view=|cx| view! { cx, <Communities/> }/>
-                    <Route path="/community/:community_name" view=|cx| view! { cx, <Community/> }/>
+                    <Route path="/community/:community_name" view=|cx| view! { cx, <Community/> } ssr=SsrMode::Async/>
                     // Fallback to support typical Lemmy /c/ url's
-                    <Route path="/c/:community_name" view=|cx| view! { cx, <Community/> }/>
+                    <Route path="/c/:community_name" view=|cx| view! { cx, <Community/> } ssr=SsrMode::Async/>
                     // <Route path="/create_community" view=|cx| view! { cx, <CreateCommunity/> }/>
                     // <Route path="/create_post" view=|cx| view! { cx, <CreatePost/> }/>
                     <Route path="/federation" view=|cx| view! { cx, <FederatedInstancesList/> }/>
                     // Fallback to support typical URL for Lemmy federated instances view
                     <Route path="/instances" view=|cx| view! { cx, <FederatedInstancesList/> }/>
+                    <Route path="/federation/:domain" view=|cx| view! { cx, <FederatedInstance/> }/>
+                    <Route path="/instances/:domain" view=|cx| view! { cx, <FederatedInstance/> }/>
                     // <Route path="/login" view=|cx| view! { cx, <Login/> }/>
                     <Route path="/modlog" view=|cx| view! { cx, <ModLog/> }/>
                     // <Route path="/notifications" view=|cx| view! { cx, <Notifications/> }/>
-                    <Route path="/post/:id" view=|cx| view! { cx, <Post/> }/>
+                    <Route path="/post/:id" view=|cx| view! { cx, <Post/> } ssr=SsrMode::Async/>
                     // Fallback for accidental usage of /p/ url's
-                    <Route path="/p/:id" view=|cx| view! { cx, <Post/> }/>
+                    <Route path="/p/:id" view=|cx| view! { cx, <Post/> } ssr=SsrMode::Async/>
                     // <Route path="/reports" view=|cx| view! { cx, <Reports/> }/>
                     // <Route path="/search" view=|cx| view! { cx, <Search/> }/>
                     // <Route path="/settings" view=|cx| view! { cx, <Settings/> }/>
-                    <Route path="/user/:username" view=|cx| view! { cx, <User/> }/>
+                    <Route path="/user/:username" view=|cx| view! { cx, <User/> } ssr=SsrMode::Async/>
                     // Fallback to support typical Lemmy /u/ url's
-                    <Route path="/u/:username" view=|cx| view! { cx, <User/> }/>
+                    <Route path="/u/:username" view=|cx| view! { cx, <User/> } ssr=SsrMode::Async/>
                 </Routes>
             </main>
             <Footer/>
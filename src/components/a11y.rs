@@ -0,0 +1,137 @@
+use leptos::*;
+use leptos_router::*;
+
+use crate::components::i18n::t;
+
+// TODO - a11y.rs:
+// Announce when the next page of an infinite scrolling feed has loaded
+
+// The id of the <main> element, which the skip link jumps to and focus is moved to after navigating
+pub const MAIN_CONTENT_ID: &str = "main-content";
+
+// The attribute each post in a feed has, which j and k move between
+pub const FEED_ITEM_ATTRIBUTE: &str = "data-feed-item";
+
+// The same for each comment on a post's page
+pub const COMMENT_ATTRIBUTE: &str = "data-comment";
+
+// Everything j and k move between
+#[cfg(not(feature = "ssr"))]
+fn item_selector() -> String {
+    format!("[{}], [{}]", FEED_ITEM_ATTRIBUTE, COMMENT_ATTRIBUTE)
+}
+
+// The first thing on the page for keyboard users, which skips past the header. It is hidden until it is focused.
+#[component]
+pub fn SkipLink(cx: Scope) -> impl IntoView {
+    view! { cx,
+        <a class="visually-hidden-focusable position-absolute top-0 start-0 m-2 p-2 bg-body border rounded" href=format!("#{}", MAIN_CONTENT_ID)>
+            {t(cx, "skip-to-content")}
+        </a>
+    }
+}
+
+// Moves focus to the main content after each navigation, so screen readers start reading the new page instead of staying on the link that was clicked.
+// It only watches the path, so changing the sort or page of a feed leaves focus where it is. Needs to be inside the Router.
+#[component]
+pub fn FocusOnNavigate(cx: Scope) -> impl IntoView {
+    let location = use_location(cx);
+
+    create_effect(cx, move |previous: Option<String>| {
+        let path = location.pathname.get();
+        // The first page load is left alone, since the browser already starts at the top of the page
+        if previous.is_some_and(|previous| previous != path) {
+            focus_main_content();
+        }
+        path
+    });
+}
+
+#[cfg(not(feature = "ssr"))]
+fn focus_main_content() {
+    use wasm_bindgen::JsCast;
+
+    if let Some(main) = document()
+        .get_element_by_id(MAIN_CONTENT_ID)
+        .and_then(|main| main.dyn_into::<web_sys::HtmlElement>().ok())
+    {
+        let _ = main.focus();
+    }
+}
+
+#[cfg(feature = "ssr")]
+fn focus_main_content() {}
+
+// Whether a key press was meant for something else, such as typing in a text box or a shortcut with Ctrl, so it shouldn't move around the page
+#[cfg(not(feature = "ssr"))]
+pub fn is_typing(ev: &web_sys::KeyboardEvent) -> bool {
+    ev.ctrl_key() || ev.meta_key() || ev.alt_key() || ev.default_prevented() || in_text_field(ev)
+}
+
+// Whether a key was pressed in a text box, select, or anything else that takes typing
+#[cfg(not(feature = "ssr"))]
+pub fn in_text_field(ev: &web_sys::KeyboardEvent) -> bool {
+    use wasm_bindgen::JsCast;
+
+    ev.target()
+        .and_then(|target| target.dyn_into::<web_sys::HtmlElement>().ok())
+        .is_some_and(|target| {
+            target.is_content_editable()
+                || matches!(
+                    target.tag_name().to_lowercase().as_str(),
+                    "input" | "textarea" | "select"
+                )
+        })
+}
+
+// The post or comment that has focus, or that something with focus is inside of
+#[cfg(not(feature = "ssr"))]
+pub fn focused_item() -> Option<web_sys::Element> {
+    document()
+        .active_element()?
+        .closest(&item_selector())
+        .ok()
+        .flatten()
+}
+
+// Moves focus to the next or previous post or comment, or to the first one if none has focus yet.
+// Returns false if there is nothing on the page to move between.
+#[cfg(not(feature = "ssr"))]
+pub fn focus_item(forward: bool) -> bool {
+    use wasm_bindgen::JsCast;
+
+    let Ok(items) = document().query_selector_all(&item_selector()) else {
+        return false;
+    };
+    let items = (0..items.length())
+        .filter_map(|index| items.item(index))
+        .filter_map(|item| item.dyn_into::<web_sys::HtmlElement>().ok())
+        .collect::<Vec<_>>();
+
+    let current = focused_item()
+        .and_then(|active| items.iter().position(|item| item.is_same_node(Some(active.as_ref()))));
+    let next = match (current, forward) {
+        (None, _) => 0,
+        (Some(current), true) => (current + 1).min(items.len().saturating_sub(1)),
+        (Some(current), false) => current.saturating_sub(1),
+    };
+
+    match items.get(next) {
+        Some(item) => item.focus().is_ok(),
+        None => false,
+    }
+}
+
+// Listens for key presses anywhere on the page until the component that called it is unmounted.
+// Leptos' window_event_listener can't be removed again, so the listener is added and removed here instead.
+#[cfg(not(feature = "ssr"))]
+pub fn on_window_keydown(cx: Scope, handler: impl Fn(web_sys::KeyboardEvent) + 'static) {
+    use wasm_bindgen::{closure::Closure, JsCast};
+
+    let listener = Closure::<dyn Fn(web_sys::KeyboardEvent)>::new(handler);
+    let _ = window().add_event_listener_with_callback("keydown", listener.as_ref().unchecked_ref());
+    on_cleanup(cx, move || {
+        let _ = window()
+            .remove_event_listener_with_callback("keydown", listener.as_ref().unchecked_ref());
+    });
+}
@@ -0,0 +1,626 @@
+use leptos::*;
+
+use crate::api::admin::*;
+use crate::api::community::remove_community;
+use crate::api::structs::*;
+use crate::api::*;
+use crate::components::i18n::{t, translate, translate_with};
+use crate::components::site::use_site_resource;
+use crate::timestamps::now_unix;
+
+// TODO - admin.rs:
+// Hide the tools for actions that don't apply, such as banning another admin
+// Refresh the sidecards after an action instead of requiring a page reload
+
+// The inputs collected by the confirmation modal, which are shared between all of the admin actions
+#[derive(Clone, Debug, Default)]
+pub struct AdminActionInput {
+    pub reason: Option<String>,
+    pub expires_days: Option<i32>,
+    pub remove_data: bool,
+}
+
+// The result of an admin action, either a message to show on success or the error returned from Lemmy
+pub type AdminAction = Action<AdminActionInput, Result<String, String>>;
+
+// The current user if they are an admin, taken from the site context, so the admin tools can be hidden from everyone else
+#[derive(Copy, Clone)]
+pub struct AdminUser(Resource<(), Option<GetSiteResponse>>);
+
+impl AdminUser {
+    // Like reading a resource, this is None while the site is loading and Some(None) for anyone who isn't an admin
+    pub fn read(&self, cx: Scope) -> Option<Option<Person>> {
+        self.0.with(cx, |site| {
+            site.as_ref()
+                .and_then(|site| site.my_user.as_ref())
+                .map(|my_user| my_user.local_user_view.person.clone())
+                .filter(|person| person.admin)
+        })
+    }
+}
+
+pub fn get_admin_user(cx: Scope) -> AdminUser {
+    AdminUser(use_site_resource(cx))
+}
+
+// Lemmy expects ban expiry as a unix timestamp in seconds, so the number of days entered is added onto the current time.
+// Lemmy's timestamp is an i32, so a ban too long to fit ends at the latest time it can hold rather than wrapping into the past.
+fn expiry_timestamp(days: i32) -> i32 {
+    i32::try_from(now_unix() + i64::from(days) * 86400).unwrap_or(i32::MAX)
+}
+
+// Reason fields are optional for Lemmy, so empty text is sent as None rather than an empty reason in the mod log
+fn reason_or_none(reason: String) -> Option<String> {
+    let reason = reason.trim().to_string();
+    if reason.is_empty() {
+        None
+    } else {
+        Some(reason)
+    }
+}
+
+// The Bootstrap modal that confirms an admin action, and collects the reason that is recorded in the mod log
+#[component]
+pub fn AdminActionModal(
+    cx: Scope,
+    modal_id: String,
+    title: String,
+    warning: String,
+    confirm_label: String,
+    action: AdminAction,
+    #[prop(optional)] show_expiry: bool,
+    #[prop(optional)] show_remove_data: bool,
+) -> impl IntoView {
+    let (reason, set_reason) = create_signal(cx, String::new());
+    let (expires_days, set_expires_days) = create_signal(cx, String::new());
+    let (remove_data, set_remove_data) = create_signal(cx, false);
+
+    let pending = action.pending();
+    let result = action.value();
+
+    let label_id = format!("{}-label", modal_id);
+
+    view! { cx,
+        <div
+            class="modal fade"
+            id=modal_id.clone()
+            tabindex="-1"
+            aria-labelledby=label_id.clone()
+            aria-hidden="true"
+        >
+            <div class="modal-dialog">
+                <div class="modal-content">
+                    <div class="modal-header">
+                        <h1 class="modal-title fs-5" id=label_id>
+                            {title}
+                        </h1>
+                        <button
+                            type="button"
+                            class="btn-close"
+                            data-bs-dismiss="modal"
+                            aria-label=t(cx, "close")
+                        ></button>
+                    </div>
+                    <div class="modal-body">
+                        <div class="alert alert-danger" role="alert">
+                            <i aria-hidden="true" class="bi bi-exclamation-triangle-fill"></i>
+                            " "
+                            {warning}
+                        </div>
+                        <div class="mb-3">
+                            <label for=format!("{}-reason", modal_id) class="form-label">
+                                {t(cx, "admin-reason")}
+                            </label>
+                            <textarea
+                                class="form-control"
+                                id=format!("{}-reason", modal_id)
+                                rows="3"
+                                prop:value=move || reason.get()
+                                on:input=move |ev| set_reason.set(event_target_value(&ev))
+                            ></textarea>
+                        </div>
+                        {if show_expiry {
+                            view! { cx,
+                                <div class="mb-3">
+                                    <label for=format!("{}-expires", modal_id) class="form-label">
+                                        {t(cx, "admin-expires")}
+                                    </label>
+                                    <input
+                                        type="number"
+                                        min="1"
+                                        class="form-control"
+                                        id=format!("{}-expires", modal_id)
+                                        prop:value=move || expires_days.get()
+                                        on:input=move |ev| set_expires_days.set(event_target_value(&ev))
+                                    />
+                                </div>
+                            }
+                                .into_view(cx)
+                        } else {
+                            ().into_view(cx)
+                        }}
+                        {if show_remove_data {
+                            view! { cx,
+                                <div class="form-check">
+                                    <input
+                                        class="form-check-input"
+                                        type="checkbox"
+                                        id=format!("{}-remove-data", modal_id)
+                                        prop:checked=move || remove_data.get()
+                                        on:change=move |ev| set_remove_data.set(event_target_checked(&ev))
+                                    />
+                                    <label class="form-check-label" for=format!("{}-remove-data", modal_id)>
+                                        {t(cx, "admin-remove-data")}
+                                    </label>
+                                </div>
+                            }
+                                .into_view(cx)
+                        } else {
+                            ().into_view(cx)
+                        }}
+                        {move || match result.get() {
+                            Some(Ok(message)) => {
+                                view! { cx, <div class="alert alert-success mt-3">{message}</div> }
+                                    .into_view(cx)
+                            }
+                            Some(Err(error)) => {
+                                view! { cx, <div class="alert alert-warning mt-3">{error}</div> }
+                                    .into_view(cx)
+                            }
+                            None => ().into_view(cx),
+                        }}
+                    </div>
+                    <div class="modal-footer">
+                        <button type="button" class="btn btn-secondary" data-bs-dismiss="modal">
+                            {t(cx, "cancel")}
+                        </button>
+                        <button
+                            type="button"
+                            class="btn btn-danger"
+                            disabled=move || pending.get()
+                            on:click=move |_| {
+                                action
+                                    .dispatch(AdminActionInput {
+                                        reason: reason_or_none(reason.get()),
+                                        expires_days: expires_days
+                                            .get()
+                                            .trim()
+                                            .parse::<i32>()
+                                            .ok()
+                                            .filter(|days| *days > 0),
+                                        remove_data: remove_data.get(),
+                                    })
+                            }
+                        >
+                            {confirm_label}
+                        </button>
+                    </div>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+// The button that opens one of the admin action modals
+#[component]
+fn AdminActionButton(cx: Scope, modal_id: String, label: String) -> impl IntoView {
+    view! { cx,
+        <button
+            type="button"
+            class="btn btn-outline-danger btn-sm me-1 mb-1"
+            data-bs-toggle="modal"
+            data-bs-target=format!("#{}", modal_id)
+        >
+            {label}
+        </button>
+    }
+}
+
+// The admin tools shown on the user sidecard for banning, purging, and appointing admins
+#[component]
+pub fn PersonAdminTools(cx: Scope, person: Person) -> impl IntoView {
+    let admin_user = get_admin_user(cx);
+
+    let person_id = person.id;
+    let banned = person.banned;
+    let is_admin = person.admin;
+
+    let ban_action: AdminAction = create_action(cx, move |input: &AdminActionInput| {
+        let input = input.clone();
+        async move {
+            let url_constructor = ApiUrlConstructor {
+                endpoint: api_endpoints::PostEndpoint::BAN_PERSON.to_string(),
+                id: None,
+                params: None,
+            };
+
+            let form = BanPerson {
+                auth: get_auth_token(cx).unwrap_or_default(),
+                ban: !banned,
+                expires: input.expires_days.map(expiry_timestamp),
+                person_id,
+                reason: input.reason,
+                remove_data: Some(input.remove_data),
+            };
+
+            ban_person(cx, &api_post_url_builder(url_constructor), &form)
+                .await
+                .map(|res| match res.banned {
+                    true => translate(cx, "admin-user-banned"),
+                    false => translate(cx, "admin-user-unbanned"),
+                })
+                .map_err(|err| err.to_string())
+        }
+    });
+
+    let purge_action: AdminAction = create_action(cx, move |input: &AdminActionInput| {
+        let input = input.clone();
+        async move {
+            let url_constructor = ApiUrlConstructor {
+                endpoint: api_endpoints::PostEndpoint::PURGE_PERSON.to_string(),
+                id: None,
+                params: None,
+            };
+
+            let form = PurgePerson {
+                auth: get_auth_token(cx).unwrap_or_default(),
+                person_id,
+                reason: input.reason,
+            };
+
+            purge_person(cx, &api_post_url_builder(url_constructor), &form)
+                .await
+                .map(|_| translate(cx, "admin-user-purged"))
+                .map_err(|err| err.to_string())
+        }
+    });
+
+    let add_admin_action: AdminAction =
+        create_action(cx, move |_input: &AdminActionInput| async move {
+            let url_constructor = ApiUrlConstructor {
+                endpoint: api_endpoints::PostEndpoint::ADD_ADMIN.to_string(),
+                id: None,
+                params: None,
+            };
+
+            let form = AddAdmin {
+                added: !is_admin,
+                auth: get_auth_token(cx).unwrap_or_default(),
+                person_id,
+            };
+
+            add_admin(cx, &api_post_url_builder(url_constructor), &form)
+                .await
+                .map(|res| translate_with(cx, "admin-count", &[("count", res.admins.len().into())]))
+                .map_err(|err| err.to_string())
+        });
+
+    let leave_admin_action: AdminAction =
+        create_action(cx, move |_input: &AdminActionInput| async move {
+            let url_constructor = ApiUrlConstructor {
+                endpoint: api_endpoints::PostEndpoint::LEAVE_ADMIN.to_string(),
+                id: None,
+                params: None,
+            };
+
+            let form = LeaveAdmin {
+                auth: get_auth_token(cx).unwrap_or_default(),
+            };
+
+            leave_admin(cx, &api_post_url_builder(url_constructor), &form)
+                .await
+                .map(|_| translate(cx, "admin-left"))
+                .map_err(|err| err.to_string())
+        });
+
+    view! { cx,
+        <Transition fallback=move || ()>
+            {move || {
+                admin_user
+                    .read(cx)
+                    .flatten()
+                    .map(|admin| {
+                        let ban_id = format!("ban-person-{}", person_id);
+                        let purge_id = format!("purge-person-{}", person_id);
+                        let add_admin_id = format!("add-admin-{}", person_id);
+                        let leave_admin_id = format!("leave-admin-{}", person_id);
+
+                        view! { cx,
+                            <div>
+                                <hr/>
+                                <h6>{t(cx, "admin-tools")}</h6>
+                                <p>
+                                    <a href="/banned">{t(cx, "admin-view-banned")}</a>
+                                    <br/>
+                                    <a href="/admin/emojis">{t(cx, "admin-manage-emojis")}</a>
+                                </p>
+                                {if admin.id == person_id {
+                                    // Admins can only step down themselves, not ban or purge their own account
+                                    view! { cx,
+                                        <div>
+                                            <AdminActionButton
+                                                modal_id=leave_admin_id.clone()
+                                                label=translate(cx, "admin-leave")
+                                            />
+                                            <AdminActionModal
+                                                modal_id=leave_admin_id
+                                                title=translate(cx, "admin-leave")
+                                                warning=translate(cx, "admin-leave-warning")
+                                                confirm_label=translate(cx, "admin-leave")
+                                                action=leave_admin_action
+                                            />
+                                        </div>
+                                    }
+                                } else {
+                                    view! { cx,
+                                        <div>
+                                            <AdminActionButton
+                                                modal_id=ban_id.clone()
+                                                label=if banned { translate(cx, "admin-unban-user") } else { translate(cx, "admin-ban-user") }
+                                            />
+                                            <AdminActionButton
+                                                modal_id=purge_id.clone()
+                                                label=translate(cx, "admin-purge-user")
+                                            />
+                                            <AdminActionButton
+                                                modal_id=add_admin_id.clone()
+                                                label=if is_admin { translate(cx, "admin-remove-admin") } else { translate(cx, "admin-appoint-admin") }
+                                            />
+                                            <AdminActionModal
+                                                modal_id=ban_id
+                                                title=if banned { translate(cx, "admin-unban-user") } else { translate(cx, "admin-ban-user-title") }
+                                                warning=if banned {
+                                                    translate(cx, "admin-unban-warning")
+                                                } else {
+                                                    translate(cx, "admin-ban-warning")
+                                                }
+                                                confirm_label=if banned { translate(cx, "admin-unban") } else { translate(cx, "admin-ban") }
+                                                action=ban_action
+                                                show_expiry=!banned
+                                                show_remove_data=!banned
+                                            />
+                                            <AdminActionModal
+                                                modal_id=purge_id
+                                                title=translate(cx, "admin-purge-user")
+                                                warning=translate(cx, "admin-purge-user-warning")
+                                                confirm_label=translate(cx, "admin-purge")
+                                                action=purge_action
+                                            />
+                                            <AdminActionModal
+                                                modal_id=add_admin_id
+                                                title=if is_admin { translate(cx, "admin-remove-admin") } else { translate(cx, "admin-appoint-admin") }
+                                                warning=if is_admin {
+                                                    translate(cx, "admin-remove-admin-warning")
+                                                } else {
+                                                    translate(cx, "admin-appoint-admin-warning")
+                                                }
+                                                confirm_label=translate(cx, "confirm")
+                                                action=add_admin_action
+                                            />
+                                        </div>
+                                    }
+                                }}
+                            </div>
+                        }
+                    })
+            }}
+        </Transition>
+    }
+}
+
+// The admin tools shown on the community sidecards for removing and purging a community
+#[component]
+pub fn CommunityAdminTools(cx: Scope, community: Community) -> impl IntoView {
+    let admin_user = get_admin_user(cx);
+
+    let community_id = community.id;
+    let removed = community.removed;
+
+    let remove_action: AdminAction = create_action(cx, move |input: &AdminActionInput| {
+        let input = input.clone();
+        async move {
+            let url_constructor = ApiUrlConstructor {
+                endpoint: api_endpoints::PostEndpoint::REMOVE_COMMUNITY.to_string(),
+                id: None,
+                params: None,
+            };
+
+            let form = RemoveCommunity {
+                auth: get_auth_token(cx).unwrap_or_default(),
+                community_id,
+                expires: None,
+                reason: input.reason,
+                removed: !removed,
+            };
+
+            remove_community(cx, &api_post_url_builder(url_constructor), &form)
+                .await
+                .map(|res| match res.community_view.community.removed {
+                    true => translate(cx, "admin-community-removed"),
+                    false => translate(cx, "admin-community-restored"),
+                })
+                .map_err(|err| err.to_string())
+        }
+    });
+
+    let purge_action: AdminAction = create_action(cx, move |input: &AdminActionInput| {
+        let input = input.clone();
+        async move {
+            let url_constructor = ApiUrlConstructor {
+                endpoint: api_endpoints::PostEndpoint::PURGE_COMMUNITY.to_string(),
+                id: None,
+                params: None,
+            };
+
+            let form = PurgeCommunity {
+                auth: get_auth_token(cx).unwrap_or_default(),
+                community_id,
+                reason: input.reason,
+            };
+
+            purge_community(cx, &api_post_url_builder(url_constructor), &form)
+                .await
+                .map(|_| translate(cx, "admin-community-purged"))
+                .map_err(|err| err.to_string())
+        }
+    });
+
+    view! { cx,
+        <Transition fallback=move || ()>
+            {move || {
+                admin_user
+                    .read(cx)
+                    .flatten()
+                    .map(|_| {
+                        let remove_id = format!("remove-community-{}", community_id);
+                        let purge_id = format!("purge-community-{}", community_id);
+
+                        view! { cx,
+                            <div>
+                                <hr/>
+                                <h6>{t(cx, "admin-tools")}</h6>
+                                <AdminActionButton
+                                    modal_id=remove_id.clone()
+                                    label=if removed { translate(cx, "admin-restore-community") } else { translate(cx, "admin-remove-community") }
+                                />
+                                <AdminActionButton
+                                    modal_id=purge_id.clone()
+                                    label=translate(cx, "admin-purge-community")
+                                />
+                                <AdminActionModal
+                                    modal_id=remove_id
+                                    title=if removed { translate(cx, "admin-restore-community") } else { translate(cx, "admin-remove-community") }
+                                    warning=if removed {
+                                        translate(cx, "admin-restore-community-warning")
+                                    } else {
+                                        translate(cx, "admin-remove-community-warning")
+                                    }
+                                    confirm_label=if removed { translate(cx, "admin-restore") } else { translate(cx, "admin-remove") }
+                                    action=remove_action
+                                />
+                                <AdminActionModal
+                                    modal_id=purge_id
+                                    title=translate(cx, "admin-purge-community")
+                                    warning=translate(cx, "admin-purge-community-warning")
+                                    confirm_label=translate(cx, "admin-purge")
+                                    action=purge_action
+                                />
+                            </div>
+                        }
+                    })
+            }}
+        </Transition>
+    }
+}
+
+// The admin tools shown on the post sidecard for purging the post being viewed
+#[component]
+pub fn PostAdminTools(cx: Scope, post_id: i32) -> impl IntoView {
+    let admin_user = get_admin_user(cx);
+
+    let purge_action: AdminAction = create_action(cx, move |input: &AdminActionInput| {
+        let input = input.clone();
+        async move {
+            let url_constructor = ApiUrlConstructor {
+                endpoint: api_endpoints::PostEndpoint::PURGE_POST.to_string(),
+                id: None,
+                params: None,
+            };
+
+            let form = PurgePost {
+                auth: get_auth_token(cx).unwrap_or_default(),
+                post_id,
+                reason: input.reason,
+            };
+
+            purge_post(cx, &api_post_url_builder(url_constructor), &form)
+                .await
+                .map(|_| translate(cx, "admin-post-purged"))
+                .map_err(|err| err.to_string())
+        }
+    });
+
+    view! { cx,
+        <Transition fallback=move || ()>
+            {move || {
+                admin_user
+                    .read(cx)
+                    .flatten()
+                    .map(|_| {
+                        let purge_id = format!("purge-post-{}", post_id);
+
+                        view! { cx,
+                            <div>
+                                <hr/>
+                                <h6>{t(cx, "admin-tools")}</h6>
+                                <AdminActionButton modal_id=purge_id.clone() label=translate(cx, "admin-purge-post")/>
+                                <AdminActionModal
+                                    modal_id=purge_id
+                                    title=translate(cx, "admin-purge-post")
+                                    warning=translate(cx, "admin-purge-post-warning")
+                                    confirm_label=translate(cx, "admin-purge")
+                                    action=purge_action
+                                />
+                            </div>
+                        }
+                    })
+            }}
+        </Transition>
+    }
+}
+
+// The purge button shown on each comment for admins, which takes the admin resource from the comments list so it is only fetched once per post
+#[component]
+pub fn CommentAdminTools(
+    cx: Scope,
+    comment_id: i32,
+    admin_user: AdminUser,
+) -> impl IntoView {
+    let purge_action: AdminAction = create_action(cx, move |input: &AdminActionInput| {
+        let input = input.clone();
+        async move {
+            let url_constructor = ApiUrlConstructor {
+                endpoint: api_endpoints::PostEndpoint::PURGE_COMMENT.to_string(),
+                id: None,
+                params: None,
+            };
+
+            let form = PurgeComment {
+                auth: get_auth_token(cx).unwrap_or_default(),
+                comment_id,
+                reason: input.reason,
+            };
+
+            purge_comment(cx, &api_post_url_builder(url_constructor), &form)
+                .await
+                .map(|_| translate(cx, "admin-comment-purged"))
+                .map_err(|err| err.to_string())
+        }
+    });
+
+    view! { cx,
+        <Transition fallback=move || ()>
+            {move || {
+                admin_user
+                    .read(cx)
+                    .flatten()
+                    .map(|_| {
+                        let purge_id = format!("purge-comment-{}", comment_id);
+
+                        view! { cx,
+                            <div class="card-footer">
+                                <AdminActionButton modal_id=purge_id.clone() label=translate(cx, "admin-purge-comment")/>
+                                <AdminActionModal
+                                    modal_id=purge_id
+                                    title=translate(cx, "admin-purge-comment")
+                                    warning=translate(cx, "admin-purge-comment-warning")
+                                    confirm_label=translate(cx, "admin-purge")
+                                    action=purge_action
+                                />
+                            </div>
+                        }
+                    })
+            }}
+        </Transition>
+    }
+}
@@ -1,10 +1,15 @@
 use leptos::*;
 use leptos_router::*;
-use markdown::*;
 
 use crate::api::comment::*;
 use crate::api::structs::*;
 use crate::api::*;
+use crate::components::admin::{get_admin_user, AdminUser, CommentAdminTools};
+use crate::components::custom_emoji::use_custom_emojis;
+use crate::components::i18n::{t, translate};
+use crate::components::markdown::render_markdown;
+use crate::components::time_ago::TimeAgo;
+use crate::image_proxy::{proxied_image, proxy_image_sources};
 
 // TODO - comments.rs:
 // Add functionality for pulling in replies (IDK how to do this...)
@@ -81,7 +86,10 @@ pub fn Comments(cx: Scope, post_info: PostView) -> impl IntoView {
             .ok()
     });
 
-    let err_msg = "Error loading these comments: ";
+    // Fetched once for the whole post, so the admin tools on each comment don't each request the site
+    let admin_user = get_admin_user(cx);
+
+    let err_msg = t(cx, "error-loading-comments");
 
     view! { cx,
         <div>
@@ -90,7 +98,7 @@ pub fn Comments(cx: Scope, post_info: PostView) -> impl IntoView {
                 view! { cx,
                     <div class="d-flex align-items-center">
                         <h1>
-                            Loading...
+                            {t(cx, "loading")}
                         </h1>
                         <div class="spinner-grow ms-auto" role="status" aria-hidden="true"></div>
                     </div>
@@ -102,14 +110,14 @@ pub fn Comments(cx: Scope, post_info: PostView) -> impl IntoView {
                         .map(|res| match res {
                             None => {
                                 view! { cx,
-                                    <div>{format!("{err_msg}{:?}", comments.read(cx))}</div>
+                                    <div>{err_msg}" "{format!("{:?}", comments.read(cx))}</div>
                                 }
                             }
                             Some(res) => {
 
                                 view! { cx,
                                     <div>
-                                        <CommentsList comments=res.comments.into()/>
+                                        <CommentsList comments=res.comments.into() admin_user=admin_user/>
                                     </div>
                                 }
                             }
@@ -124,13 +132,22 @@ pub fn Comments(cx: Scope, post_info: PostView) -> impl IntoView {
 // The component for the comments list
 // May need another component for holding a comment and its replies, TBD...
 #[component]
-fn CommentsList(cx: Scope, comments: MaybeSignal<Vec<CommentView>>) -> impl IntoView {
+fn CommentsList(
+    cx: Scope,
+    comments: MaybeSignal<Vec<CommentView>>,
+    admin_user: AdminUser,
+) -> impl IntoView {
     view! { cx,
         {comments
             .get()
             .into_iter()
             .map(|comment| {
-                view! { cx, <CommentItem comment_item=leptos::MaybeSignal::Static(comment)/> }
+                view! { cx,
+                    <CommentItem
+                        comment_item=leptos::MaybeSignal::Static(comment)
+                        admin_user=admin_user
+                    />
+                }
             })
             .collect_view(cx)}
     }
@@ -138,8 +155,14 @@ fn CommentsList(cx: Scope, comments: MaybeSignal<Vec<CommentView>>) -> impl Into
 
 // The component for a single comment
 #[component]
-pub fn CommentItem(cx: Scope, comment_item: MaybeSignal<CommentView>) -> impl IntoView {
+pub fn CommentItem(
+    cx: Scope,
+    comment_item: MaybeSignal<CommentView>,
+    #[prop(optional)] admin_user: Option<AdminUser>,
+) -> impl IntoView {
     let comment = comment_item.get();
+    let comment_id = comment.comment.id;
+    let comment_content = comment.comment.content.clone();
 
     let creator_link = if comment.creator.local {
         format!("/user/{}", comment.creator.name)
@@ -167,25 +190,62 @@ pub fn CommentItem(cx: Scope, comment_item: MaybeSignal<CommentView>) -> impl In
         _ => Option::Some("/static/default_assets/default-profile.png".to_string()),
     };
 
+    // Collapsing a comment hides everything but its header, with Enter or the button next to the author
+    let collapsed = create_rw_signal(cx, false);
+    let body_id = format!("comment-body-{}", comment_id);
+    let collapse_label = move || {
+        translate(cx, if collapsed.get() { "comment-expand" } else { "comment-collapse" })
+    };
+
     view! { cx,
-        <div>
+        // The id lets links from elsewhere, such as a user's profile, jump straight to the comment.
+        // Comments can be focused so j and k can move between them.
+        <div id=format!("comment-{}", comment_id) role="article" tabindex="-1" data-comment="">
             <div class="card">
                 <div class="card-header">
+                    <button
+                        type="button"
+                        class="btn btn-link btn-sm p-0 me-2 link-secondary align-baseline"
+                        aria-expanded=move || (!collapsed.get()).to_string()
+                        aria-controls=body_id.clone()
+                        aria-label=collapse_label
+                        title=collapse_label
+                        data-shortcut="collapse"
+                        on:click=move |_| collapsed.update(|collapsed| *collapsed = !*collapsed)
+                    >
+                        <i
+                            aria-hidden="true"
+                            class=move || if collapsed.get() { "bi bi-plus-square" } else { "bi bi-dash-square" }
+                        ></i>
+                    </button>
                     <a href=format!("{}", creator_link)>
-                        <img src=creator_avatar alt="mdo" width="32" height="32" class="rounded"/>
+                        <img src=proxied_image(creator_avatar, Some(64)) alt="" width="32" height="32" class="rounded"/>
                         "  "
                         {comment.creator.name}
                     </a>
+                    <span class="small text-body-secondary">
+                        " · "
+                        <TimeAgo time=comment.comment.published.clone()/>
+                        {comment.comment.updated.clone().map(|updated| view! { cx,
+                            " · "
+                            <TimeAgo time=updated label="time-edited"/>
+                        })}
+                    </span>
+                </div>
+                <div class="card-body" id=body_id class:d-none=move || collapsed.get()>
+                    {move || {
+                        let content = proxy_image_sources(
+                            &render_markdown(&comment_content, &use_custom_emojis(cx)),
+                            Some(1024),
+                        );
+                        view! { cx, <div class="markdown" inner_html=content></div> }
+                    }}
                 </div>
-                <div class="card-body">
-                    <div
-                        class="markdown"
-                        inner_html=markdown::to_html_with_options(
-                                comment.comment.content.as_str(),
-                                &Options::gfm(),
-                            )
-                            .unwrap()
-                    ></div>
+                <div class:d-none=move || collapsed.get()>
+                    {admin_user
+                        .map(|admin_user| {
+                            view! { cx, <CommentAdminTools comment_id=comment_id admin_user=admin_user/> }
+                        })}
                 </div>
             </div>
         </div>
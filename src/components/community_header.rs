@@ -0,0 +1,97 @@
+use leptos::*;
+
+use crate::api::structs::*;
+use crate::components::i18n::t;
+use crate::components::nsfw::*;
+use crate::components::subscribe_button::SubscribeButton;
+use crate::image_proxy::proxied_image;
+
+// TODO - community_header.rs:
+// Show the community's rank on the instance once Lemmy returns it
+
+// The handle other instances use for a community, such as !wolfrs@pawb.social
+pub fn community_handle(community: &Community) -> String {
+    let host = community
+        .actor_id
+        .split("://")
+        .nth(1)
+        .and_then(|rest| rest.split('/').next())
+        .unwrap_or_default();
+
+    format!("!{}@{}", community.name, host)
+}
+
+// The banner, icon, title, and subscribe button at the top of a community's page
+#[component]
+pub fn CommunityHeader(cx: Scope, community: GetCommunityResponse) -> impl IntoView {
+    let community_view = community.community_view;
+    let handle = community_handle(&community_view.community);
+    let community = community_view.community;
+
+    let icon_image = match community.icon {
+        Some(_) => community.icon,
+        _ => Option::Some("/static/default_assets/default-community.png".to_string()),
+    };
+    let banner_image = match community.banner {
+        Some(_) => community.banner,
+        _ => Option::Some(
+            "/static/default_assets/banners/wolfrs-default-community-banner.png".to_string(),
+        ),
+    };
+    let icon_image = proxied_image(icon_image, Some(512));
+    let banner_image = proxied_image(banner_image, Some(1024));
+
+    view! { cx,
+        <div class="card mb-3">
+            <NsfwBlur nsfw=community.nsfw>
+                <img
+                    src=banner_image.clone()
+                    class="card-img-top object-fit-cover"
+                    style="max-height: 12rem;"
+                    alt=""
+                />
+            </NsfwBlur>
+            <div class="card-body d-flex flex-wrap align-items-end gap-3">
+                <NsfwBlur nsfw=community.nsfw compact=true>
+                    <img
+                        src=icon_image.clone()
+                        class="rounded border border-3 border-body-tertiary bg-body"
+                        style="margin-top: -3rem;"
+                        width="96"
+                        height="96"
+                        alt=""
+                    />
+                </NsfwBlur>
+                <div class="flex-grow-1">
+                    <h2 class="card-title mb-0">
+                        {community.title}
+                        {community.nsfw.then(|| view! { cx, <NsfwBadge/> })}
+                    </h2>
+                    <span class="text-body-secondary user-select-all">{handle}</span>
+                </div>
+                <SubscribeButton community_id=community.id subscribed=community_view.subscribed/>
+            </div>
+            {community
+                .posting_restricted_to_mods
+                .then(|| {
+                    view! { cx,
+                        <div class="alert alert-info rounded-0 rounded-bottom mb-0" role="status">
+                            <i aria-hidden="true" class="bi bi-lock"></i>
+                            " "
+                            {t(cx, "community-mods-only")}
+                        </div>
+                    }
+                })}
+            {(community.removed || community.deleted)
+                .then(|| {
+                    view! { cx,
+                        <div class="alert alert-danger rounded-0 rounded-bottom mb-0" role="status">
+                            <i aria-hidden="true" class="bi bi-trash"></i>
+                            " "
+                            {if community.removed { t(cx, "community-removed") } else { t(cx, "community-deleted") }}
+                        </div>
+                    }
+                })}
+        </div>
+    }
+}
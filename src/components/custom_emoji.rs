@@ -0,0 +1,184 @@
+use std::collections::{BTreeMap, HashMap};
+
+use leptos::*;
+
+use crate::api::structs::*;
+use crate::components::i18n::t;
+use crate::components::markdown::{escape_html, replace_in_text};
+use crate::components::site::{refetch_site, with_site};
+
+// TODO - custom_emoji.rs:
+// Remember recently used emojis and show them at the top of the picker
+// Keyboard navigation for the picker
+
+// Returns the site's custom emojis from the site context if they have loaded, or an empty list so Markdown still renders while they load
+pub fn use_custom_emojis(cx: Scope) -> Vec<CustomEmojiView> {
+    with_site(cx, |site| site.custom_emojis.clone()).unwrap_or_default()
+}
+
+// Refetches the custom emojis after an admin has created, edited, or deleted one
+pub fn refetch_custom_emojis(cx: Scope) {
+    refetch_site(cx);
+}
+
+// The <img> tag that a :shortcode: is replaced with
+fn custom_emoji_html(custom_emoji: &CustomEmoji) -> String {
+    format!(
+        "<img class=\"custom-emoji\" src=\"{}\" alt=\"{}\" title=\":{}:\">",
+        escape_html(&custom_emoji.image_url),
+        escape_html(&custom_emoji.alt_text),
+        escape_html(&custom_emoji.shortcode),
+    )
+}
+
+// Replaces every :shortcode: in rendered Markdown with the image of the matching custom emoji.
+// This runs on the HTML rather than the Markdown so that shortcodes inside of tags and code blocks are left alone.
+pub fn replace_custom_emojis(html: &str, custom_emojis: &[CustomEmojiView]) -> String {
+    if custom_emojis.is_empty() {
+        return html.to_string();
+    }
+
+    let emoji_map: HashMap<&str, &CustomEmoji> = custom_emojis
+        .iter()
+        .map(|view| (view.custom_emoji.shortcode.as_str(), &view.custom_emoji))
+        .collect();
+
+    let shortcode_regex = regex::Regex::new(r":([A-Za-z0-9_\-]+):").unwrap();
+
+    replace_in_text(html, &["code", "pre"], |text| {
+        shortcode_regex
+            .replace_all(text, |captures: &regex::Captures| {
+                match emoji_map.get(&captures[1]) {
+                    Some(custom_emoji) => custom_emoji_html(custom_emoji),
+                    None => captures[0].to_string(),
+                }
+            })
+            .to_string()
+    })
+}
+
+// Checks if a custom emoji matches the search text by its shortcode or any of its keywords
+fn matches_search(custom_emoji: &CustomEmojiView, search: &str) -> bool {
+    let search = search.trim().trim_matches(':').to_lowercase();
+
+    search.is_empty()
+        || custom_emoji
+            .custom_emoji
+            .shortcode
+            .to_lowercase()
+            .contains(&search)
+        || custom_emoji
+            .keywords
+            .iter()
+            .any(|keyword| keyword.keyword.to_lowercase().contains(&search))
+}
+
+// Groups the custom emojis by their category, so they can be listed under a heading for each one
+pub fn group_by_category(
+    custom_emojis: Vec<CustomEmojiView>,
+) -> BTreeMap<String, Vec<CustomEmojiView>> {
+    let mut categories: BTreeMap<String, Vec<CustomEmojiView>> = BTreeMap::new();
+    for custom_emoji in custom_emojis {
+        categories
+            .entry(custom_emoji.custom_emoji.category.clone())
+            .or_default()
+            .push(custom_emoji);
+    }
+    categories
+}
+
+// The dropdown for picking a custom emoji, which inserts the :shortcode: of the chosen emoji into the text of a composer
+#[component]
+pub fn EmojiPicker(cx: Scope, text: RwSignal<String>) -> impl IntoView {
+    let (search, set_search) = create_signal(cx, String::new());
+
+    let insert_emoji = move |shortcode: String| {
+        text.update(|text| {
+            if !text.is_empty() && !text.ends_with(char::is_whitespace) {
+                text.push(' ');
+            }
+            text.push_str(&format!(":{}:", shortcode));
+        })
+    };
+
+    view! { cx,
+        {move || {
+            let custom_emojis = use_custom_emojis(cx);
+            if custom_emojis.is_empty() {
+                return ().into_view(cx);
+            }
+
+            view! { cx,
+                <div class="dropdown d-inline-block">
+                    <button
+                        type="button"
+                        class="btn btn-outline-secondary btn-sm dropdown-toggle"
+                        data-bs-toggle="dropdown"
+                        data-bs-auto-close="outside"
+                        aria-expanded="false"
+                        title=t(cx, "emoji-picker-insert")
+                        aria-label=t(cx, "emoji-picker-insert")
+                    >
+                        <i aria-hidden="true" class="bi bi-emoji-smile"></i>
+                    </button>
+                    <div class="dropdown-menu p-2" style="width: 20rem;">
+                        <input
+                            type="search"
+                            class="form-control form-control-sm mb-2"
+                            placeholder=t(cx, "emoji-picker-search")
+                            prop:value=move || search.get()
+                            on:input=move |ev| set_search.set(event_target_value(&ev))
+                        />
+                        <div style="max-height: 16rem; overflow-y: auto;">
+                            {move || {
+                                let custom_emojis = use_custom_emojis(cx)
+                                    .into_iter()
+                                    .filter(|custom_emoji| matches_search(custom_emoji, &search.get()))
+                                    .collect::<Vec<_>>();
+                                if custom_emojis.is_empty() {
+                                    return view! { cx,
+                                        <p class="text-body-secondary small mb-0">{t(cx, "emoji-picker-no-matches")}</p>
+                                    }
+                                        .into_view(cx);
+                                }
+                                group_by_category(custom_emojis)
+                                    .into_iter()
+                                    .map(|(category, custom_emojis)| {
+                                        view! { cx,
+                                            <h6 class="dropdown-header px-0">{category}</h6>
+                                            <div class="d-flex flex-wrap gap-1 mb-2">
+                                                {custom_emojis
+                                                    .into_iter()
+                                                    .map(|custom_emoji| {
+                                                        let shortcode = custom_emoji.custom_emoji.shortcode.clone();
+                                                        let title = format!(":{}:", shortcode);
+                                                        view! { cx,
+                                                            <button
+                                                                type="button"
+                                                                class="btn btn-sm btn-link p-1"
+                                                                title=title
+                                                                on:click=move |_| insert_emoji(shortcode.clone())
+                                                            >
+                                                                <img
+                                                                    src=custom_emoji.custom_emoji.image_url
+                                                                    alt=custom_emoji.custom_emoji.alt_text
+                                                                    width="24"
+                                                                    height="24"
+                                                                />
+                                                            </button>
+                                                        }
+                                                    })
+                                                    .collect_view(cx)}
+                                            </div>
+                                        }
+                                    })
+                                    .collect_view(cx)
+                            }}
+                        </div>
+                    </div>
+                </div>
+            }
+                .into_view(cx)
+        }}
+    }
+}
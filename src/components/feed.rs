@@ -1,19 +1,44 @@
+use std::collections::{HashMap, HashSet};
+
 use leptos::*;
 use leptos_router::*;
+use serde::{Deserialize, Serialize};
 
 use crate::api::posts::get_posts;
 use crate::api::structs::*;
-use crate::api::user::get_person_details;
 use crate::api::*;
-use crate::components::pagination::Pagination;
+use crate::components::feed_layout::*;
+use crate::components::i18n::{t, t_count, translate_listing, translate_sort, translate_with};
+use crate::components::nsfw::*;
+use crate::components::pagination::{first_page_href, page_href, PageCursors, Pagination};
+use crate::components::post_media::*;
+use crate::components::time_ago::TimeAgo;
+use crate::image_proxy::proxied_image;
 
 // TODO - feed.rs:
 // Improve support for KBin, which is currently somewhat... Broken
 // onclick functionality for voting, favoriting, crossposting, and reporting
 // Better handling for mobile layouts, including possibly removing voting buttons on mobile
-// Finish fleshing out PostItem for stuff like language, edited status, date, etc
+// Finish fleshing out PostItem for stuff like language
+
+// A page of the feed as it was returned from the API
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct FeedPage {
+    page: i32,
+    posts: Vec<PostView>,
+    // The cursor for the next page, on Lemmy versions that support cursor based paging
+    next_page: Option<String>,
+}
+
+// Leaves out posts that are already in the feed. On Hot and Active, posts move between pages as they are re-ranked, so the same post can come back on the next page.
+fn dedup_posts(posts: Vec<PostView>, seen: &mut HashSet<i32>) -> Vec<PostView> {
+    posts
+        .into_iter()
+        .filter(|post| seen.insert(post.post.id))
+        .collect()
+}
 
-// The feed column that shows the Posts list, used for Home, Community, and User pages
+// The feed column that shows the Posts list, used for Home and Community pages
 #[component]
 pub fn Feed(cx: Scope, endpoint: &'static str) -> impl IntoView {
     let query = use_query_map(cx);
@@ -23,6 +48,9 @@ pub fn Feed(cx: Scope, endpoint: &'static str) -> impl IntoView {
             .unwrap_or(1)
     };
 
+    // When there is a cursor it decides which posts are loaded, and the page number is only shown to the user
+    let cursor = move || query.with(|q| q.get("cursor").cloned());
+
     let params = use_params_map(cx);
     let community_name = move || {
         params
@@ -32,14 +60,6 @@ pub fn Feed(cx: Scope, endpoint: &'static str) -> impl IntoView {
             .unwrap()
     };
 
-    let username = move || {
-        params
-            .with(|params| params.get("username").cloned())
-            .unwrap_or_default()
-            .parse::<String>()
-            .unwrap()
-    };
-
     // Creates the signals needed for handling the tab reactivity on the feed
     // Needs to be made to ignore/change the tabs when looking at a user or community page feed
     let (subscribed_active, set_subscribed_active) = create_signal(cx, false);
@@ -56,82 +76,157 @@ pub fn Feed(cx: Scope, endpoint: &'static str) -> impl IntoView {
 
     let (sorting, set_sorting) = create_signal(cx, SortType::Active);
 
+    // The layout and paging the user has chosen, which are the same for every feed
+    let preferences = FeedPreferences::from_cookies(cx);
+
     // Creates the derived signal from page and active_tab for the create_resource function below
-    let updater = move || (page(), active_tab.get(), sorting.get());
+    let updater = move || (page(), active_tab.get(), sorting.get(), cursor());
 
-    // Variable that holds the returned PostView from the API for either GetPostsResponse or GetPersonDetailsResponse
+    // Variable that holds the page number and the returned PostView from the API for GetPostsResponse
     let posts = create_resource(cx, updater, move |updater| async move {
-        // This constructs the proper API URL for GetPosts or GetPersonDetails
-        let url_constructor = match endpoint {
-            "community" | "home" => ApiUrlConstructor {
-                endpoint: api_endpoints::GetEndpoint::GET_POSTS.to_string(),
-                id: None,
-                params: None,
+        // This constructs the proper API URL for GetPosts
+        let url_constructor = ApiUrlConstructor {
+            endpoint: api_endpoints::GetEndpoint::GET_POSTS.to_string(),
+            id: None,
+            params: None,
+        };
+
+        // This assembles the GetPosts request form for either the home feed or community feed
+        let get_form = match endpoint {
+            "community" => GetPosts {
+                auth: None,
+                community_id: None,
+                community_name: Some(community_name()),
+                limit: Some(20),
+                page: updater.3.is_none().then_some(updater.0),
+                page_cursor: updater.3.clone(),
+                post_id: None,
+                saved_only: None,
+                sort: Some(updater.2),
+                type_: None,
             },
-            "user" => ApiUrlConstructor {
-                endpoint: api_endpoints::GetEndpoint::GET_PERSON_DETAILS.to_string(),
-                id: None,
-                params: None,
+            "home" => GetPosts {
+                auth: None,
+                community_id: None,
+                community_name: None,
+                limit: Some(20),
+                page: updater.3.is_none().then_some(updater.0),
+                page_cursor: updater.3.clone(),
+                post_id: None,
+                saved_only: None,
+                sort: Some(updater.2),
+                type_: Some(updater.1),
             },
             &_ => unreachable!(),
         };
+        // This is where the API is called for GetPosts and the GetPostsResponse is returned and converted to PostView
+        get_posts(cx, &api_url_builder(cx, url_constructor, get_form))
+            .await
+            .ok()
+            .map(|res| FeedPage {
+                page: updater.0,
+                posts: res.posts,
+                next_page: res.next_page,
+            })
+    });
 
-        if endpoint.ne("user") {
-            // This assembles the GetPosts request form for either the home feed or community feed
-            let get_form = match endpoint {
-                "community" => GetPosts {
-                    auth: None,
-                    community_id: None,
-                    community_name: Some(community_name()),
-                    limit: Some(20),
-                    page: Some(updater.0),
-                    post_id: None,
-                    saved_only: None,
-                    sort: Some(updater.2),
-                    type_: None,
-                },
-                "home" => GetPosts {
-                    auth: None,
-                    community_id: None,
-                    community_name: None,
-                    limit: Some(20),
-                    page: Some(updater.0),
-                    post_id: None,
-                    saved_only: None,
-                    sort: Some(updater.2),
-                    type_: Some(updater.1),
-                },
-                &_ => unreachable!(),
+    // Remembers the cursor each page was loaded with, so Previous can go back to the same posts
+    let cursors = PageCursors::new(
+        cx,
+        Signal::derive(cx, move || posts.read(cx).flatten().and_then(|page| page.next_page)),
+    );
+    create_effect(cx, move |_| {
+        if let (Some(Some(loaded)), Some(cursor)) = (posts.read(cx), cursor()) {
+            cursors.pages.update(|pages| {
+                pages.insert(loaded.page, cursor);
+            });
+        }
+    });
+
+    // With infinite scrolling, the pages before the current one are kept here and shown above it
+    let earlier_pages = create_rw_signal(cx, Vec::<FeedPage>::new());
+    create_effect(cx, move |_| {
+        let _ = preferences.paging.get();
+        earlier_pages.set(Vec::new());
+    });
+
+    // The posts shown on each numbered page, so a post that moved down to the next page isn't shown twice
+    let shown_posts = store_value(cx, HashMap::<i32, Vec<i32>>::new());
+    // Numbered pages leave out what was shown on the pages before, cursors already keep pages from overlapping
+    let dedups_numbered_pages = move || {
+        preferences.paging.get() != FeedPaging::InfiniteScroll && cursor().is_none()
+    };
+    let shown_before = move |page: i32| {
+        shown_posts.with_value(|shown_posts| {
+            shown_posts
+                .iter()
+                .filter(|(shown_page, _)| **shown_page < page)
+                .flat_map(|(_, post_ids)| post_ids.iter().copied())
+                .collect::<HashSet<_>>()
+        })
+    };
+    // Each page is recorded once it loads rather than while it is rendered, so rendering it again doesn't change what it left out
+    create_effect(cx, move |_| {
+        if let Some(Some(loaded)) = posts.read(cx) {
+            let mut seen = if dedups_numbered_pages() {
+                shown_before(loaded.page)
+            } else {
+                HashSet::new()
             };
-            // This is where the API is called for GetPosts and the GetPostsResponse is returned and converted to PostView
-            return get_posts(cx, &api_url_builder(cx, url_constructor, get_form))
-                .await
-                .unwrap()
-                .posts
-                .into();
+            let post_ids = dedup_posts(loaded.posts, &mut seen)
+                .iter()
+                .map(|post| post.post.id)
+                .collect();
+            shown_posts.update_value(|shown_posts| {
+                shown_posts.insert(loaded.page, post_ids);
+            });
+        }
+    });
+
+    let location = use_location(cx);
+    let navigate = std::rc::Rc::new(use_navigate(cx));
+
+    // Changing the listing or sorting starts the feed again from the first page, since the earlier pages and cursors belong to the old feed
+    let restart_navigate = navigate.clone();
+    create_effect(cx, move |previous: Option<()>| {
+        let _ = (active_tab.get(), sorting.get());
+        if previous.is_some() {
+            earlier_pages.set(Vec::new());
+            cursors.pages.set(Default::default());
+            shown_posts.set_value(Default::default());
+            let first_page = location
+                .query
+                .with_untracked(|query| format!("{}{}", location.pathname.get_untracked(), first_page_href(query)));
+            let _ = restart_navigate(&first_page, NavigateOptions::default());
         }
+    });
 
-        // This assembles the GetPersonDetails request form for the user feed
-        let get_form = GetPersonDetails {
-            auth: None,
-            community_id: None,
-            limit: Some(20),
-            page: Some(updater.0),
-            person_id: None,
-            saved_only: None,
-            sort: Some(updater.2),
-            username: Some(username()),
+    // Moves the current page above the feed and loads the next one, keeping ?page= in sync without adding to the history.
+    // Stored so the Transition's children can be rendered more than once and still share it.
+    let load_next_page = store_value(cx, move || {
+        if posts.loading().get_untracked() {
+            return;
+        }
+        let Some(Some(loaded)) = cx.untrack(move || posts.read(cx)) else {
+            return;
         };
+        if loaded.posts.is_empty() || loaded.page != page() {
+            return;
+        }
 
-        // This is where the API is called for GetPersonDetails and the GetPersonDetailsResponse is returned and converted to PostView
-        get_person_details(cx, &api_url_builder(cx, url_constructor, get_form))
-            .await
-            .unwrap()
-            .posts
-            .into()
+        let next_href = page_href(loaded.page + 1, loaded.next_page.clone());
+        earlier_pages.update(|pages| pages.push(loaded));
+        let _ = navigate(
+            &format!("{}{}", location.pathname.get_untracked(), next_href),
+            NavigateOptions {
+                replace: true,
+                scroll: false,
+                ..Default::default()
+            },
+        );
     });
 
-    let err_msg = "Error loading this post: ";
+    let err_msg = t(cx, "error-loading-post");
 
     view! { cx,
         <div class="card text-left">
@@ -142,51 +237,53 @@ pub fn Feed(cx: Scope, endpoint: &'static str) -> impl IntoView {
                             class="nav nav-tabs card-header-tabs"
                             class:invisible=move || invisible_listing.get()
                         >
+                            // The tabs change the feed without leaving the page, so they are buttons rather than links
                             <li class="nav-item">
-                                <a
+                                <button
+                                    type="button"
                                     class="nav-link"
                                     class:disabled=move || subscribed_disabled
-                                    aria-disabled=subscribed_disabled
-                                    href="#"
+                                    disabled=subscribed_disabled
                                 >
-                                    "Subscribed"
-                                </a>
+                                    {move || translate_listing(cx, &ListingType::Subscribed)}
+                                </button>
                             </li>
-                            <li
-                                class="nav-item"
-                                on:click=move |_| {
-                                    set_local_active.update(|value| *value = true);
-                                    set_subscribed_active.update(|value| *value = false);
-                                    set_all_active.update(|value| *value = false);
-                                    set_active_tab.update(|value| *value = ListingType::Local)
-                                }
-                            >
-
-                                <a
+                            <li class="nav-item">
+                                <button
+                                    type="button"
                                     class="nav-link"
                                     class:active=move || local_active.get()
-                                    href="#"
+                                    aria-pressed=move || local_active.get().to_string()
+                                    on:click=move |_| {
+                                        set_local_active.update(|value| *value = true);
+                                        set_subscribed_active.update(|value| *value = false);
+                                        set_all_active.update(|value| *value = false);
+                                        set_active_tab.update(|value| *value = ListingType::Local)
+                                    }
                                 >
-                                    "Local"
-                                </a>
+                                    {move || translate_listing(cx, &ListingType::Local)}
+                                </button>
                             </li>
-                            <li
-                                class="nav-item"
-                                on:click=move |_| {
-                                    set_all_active.update(|value| *value = true);
-                                    set_local_active.update(|value| *value = false);
-                                    set_subscribed_active.update(|value| *value = false);
-                                    set_active_tab.update(|value| *value = ListingType::All)
-                                }
-                            >
-
-                                <a class="nav-link" class:active=move || all_active.get() href="#">
-                                    "All"
-                                </a>
+                            <li class="nav-item">
+                                <button
+                                    type="button"
+                                    class="nav-link"
+                                    class:active=move || all_active.get()
+                                    aria-pressed=move || all_active.get().to_string()
+                                    on:click=move |_| {
+                                        set_all_active.update(|value| *value = true);
+                                        set_local_active.update(|value| *value = false);
+                                        set_subscribed_active.update(|value| *value = false);
+                                        set_active_tab.update(|value| *value = ListingType::All)
+                                    }
+                                >
+                                    {move || translate_listing(cx, &ListingType::All)}
+                                </button>
                             </li>
                         </ul>
                     </div>
-                    <div class="col-sm-6">
+                    <div class="col-sm-6 d-flex justify-content-end gap-2">
+                        <FeedLayoutMenu preferences=preferences/>
                         <div class="dropstart text-end">
                             <button
                                 class="btn btn-secondary dropdown-toggle"
@@ -195,168 +292,168 @@ pub fn Feed(cx: Scope, endpoint: &'static str) -> impl IntoView {
                                 data-bs-toggle="dropdown"
                                 aria-expanded="false"
                             >
-                                {move || sorting.get().to_str()}
+                                {move || translate_sort(cx, &sorting.get())}
                             </button>
                             <ul class="dropdown-menu">
                                 <li>
-                                    <a
+                                    <button
+                                        type="button"
                                         class="dropdown-item"
-                                        href="#"
                                         on:click=move |_| {
                                             set_sorting.update(|value| *value = SortType::Active)
                                         }
                                     >
 
-                                        "Active"
-                                    </a>
+                                        {move || translate_sort(cx, &SortType::Active)}
+                                    </button>
                                 </li>
                                 <li>
-                                    <a
+                                    <button
+                                        type="button"
                                         class="dropdown-item"
-                                        href="#"
                                         on:click=move |_| {
                                             set_sorting.update(|value| *value = SortType::Hot)
                                         }
                                     >
 
-                                        "Hot"
-                                    </a>
+                                        {move || translate_sort(cx, &SortType::Hot)}
+                                    </button>
                                 </li>
                                 <li>
-                                    <a
+                                    <button
+                                        type="button"
                                         class="dropdown-item"
-                                        href="#"
                                         on:click=move |_| {
                                             set_sorting.update(|value| *value = SortType::New)
                                         }
                                     >
 
-                                        "New"
-                                    </a>
+                                        {move || translate_sort(cx, &SortType::New)}
+                                    </button>
                                 </li>
                                 <li>
-                                    <a
+                                    <button
+                                        type="button"
                                         class="dropdown-item"
-                                        href="#"
                                         on:click=move |_| {
                                             set_sorting.update(|value| *value = SortType::Old)
                                         }
                                     >
 
-                                        "Old"
-                                    </a>
+                                        {move || translate_sort(cx, &SortType::Old)}
+                                    </button>
                                 </li>
                                 <li>
                                     <hr class="dropdown-divider"/>
                                 </li>
                                 <li>
-                                    <a
+                                    <button
+                                        type="button"
                                         class="dropdown-item"
-                                        href="#"
                                         on:click=move |_| {
                                             set_sorting.update(|value| *value = SortType::TopHour)
                                         }
                                     >
 
-                                        "Top - Hour"
-                                    </a>
+                                        {move || translate_sort(cx, &SortType::TopHour)}
+                                    </button>
                                 </li>
                                 <li>
-                                    <a
+                                    <button
+                                        type="button"
                                         class="dropdown-item"
-                                        href="#"
                                         on:click=move |_| {
                                             set_sorting.update(|value| *value = SortType::TopTwelveHour)
                                         }
                                     >
 
-                                        "Top - 12 Hours"
-                                    </a>
+                                        {move || translate_sort(cx, &SortType::TopTwelveHour)}
+                                    </button>
                                 </li>
                                 <li>
-                                    <a
+                                    <button
+                                        type="button"
                                         class="dropdown-item"
-                                        href="#"
                                         on:click=move |_| {
                                             set_sorting.update(|value| *value = SortType::TopDay)
                                         }
                                     >
 
-                                        "Top - Day"
-                                    </a>
+                                        {move || translate_sort(cx, &SortType::TopDay)}
+                                    </button>
                                 </li>
                                 <li>
-                                    <a
+                                    <button
+                                        type="button"
                                         class="dropdown-item"
-                                        href="#"
                                         on:click=move |_| {
                                             set_sorting.update(|value| *value = SortType::TopWeek)
                                         }
                                     >
 
-                                        "Top - Week"
-                                    </a>
+                                        {move || translate_sort(cx, &SortType::TopWeek)}
+                                    </button>
                                 </li>
                                 <li>
-                                    <a
+                                    <button
+                                        type="button"
                                         class="dropdown-item"
-                                        href="#"
                                         on:click=move |_| {
                                             set_sorting.update(|value| *value = SortType::TopMonth)
                                         }
                                     >
 
-                                        "Top - Month"
-                                    </a>
+                                        {move || translate_sort(cx, &SortType::TopMonth)}
+                                    </button>
                                 </li>
                                 <li>
-                                    <a
+                                    <button
+                                        type="button"
                                         class="dropdown-item"
-                                        href="#"
                                         on:click=move |_| {
                                             set_sorting
                                                 .update(|value| *value = SortType::TopThreeMonths)
                                         }
                                     >
 
-                                        "Top - 3 Months"
-                                    </a>
+                                        {move || translate_sort(cx, &SortType::TopThreeMonths)}
+                                    </button>
                                 </li>
                                 <li>
-                                    <a
+                                    <button
+                                        type="button"
                                         class="dropdown-item"
-                                        href="#"
                                         on:click=move |_| {
                                             set_sorting.update(|value| *value = SortType::TopSixMonths)
                                         }
                                     >
 
-                                        "Top - 6 Months"
-                                    </a>
+                                        {move || translate_sort(cx, &SortType::TopSixMonths)}
+                                    </button>
                                 </li>
                                 <li>
-                                    <a
+                                    <button
+                                        type="button"
                                         class="dropdown-item"
-                                        href="#"
                                         on:click=move |_| {
                                             set_sorting.update(|value| *value = SortType::TopYear)
                                         }
                                     >
 
-                                        "Top - Year"
-                                    </a>
+                                        {move || translate_sort(cx, &SortType::TopYear)}
+                                    </button>
                                 </li>
                                 <li>
-                                    <a
+                                    <button
+                                        type="button"
                                         class="dropdown-item"
-                                        href="#"
                                         on:click=move |_| {
                                             set_sorting.update(|value| *value = SortType::TopAll)
                                         }
                                     >
 
-                                        "Top - All Time"
-                                    </a>
+                                        {move || translate_sort(cx, &SortType::TopAll)}
+                                    </button>
                                 </li>
                             </ul>
                         </div>
@@ -370,7 +467,7 @@ pub fn Feed(cx: Scope, endpoint: &'static str) -> impl IntoView {
                         view! { cx,
                             <div class="d-flex align-items-center">
                                 <h1>
-                                    Loading...
+                                    {t(cx, "loading")}
                                 </h1>
                                 <div
                                     class="spinner-grow ms-auto"
@@ -385,13 +482,46 @@ pub fn Feed(cx: Scope, endpoint: &'static str) -> impl IntoView {
                                 .read(cx)
                                 .map(|res| match res {
                                     None => {
-                                        view! { cx, <div>{format!("{err_msg}")}</div> }
+                                        view! { cx, <div>{err_msg}</div> }
                                     }
-                                    Some(res) => {
+                                    Some(loaded) => {
+                                        let infinite_scroll = preferences.paging.get()
+                                            == FeedPaging::InfiniteScroll;
+                                        let end_of_feed = loaded.posts.is_empty();
+
+                                        // While the next page is loading the current one is still shown, so only pages before it are added above
+                                        let mut seen = HashSet::new();
+                                        let mut feed_posts = earlier_pages
+                                            .get()
+                                            .into_iter()
+                                            .filter(|earlier_page| earlier_page.page < loaded.page)
+                                            .flat_map(|earlier_page| dedup_posts(earlier_page.posts, &mut seen))
+                                            .collect::<Vec<_>>();
+
+                                        if dedups_numbered_pages() {
+                                            seen.extend(shown_before(loaded.page));
+                                        }
+                                        feed_posts.extend(dedup_posts(loaded.posts, &mut seen));
 
                                         view! { cx,
                                             <div>
-                                                <FeedList posts=res.into()/>
+                                                <FeedList
+                                                    posts=feed_posts.into()
+                                                    layout=preferences.layout.get()
+                                                />
+                                                {match (infinite_scroll, end_of_feed) {
+                                                    (false, _) => ().into_view(cx),
+                                                    (true, false) => view! { cx,
+                                                        <InfiniteScrollSentinel on_visible=move || load_next_page.with_value(|load_next_page| load_next_page())/>
+                                                    }
+                                                    .into_view(cx),
+                                                    (true, true) => view! { cx,
+                                                        <p class="text-center text-body-secondary py-3">
+                                                            {t(cx, "feed-end")}
+                                                        </p>
+                                                    }
+                                                    .into_view(cx),
+                                                }}
                                             </div>
                                         }
                                     }
@@ -403,7 +533,10 @@ pub fn Feed(cx: Scope, endpoint: &'static str) -> impl IntoView {
 
             </div>
 
-            <Pagination/>
+            {move || {
+                (preferences.paging.get() == FeedPaging::Pages)
+                    .then(|| view! { cx, <Pagination cursors=cursors/> })
+            }}
         </div>
     }
 }
@@ -411,33 +544,112 @@ pub fn Feed(cx: Scope, endpoint: &'static str) -> impl IntoView {
 // The Posts list containing the Post items
 // Suggestion: Implement collapsing accordions for multiple posts in a row from the same community in the home feed?
 #[component]
-fn FeedList(cx: Scope, posts: MaybeSignal<Vec<PostView>>) -> impl IntoView {
+fn FeedList(cx: Scope, posts: MaybeSignal<Vec<PostView>>, layout: FeedLayout) -> impl IntoView {
+    let items = posts
+        .get()
+        .into_iter()
+        // Instances that don't allow NSFW content leave those posts out of the feed entirely
+        .filter(|post| !is_nsfw_forbidden(post.post.nsfw || post.community.nsfw))
+        .map(|post| {
+            let item = view! { cx, <FeedItem post_view=leptos::MaybeSignal::Static(post) layout=layout/> };
+            match layout {
+                FeedLayout::Cards => view! { cx, <div class="col" role="none">{item}</div> }.into_view(cx),
+                FeedLayout::List | FeedLayout::Compact => item.into_view(cx),
+            }
+        })
+        .collect_view(cx);
+
+    // The feed role tells screen readers the posts are a list of articles, which j and k move between
+    match layout {
+        FeedLayout::List => view! { cx, <div role="feed" aria-label=t(cx, "feed")>{items}</div> },
+        FeedLayout::Compact => view! { cx,
+            <div class="list-group mb-3" role="feed" aria-label=t(cx, "feed")>{items}</div>
+        },
+        FeedLayout::Cards => view! { cx,
+            <div class="row row-cols-1 row-cols-md-2 g-3 mb-3" role="feed" aria-label=t(cx, "feed")>{items}</div>
+        },
+    }
+}
+
+// A button for an action on a post, with its label for screen readers and as a tooltip.
+// The actions aren't sent to the API yet, so the buttons are disabled until they are.
+#[component]
+fn PostActionButton(
+    cx: Scope,
+    icon: &'static str,
+    // The id of the label in the locale files
+    label: &'static str,
+) -> impl IntoView {
+    view! { cx,
+        <button
+            type="button"
+            class="btn btn-link btn-sm p-0 link-secondary align-baseline"
+            aria-label=t(cx, label)
+            title=t(cx, label)
+            disabled=true
+        >
+            <i aria-hidden="true" class=icon></i>
+        </button>
+    }
+}
+
+// The post's score, which is read out with what the number means
+#[component]
+fn PostScore(cx: Scope, score: i64) -> impl IntoView {
+    view! { cx,
+        <span aria-hidden="true">{score}</span>
+        <span class="visually-hidden">{t_count(cx, "post-score", score)}</span>
+    }
+}
+
+// The pin on featured posts, with what it means spelled out for screen readers
+#[component]
+fn PinnedIcon(cx: Scope) -> impl IntoView {
     view! { cx,
-        {posts
-            .get()
-            .into_iter()
-            .map(|post| view! { cx, <FeedItem post_view=leptos::MaybeSignal::Static(post)/> })
-            .collect_view(cx)}
+        <i aria-hidden="true" class="bi bi-pin-angle-fill ms-1" title=t(cx, "post-pinned")></i>
+        <span class="visually-hidden">{t(cx, "post-pinned")}</span>
     }
 }
 
 // The individual Post items, may need to be a seperate component if it grows too large...
 #[component]
-pub fn FeedItem(cx: Scope, post_view: MaybeSignal<PostView>) -> impl IntoView {
+pub fn FeedItem(
+    cx: Scope,
+    post_view: MaybeSignal<PostView>,
+    // Shows the post's media expanded straight away, which is used at the top of the post's own page
+    #[prop(optional)]
+    expand_media: bool,
+    // How the post is laid out, which is the full size list item unless the user has chosen otherwise
+    #[prop(optional)]
+    layout: FeedLayout,
+) -> impl IntoView {
     // These set the varaibles from the PostView struct to make insetion into the view easier
     let post = post_view.get();
 
     let post_link = format!("/post/{}", post.post.id);
+    // Where the o shortcut goes, which is the post's link, or the post itself for text posts
+    let shortcut_link = post
+        .post
+        .url
+        .clone()
+        .filter(|url| url.starts_with("https://") || url.starts_with("http://"))
+        .unwrap_or_else(|| post_link.clone());
+    // Each item is labelled by its title, so screen readers announce which post they have moved to
+    let title_id = format!("post-title-{}", post.post.id);
 
     // Currently not used, may be used later on
     //let total_votes = post.counts.upvotes - post.counts.downvotes;
 
-    // Checks to see if a post thumbnail exists, if it does not it setts it to a default image
-    // This needs to handle external links as well with the external-link.png file
-    let post_thumbnail = match post.post.thumbnail_url {
-        Some(_) => post.post.thumbnail_url,
-        _ => Option::Some("/static/default_assets/comment.png".to_string()),
-    };
+    // Works out whether the post is an image, video, embed, or link, which decides the thumbnail and the expanded media
+    let media = PostMedia::from_post(&post.post);
+    let media_expandable = media.is_expandable();
+    let thumbnail_media = media.clone();
+    let media_post = post.post.clone();
+    let thumbnail_post = post.post.clone();
+    let media_expanded = create_rw_signal(cx, expand_media);
+
+    // Posts in NSFW communities are treated as NSFW even when the post itself isn't marked as such
+    let post_nsfw = post.post.nsfw || post.community.nsfw;
 
     let post_title = post.post.name;
 
@@ -541,8 +753,179 @@ pub fn FeedItem(cx: Scope, post_view: MaybeSignal<PostView>) -> impl IntoView {
 
     let comment_count = post.counts.comments;
 
+    let score = post.counts.upvotes - post.counts.downvotes;
+
+    let published = post.post.published.clone();
+    // Edited posts are marked with a pencil, and when they were edited is shown when it's hovered
+    let edited = post.post.updated.clone().map(|updated| {
+        view! { cx,
+            " "
+            <span title=translate_with(cx, "time-edited", &[("time", updated.clone().into())])>
+                <i aria-hidden="true" class="bi bi-pencil"></i>
+                <span class="visually-hidden">
+                    <TimeAgo time=updated.clone() label="time-edited"/>
+                </span>
+            </span>
+        }
+    });
+
+    // A single line with a small thumbnail, which fits many more posts on the screen
+    if layout == FeedLayout::Compact {
+        let thumbnail = proxied_image(thumbnail_media.thumbnail(&thumbnail_post), Some(64));
+
+        return view! { cx,
+            <div
+                class="list-group-item d-flex align-items-center gap-3"
+                role="article"
+                tabindex="-1"
+                data-feed-item=""
+                data-link=shortcut_link.clone()
+                data-comments-link=post_link.clone()
+                aria-labelledby=title_id.clone()
+            >
+                <span class="text-nowrap text-center" style="min-width: 3rem;">
+                    <PostActionButton icon="bi bi-caret-up" label="post-upvote"/>
+                    " "
+                    <PostScore score=score.into()/>
+                </span>
+                <NsfwBlur nsfw=post_nsfw compact=true>
+                    <img
+                        src=thumbnail.clone()
+                        class="rounded object-fit-cover"
+                        alt=""
+                        width="48"
+                        height="48"
+                        loading="lazy"
+                    />
+                </NsfwBlur>
+                <div class="flex-grow-1 text-truncate">
+                    <a
+                        id=title_id.clone()
+                        href=post_link.clone()
+                        class="link-offset-2 link-underline link-underline-opacity-0"
+                    >
+                        {post_title}
+                    </a>
+                    {post_pin.then(|| view! { cx, <PinnedIcon/> })}
+                    {post_nsfw.then(|| view! { cx, <NsfwBadge/> })}
+                    <div class="small text-body-secondary text-truncate">
+                        <a href=community_link class="link-secondary">
+                            {community_name}
+                        </a>
+                        " · "
+                        <a href=creator_link class="link-secondary">
+                            {creator_name}
+                        </a>
+                        " · "
+                        <TimeAgo time=published/>
+                        {edited}
+                        " · "
+                        <a
+                            href=post_link
+                            class="link-secondary link-offset-2 link-underline link-underline-opacity-0"
+                            aria-label=t_count(cx, "comments-count", comment_count.unwrap_or_default().into())
+                        >
+                            <i aria-hidden="true" class="bi bi-chat-right-text"></i>
+                            " "
+                            {comment_count}
+                        </a>
+                    </div>
+                </div>
+            </div>
+        }
+        .into_view(cx);
+    }
+
+    // A card with the post's image on top, which suits image heavy communities.
+    // Text posts don't get the default placeholder image, since it would take up most of the card.
+    if layout == FeedLayout::Cards {
+        let card_image = (!matches!(thumbnail_media, PostMedia::None))
+            .then(|| proxied_image(thumbnail_media.thumbnail(&thumbnail_post), Some(512)))
+            .flatten();
+        let image_link = post_link.clone();
+        // The image links to the post too, so it is named after the post for screen readers
+        let image_alt = post_title.clone();
+
+        return view! { cx,
+            <div
+                class="card h-100"
+                role="article"
+                tabindex="-1"
+                data-feed-item=""
+                data-link=shortcut_link.clone()
+                data-comments-link=post_link.clone()
+                aria-labelledby=title_id.clone()
+            >
+                {card_image
+                    .map(move |card_image| {
+                        view! { cx,
+                            <NsfwBlur nsfw=post_nsfw>
+                                <a href=image_link.clone()>
+                                    <img
+                                        src=card_image.clone()
+                                        class="card-img-top object-fit-cover"
+                                        alt=image_alt.clone()
+                                        loading="lazy"
+                                        style="height: 200px;"
+                                    />
+                                </a>
+                            </NsfwBlur>
+                        }
+                    })}
+                <div class="card-body">
+                    <h6 class="card-title">
+                        <a
+                            id=title_id.clone()
+                            href=post_link.clone()
+                            class="link-offset-2 link-underline link-underline-opacity-0"
+                        >
+                            {post_title}
+                        </a>
+                        {post_pin.then(|| view! { cx, <PinnedIcon/> })}
+                        {post_nsfw.then(|| view! { cx, <NsfwBadge/> })}
+                    </h6>
+                    <p class="card-text small text-body-secondary">
+                        <a href=community_link class="link-secondary">
+                            {community_name}
+                        </a>
+                        " · "
+                        <a href=creator_link class="link-secondary">
+                            {creator_name}
+                        </a>
+                        " · "
+                        <TimeAgo time=published/>
+                        {edited}
+                    </p>
+                </div>
+                <div class="card-footer small">
+                    <PostActionButton icon="bi bi-caret-up" label="post-upvote"/>
+                    " "
+                    <PostScore score=score.into()/>
+                    "   "
+                    <a
+                        href=post_link
+                        class="link-secondary link-offset-2 link-underline link-underline-opacity-0"
+                    >
+                        <i aria-hidden="true" class="bi bi-chat-right-text"></i>
+                        " "
+                        {t_count(cx, "comments-count", comment_count.unwrap_or_default().into())}
+                    </a>
+                </div>
+            </div>
+        }
+        .into_view(cx);
+    }
+
     view! { cx,
-        <div class="row">
+        <div
+            class="row"
+            role="article"
+            tabindex="-1"
+            data-feed-item=""
+            data-link=shortcut_link
+            data-comments-link=post_link.clone()
+            aria-labelledby=title_id.clone()
+        >
 
             // OLD LAYOUT CODE - MAY BE DELETED EVENTUALLY IF UNUSED
             // <div class="col-sm-1">
@@ -665,12 +1048,13 @@ pub fn FeedItem(cx: Scope, post_view: MaybeSignal<PostView>) -> impl IntoView {
 
                     // Image/Thumbnail Column
                     <div class="col-md-3 align-self-center text-center">
-                        <img
-                            src=post_thumbnail
-                            class="img-fluid rounded-start"
-                            alt="..."
-                            style="max-height: 300px;"
-                        />
+                        <NsfwBlur nsfw=post_nsfw>
+                            <PostThumbnail
+                                post=thumbnail_post.clone()
+                                media=thumbnail_media.clone()
+                                expanded=media_expanded
+                            />
+                        </NsfwBlur>
                     </div>
 
                     // Content Column
@@ -681,26 +1065,33 @@ pub fn FeedItem(cx: Scope, post_view: MaybeSignal<PostView>) -> impl IntoView {
                         <div class="card-body" style="flex: 1 0 auto;">
                             <h5 class="card-title">
                                 <a
+                                    id=title_id.clone()
                                     href=post_link.clone()
                                     class="link-offset-2 link-underline link-underline-opacity-0"
                                 >
                                     {post_title}
                                 </a>
-                                {if post_pin {
-                                    view! { cx, <i class="bi bi-pin-angle-fill"></i> }
-                                } else {
-                                    view! { cx, <i></i> }
-                                }}
+                                {post_pin.then(|| view! { cx, <PinnedIcon/> })}
+                                {post_nsfw.then(|| view! { cx, <NsfwBadge/> })}
+                                {media_expandable
+                                    .then(|| view! { cx, <ExpandMediaButton expanded=media_expanded/> })}
 
                             </h5>
+                            <NsfwBlur nsfw=post_nsfw>
+                                <PostMediaView
+                                    post=media_post.clone()
+                                    media=media.clone()
+                                    expanded=media_expanded
+                                />
+                            </NsfwBlur>
                             <p class="card-text">{post_truncated}</p>
                             <br/>
                         </div>
                         <span class="m-3">
                             <a href=creator_link class="">
                                 <img
-                                    src=creator_avatar
-                                    alt="mdo"
+                                    src=proxied_image(creator_avatar, Some(64))
+                                    alt=""
                                     width="32"
                                     height="32"
                                     class="rounded"
@@ -708,16 +1099,21 @@ pub fn FeedItem(cx: Scope, post_view: MaybeSignal<PostView>) -> impl IntoView {
                                 "  "
                                 {creator_name}
                             </a>
+                            <span class="small text-body-secondary">
+                                " · "
+                                <TimeAgo time=published/>
+                                {edited}
+                            </span>
                         </span>
                         <div class="card-footer me-0">
                             <div class="row align-items-center">
                                 <div class="col-sm-5">
                                     <span class="text-nowrap">
-                                        <i class="bi bi-caret-up text-center"></i>
+                                        <PostActionButton icon="bi bi-caret-up" label="post-upvote"/>
                                         " "
-                                        {post.counts.upvotes - post.counts.downvotes}
+                                        <PostScore score=score.into()/>
                                         " "
-                                        <i class="bi bi-caret-down text-center"></i>
+                                        <PostActionButton icon="bi bi-caret-down" label="post-downvote"/>
                                         "   "
                                         <div class="vr"></div>
                                         "   "
@@ -725,24 +1121,23 @@ pub fn FeedItem(cx: Scope, post_view: MaybeSignal<PostView>) -> impl IntoView {
                                             href=post_link
                                             class="link-secondary link-offset-2 link-underline link-underline-opacity-0"
                                         >
-                                            <i class="bi bi-chat-right-text"></i>
+                                            <i aria-hidden="true" class="bi bi-chat-right-text"></i>
                                             " "
-                                            {comment_count}
-                                            " Comments"
+                                            {t_count(cx, "comments-count", comment_count.unwrap_or_default().into())}
                                         </a>
                                         "   "
-                                        <i class="bi bi-bookmark-star text-secondary"></i>
+                                        <PostActionButton icon="bi bi-bookmark-star" label="post-save"/>
                                         "   "
-                                        <i class="bi bi-signpost-split text-secondary"></i>
+                                        <PostActionButton icon="bi bi-signpost-split" label="post-cross-post"/>
                                         "   "
-                                        <i class="bi bi-flag text-secondary"></i>
+                                        <PostActionButton icon="bi bi-flag" label="post-report"/>
                                     </span>
                                 </div>
                                 <div class="col-sm-7 text-end">
                                     <a href=community_link>
                                         <img
-                                            src=community_avatar
-                                            alt="mdo"
+                                            src=proxied_image(community_avatar, Some(64))
+                                            alt=""
                                             width="32"
                                             height="32"
                                             class="rounded"
@@ -759,4 +1154,5 @@ pub fn FeedItem(cx: Scope, post_view: MaybeSignal<PostView>) -> impl IntoView {
             </div>
         </div>
     }
+    .into_view(cx)
 }
@@ -0,0 +1,248 @@
+use leptos::*;
+
+use crate::api::{get_cookie, set_cookie};
+use crate::components::i18n::t;
+
+// TODO - feed_layout.rs:
+// Save the layout to the user's settings once there is a settings page, instead of only in a cookie
+// Restore the scroll position when coming back to an infinite scrolling feed
+
+// The preferences are kept in cookies rather than local storage, so the server renders the feed the same way the browser will
+const LAYOUT_COOKIE: &str = "wolfrs_feed_layout";
+const PAGING_COOKIE: &str = "wolfrs_feed_paging";
+
+// How the posts in the feed are laid out
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum FeedLayout {
+    // The full size items with a thumbnail column, which is the original layout
+    #[default]
+    List,
+    // A single line for each post, with a small thumbnail
+    Compact,
+    // A grid of cards with a large image on top, for image heavy communities
+    Cards,
+}
+
+impl FeedLayout {
+    pub const ALL: [FeedLayout; 3] = [FeedLayout::List, FeedLayout::Compact, FeedLayout::Cards];
+
+    // The name of the layout in the locale files
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            FeedLayout::List => "layout-list",
+            FeedLayout::Compact => "layout-compact",
+            FeedLayout::Cards => "layout-cards",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            FeedLayout::List => "bi bi-view-list",
+            FeedLayout::Compact => "bi bi-list",
+            FeedLayout::Cards => "bi bi-grid-3x2-gap",
+        }
+    }
+
+    fn cookie_value(&self) -> &'static str {
+        match self {
+            FeedLayout::List => "list",
+            FeedLayout::Compact => "compact",
+            FeedLayout::Cards => "cards",
+        }
+    }
+
+    fn from_cookie_value(value: &str) -> Option<Self> {
+        FeedLayout::ALL
+            .into_iter()
+            .find(|layout| layout.cookie_value() == value)
+    }
+}
+
+// Whether the feed is split into pages, or loads the next page when the bottom of the feed is reached
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum FeedPaging {
+    #[default]
+    Pages,
+    InfiniteScroll,
+}
+
+impl FeedPaging {
+    fn cookie_value(&self) -> &'static str {
+        match self {
+            FeedPaging::Pages => "pages",
+            FeedPaging::InfiniteScroll => "infinite",
+        }
+    }
+
+    fn from_cookie_value(value: &str) -> Option<Self> {
+        match value {
+            "pages" => Some(FeedPaging::Pages),
+            "infinite" => Some(FeedPaging::InfiniteScroll),
+            _ => None,
+        }
+    }
+}
+
+// The user's choice of layout and paging for every feed, which is remembered in their browser
+#[derive(Clone, Copy)]
+pub struct FeedPreferences {
+    pub layout: RwSignal<FeedLayout>,
+    pub paging: RwSignal<FeedPaging>,
+}
+
+impl FeedPreferences {
+    pub fn from_cookies(cx: Scope) -> Self {
+        let layout = get_cookie(cx, LAYOUT_COOKIE)
+            .and_then(|value| FeedLayout::from_cookie_value(&value))
+            .unwrap_or_default();
+        let paging = get_cookie(cx, PAGING_COOKIE)
+            .and_then(|value| FeedPaging::from_cookie_value(&value))
+            .unwrap_or_default();
+
+        FeedPreferences {
+            layout: create_rw_signal(cx, layout),
+            paging: create_rw_signal(cx, paging),
+        }
+    }
+
+    pub fn set_layout(&self, layout: FeedLayout) {
+        set_cookie(LAYOUT_COOKIE, layout.cookie_value());
+        self.layout.set(layout);
+    }
+
+    pub fn set_paging(&self, paging: FeedPaging) {
+        set_cookie(PAGING_COOKIE, paging.cookie_value());
+        self.paging.set(paging);
+    }
+}
+
+// The dropdown in the feed header for choosing the layout and paging
+#[component]
+pub fn FeedLayoutMenu(cx: Scope, preferences: FeedPreferences) -> impl IntoView {
+    view! { cx,
+        <div class="dropdown text-end">
+            <button
+                class="btn btn-secondary dropdown-toggle"
+                style="margin-bottom: -0.50rem; margin-top: -0.25rem;"
+                type="button"
+                title=t(cx, "layout")
+                aria-label=t(cx, "layout")
+                data-bs-toggle="dropdown"
+                aria-expanded="false"
+            >
+                <i aria-hidden="true" class=move || preferences.layout.get().icon()></i>
+            </button>
+            <ul class="dropdown-menu dropdown-menu-end">
+                {FeedLayout::ALL
+                    .into_iter()
+                    .map(|layout| {
+                        view! { cx,
+                            <li>
+                                <button
+                                    type="button"
+                                    class="dropdown-item"
+                                    class:active=move || preferences.layout.get() == layout
+                                    aria-pressed=move || (preferences.layout.get() == layout).to_string()
+                                    on:click=move |_| preferences.set_layout(layout)
+                                >
+                                    <i aria-hidden="true" class=layout.icon()></i>
+                                    " "
+                                    {t(cx, layout.message_id())}
+                                </button>
+                            </li>
+                        }
+                    })
+                    .collect_view(cx)}
+                <li>
+                    <hr class="dropdown-divider"/>
+                </li>
+                <li>
+                    <button
+                        type="button"
+                        class="dropdown-item"
+                        role="switch"
+                        aria-checked=move || (preferences.paging.get() == FeedPaging::InfiniteScroll).to_string()
+                        on:click=move |_| {
+                            preferences
+                                .set_paging(
+                                    match preferences.paging.get_untracked() {
+                                        FeedPaging::Pages => FeedPaging::InfiniteScroll,
+                                        FeedPaging::InfiniteScroll => FeedPaging::Pages,
+                                    },
+                                );
+                        }
+                    >
+                        <i aria-hidden="true" class=move || {
+                            if preferences.paging.get() == FeedPaging::InfiniteScroll {
+                                "bi bi-check-square"
+                            } else {
+                                "bi bi-square"
+                            }
+                        }></i>
+                        " "
+                        {t(cx, "layout-infinite-scroll")}
+                    </button>
+                </li>
+            </ul>
+        </div>
+    }
+}
+
+// An empty element at the bottom of an infinite scrolling feed, which calls on_visible once it comes close to being scrolled into view.
+// It only fires once, so it should be rendered again after each page has loaded.
+#[component]
+pub fn InfiniteScrollSentinel<F>(cx: Scope, on_visible: F) -> impl IntoView
+where
+    F: Fn() + 'static,
+{
+    let sentinel = create_node_ref::<html::Div>(cx);
+
+    #[cfg(not(feature = "ssr"))]
+    observe_sentinel(cx, sentinel, on_visible);
+    #[cfg(feature = "ssr")]
+    let _ = (sentinel, on_visible);
+
+    view! { cx,
+        <div node_ref=sentinel class="d-flex justify-content-center py-3">
+            <div class="spinner-grow" role="status">
+                <span class="visually-hidden">{t(cx, "loading-more-posts")}</span>
+            </div>
+        </div>
+    }
+}
+
+#[cfg(not(feature = "ssr"))]
+fn observe_sentinel(cx: Scope, sentinel: NodeRef<html::Div>, on_visible: impl Fn() + 'static) {
+    use std::cell::Cell;
+    use wasm_bindgen::{closure::Closure, JsCast};
+
+    sentinel.on_load(cx, move |element| {
+        let fired = Cell::new(false);
+        let callback = Closure::<dyn Fn(js_sys::Array)>::new(move |entries: js_sys::Array| {
+            let visible = entries
+                .iter()
+                .filter_map(|entry| entry.dyn_into::<web_sys::IntersectionObserverEntry>().ok())
+                .any(|entry| entry.is_intersecting());
+            if visible && !fired.replace(true) {
+                on_visible();
+            }
+        });
+
+        // The next page starts loading a little before the bottom is reached, so scrolling doesn't stop and wait for it
+        let options = web_sys::IntersectionObserverInit::new();
+        options.set_root_margin("0px 0px 800px 0px");
+
+        let Ok(observer) = web_sys::IntersectionObserver::new_with_options(
+            callback.as_ref().unchecked_ref(),
+            &options,
+        ) else {
+            return;
+        };
+        observer.observe(&element);
+
+        on_cleanup(cx, move || {
+            observer.disconnect();
+            drop(callback);
+        });
+    });
+}
@@ -1,6 +1,8 @@
 use leptos::*;
 
+use crate::components::i18n::{t, translate_with};
 use crate::components::instance::*;
+use crate::components::shortcuts::use_shortcuts;
 
 // The Footer for the site
 #[component]
@@ -12,19 +14,21 @@ pub fn Footer(cx: Scope) -> impl IntoView {
     let width = get_instance_details().unwrap().logo_width;
     let height = get_instance_details().unwrap().logo_height;
     let name = get_instance_details().unwrap().name;
+    let logo_alt = translate_with(cx, "logo-alt", &[("name", name.clone().into())]);
     let slogan = get_instance_details().unwrap().slogan;
     let documentation_url = get_instance_details().unwrap().documentation_url;
     let source_code_url = get_instance_details().unwrap().source_code_url;
+    let shortcuts = use_shortcuts(cx);
 
     view! { cx,
         <div class="container">
             <footer class="d-flex flex-wrap justify-content-between align-items-center py-3 my-4 border-top">
                 <p class="col-md-4 mb-0 text-body-secondary">{format!("{}: {}", name, slogan)}</p>
 
-                <a class="navbar-brand" href="#">
+                <a class="navbar-brand" href="/">
                     <img
                         src=format!("/assets/{}", logo_source)
-                        alt="Logo"
+                        alt=logo_alt
                         width=format!("{}", width)
                         height=format!("{}", height)
                         class="d-inline-block align-text-top"
@@ -34,12 +38,12 @@ pub fn Footer(cx: Scope) -> impl IntoView {
                 <ul class="nav col-md-5 justify-content-end">
                     <li class="nav-item">
                         <a href="/modlog" class="nav-link px-2 text-body-secondary">
-                            {"Modlog"}
+                            {t(cx, "footer-modlog")}
                         </a>
                     </li>
                     <li class="nav-item">
                         <a href="/federation" class="nav-link px-2 text-body-secondary">
-                            {"Federation"}
+                            {t(cx, "footer-federation")}
                         </a>
                     </li>
                     <li class="nav-item">
@@ -47,7 +51,7 @@ pub fn Footer(cx: Scope) -> impl IntoView {
                             href=documentation_url.to_string()
                             class="nav-link px-2 text-body-secondary"
                         >
-                            {"Documentation"}
+                            {t(cx, "footer-documentation")}
                         </a>
                     </li>
                     <li class="nav-item">
@@ -55,14 +59,23 @@ pub fn Footer(cx: Scope) -> impl IntoView {
                             href=source_code_url.to_string()
                             class="nav-link px-2 text-body-secondary"
                         >
-                            {"Source Code"}
+                            {t(cx, "footer-source-code")}
                         </a>
                     </li>
                     <li class="nav-item">
                         <a href="https://join-lemmy.org/" class="nav-link px-2 text-body-secondary">
-                            {"Lemmy Info"}
+                            {t(cx, "footer-lemmy-info")}
                         </a>
                     </li>
+                    <li class="nav-item">
+                        <button
+                            type="button"
+                            class="nav-link px-2 text-body-secondary"
+                            on:click=move |_| shortcuts.toggle_help()
+                        >
+                            {t(cx, "footer-shortcuts")}
+                        </button>
+                    </li>
                 </ul>
             </footer>
         </div>
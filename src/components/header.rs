@@ -1,6 +1,8 @@
 use leptos::*;
 
+use crate::components::i18n::{t, translate_with, LanguageMenu};
 use crate::components::instance::*;
+use crate::components::theme::ThemeMenu;
 
 // The Navbar Header for the UI
 #[component]
@@ -15,6 +17,11 @@ pub fn Header(cx: Scope) -> impl IntoView {
         true => get_instance_details().unwrap().name,
         false => "".to_string(),
     };
+    let logo_alt = translate_with(
+        cx,
+        "logo-alt",
+        &[("name", get_instance_details().unwrap().name.into())],
+    );
     //TODO: Change how default image is handled
     let temp_img_src = "/assets/favicon.ico";
 
@@ -24,7 +31,7 @@ pub fn Header(cx: Scope) -> impl IntoView {
                 <a class="navbar-brand" href="/">
                     <img
                         src=format!("/assets/{}", logo_source)
-                        alt="Logo"
+                        alt=logo_alt
                         width=format!("{}", width)
                         height=format!("{}", height)
                         class="d-inline-block align-text-top"
@@ -38,26 +45,25 @@ pub fn Header(cx: Scope) -> impl IntoView {
                     data-bs-target="#navbarSupportedContent"
                     aria-controls="navbarSupportedContent"
                     aria-expanded="false"
-                    aria-label="Toggle navigation"
+                    aria-label=t(cx, "nav-toggle")
                 >
                     <span class="navbar-toggler-icon"></span>
                 </button>
                 <div class="collapse navbar-collapse" id="navbarSupportedContent">
                     <ul class="navbar-nav me-auto mb-2 mb-lg-0">
                         <li class="nav-item dropdown">
-                            <a
+                            <button
+                                type="button"
                                 class="nav-link dropdown-toggle"
-                                href="#"
-                                role="button"
                                 data-bs-toggle="dropdown"
                                 aria-expanded="false"
                             >
-                                {"Menu"}
-                            </a>
+                                {t(cx, "nav-menu")}
+                            </button>
                             <ul class="dropdown-menu">
                                 <li>
                                     <a class="dropdown-item" href="/communities">
-                                        {"Explore Communities"}
+                                        {t(cx, "nav-explore-communities")}
                                     </a>
                                 </li>
                                 <li>
@@ -65,12 +71,12 @@ pub fn Header(cx: Scope) -> impl IntoView {
                                 </li>
                                 <li>
                                     <a class="dropdown-item" href="/create_post">
-                                        {"Create Post"}
+                                        {t(cx, "nav-create-post")}
                                     </a>
                                 </li>
                                 <li>
                                     <a class="dropdown-item" href="/create_community">
-                                        {"Create Communitiy"}
+                                        {t(cx, "nav-create-community")}
                                     </a>
                                 </li>
                             </ul>
@@ -87,38 +93,53 @@ pub fn Header(cx: Scope) -> impl IntoView {
                             // </div>
                             // </a>
                             <div class="dropdown">
+                                // The id is how the / shortcut opens the search
                                 <button
                                     type="button"
                                     class="btn btn-primary dropdown-toggle"
+                                    id="search-toggle"
                                     data-bs-toggle="dropdown"
                                     aria-expanded="false"
                                     data-bs-auto-close="outside"
+                                    aria-label=t(cx, "search-label")
+                                    title=t(cx, "search-label")
                                 >
-                                    <i class="bi bi-search"></i>
+                                    <i aria-hidden="true" class="bi bi-search"></i>
                                 </button>
                                 <form class="dropdown-menu p-4">
                                     <div class="mb-3">
                                         <label for="searchBox" class="form-label">
-                                            "Search Lemmy"
+                                            {t(cx, "search-label")}
                                         </label>
                                         <input
                                             type="search"
                                             class="form-control"
                                             id="searchBox"
-                                            placeholder="Enter search text here..."
+                                            placeholder=t(cx, "search-placeholder")
                                         />
                                     </div>
-                                    <button type="submit" class="btn btn-primary">
-                                        <i class="bi bi-search"></i>
+                                    <button type="submit" class="btn btn-primary" aria-label=t(cx, "search")>
+                                        <i aria-hidden="true" class="bi bi-search"></i>
                                     </button>
                                 </form>
                             </div>
                         </div>
                         <div class="col">
-                            <a class="btn btn-primary" href="/notifications" role="button">
+                            <ThemeMenu/>
+                        </div>
+                        <div class="col">
+                            <LanguageMenu/>
+                        </div>
+                        <div class="col">
+                            <a
+                                class="btn btn-primary"
+                                href="/notifications"
+                                aria-label=t(cx, "notifications")
+                                title=t(cx, "notifications")
+                            >
                                 <div class="row">
                                     <div class="col col-sm-12">
-                                        <i class="bi bi-bell"></i>
+                                        <i aria-hidden="true" class="bi bi-bell"></i>
                                     </div>
                                     <div class="col col-sm-0">{""}</div>
                                 </div>
@@ -136,31 +157,31 @@ pub fn Header(cx: Scope) -> impl IntoView {
                                 >
                                     <img
                                         src=temp_img_src.to_string()
-                                        alt="mdo"
+                                        alt=""
                                         width="32"
                                         height="32"
                                         class="rounded-circle"
                                     />
-                                    {"Sign In/Create Account"}
+                                    {t(cx, "nav-sign-in")}
                                 </button>
                                 <ul class="dropdown-menu text-small">
                                     <li>
                                         <a class="dropdown-item" href="/user/login">
-                                            {"Profile"}
+                                            {t(cx, "nav-profile")}
                                         </a>
                                     </li>
                                     <li>
                                         <a class="dropdown-item" href="/settings">
-                                            {"Settings"}
+                                            {t(cx, "nav-settings")}
                                         </a>
                                     </li>
                                     <li>
                                         <hr class="dropdown-divider"/>
                                     </li>
                                     <li>
-                                        <a class="dropdown-item" href="#">
-                                            {"Sign out"}
-                                        </a>
+                                        <button type="button" class="dropdown-item">
+                                            {t(cx, "nav-sign-out")}
+                                        </button>
                                     </li>
                                 </ul>
                             </div>
@@ -0,0 +1,338 @@
+use std::collections::HashMap;
+
+use fluent_templates::fluent_bundle::FluentValue;
+use fluent_templates::{static_loader, LanguageIdentifier, Loader};
+use leptos::*;
+
+use crate::api::structs::*;
+use crate::api::user::save_user_settings;
+use crate::api::*;
+use crate::components::site::{refetch_site, with_site};
+use crate::timestamps::Age;
+
+// TODO - i18n.rs:
+// Move the language picker into the settings page once it exists, and keep the header button as a shortcut
+
+// The UI's text is kept in Fluent files in locales/, one folder for each language, and compiled into both the server and the WASM.
+// Messages missing from a translation fall back to English, so a translation can be added a few pages at a time.
+static_loader! {
+    static LOCALES = {
+        locales: "./locales",
+        fallback_language: "en",
+        // Fluent wraps arguments in invisible marks for right-to-left text by default, which would end up in attributes and copied text
+        customise: |bundle| bundle.set_use_isolating(false),
+    };
+}
+
+// The language is kept in a cookie once it has been picked, the same way as the theme.
+// Until then it comes from the Accept-Language header, which the server renders into <html lang> for the browser to carry on with.
+const LOCALE_COOKIE: &str = "wolfrs_locale";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Locale {
+    #[default]
+    English,
+    French,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::English, Locale::French];
+
+    // The language's tag, which is the name of its folder in locales/ and what Lemmy calls its interface_language
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            Locale::French => "fr",
+        }
+    }
+
+    // The language's name in itself, so it can be found in the picker by people who can't read the current language
+    pub fn native_name(&self) -> &'static str {
+        match self {
+            Locale::English => "English",
+            Locale::French => "Français",
+        }
+    }
+
+    // Matches a language tag such as fr, fr-CH, or Lemmy's pt_BR on its language, ignoring the region
+    pub fn from_code(code: &str) -> Option<Self> {
+        let language = code
+            .trim()
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+
+        Locale::ALL
+            .into_iter()
+            .find(|locale| locale.code() == language)
+    }
+
+    fn language_identifier(&self) -> LanguageIdentifier {
+        self.code().parse().unwrap_or_default()
+    }
+}
+
+// Picks the best supported language from an Accept-Language header, such as "fr-CH, fr;q=0.9, en;q=0.8, *;q=0.5"
+pub fn negotiate_locale(accept_language: &str) -> Option<Locale> {
+    let mut languages = accept_language
+        .split(',')
+        .filter_map(|language| {
+            let mut parts = language.split(';');
+            let tag = parts.next()?.trim();
+            let quality = parts
+                .find_map(|part| part.trim().strip_prefix("q="))
+                .map_or(Some(1.0), |quality| quality.trim().parse::<f32>().ok())?;
+
+            (!tag.is_empty() && quality > 0.0).then_some((tag, quality))
+        })
+        .collect::<Vec<_>>();
+    // The sort is stable, so languages with the same quality keep the order they were listed in
+    languages.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+    languages
+        .into_iter()
+        .find_map(|(tag, _)| Locale::from_code(tag))
+}
+
+// The locale the page was asked for in, before anyone has picked one
+#[cfg(feature = "ssr")]
+fn requested_locale(cx: Scope) -> Option<Locale> {
+    let request = use_context::<leptos_axum::RequestParts>(cx)?;
+
+    // The page is different for each language, so caches in front of Wolfrs have to keep them apart
+    if let Some(response) = use_context::<leptos_axum::ResponseOptions>(cx) {
+        response.append_header(
+            http::header::VARY,
+            http::HeaderValue::from_static("Accept-Language, Cookie"),
+        );
+    }
+
+    negotiate_locale(
+        request
+            .headers
+            .get(http::header::ACCEPT_LANGUAGE)?
+            .to_str()
+            .ok()?,
+    )
+}
+
+// The browser carries on in the language the server rendered the page in, so hydration doesn't change any text
+#[cfg(not(feature = "ssr"))]
+fn requested_locale(_cx: Scope) -> Option<Locale> {
+    let lang = document().document_element()?.get_attribute("lang")?;
+    Locale::from_code(&lang)
+}
+
+// The current language, which is provided as context by the App
+#[derive(Clone, Copy)]
+pub struct I18nContext {
+    // The language from the cookie or Accept-Language, which is used until the site has loaded and for anonymous users
+    requested: Locale,
+    // The language saved in the logged in user's Lemmy settings, unless it is set to follow the browser or isn't one Wolfrs has
+    saved: Signal<Option<Locale>>,
+    // The language picked from the menu on this page, which is used until the site is fetched again with it saved
+    picked: RwSignal<Option<Locale>>,
+}
+
+impl I18nContext {
+    pub fn get(&self) -> Locale {
+        self.picked
+            .get()
+            .or_else(|| self.saved.get())
+            .unwrap_or(self.requested)
+    }
+
+    // Changes the language for this browser. Saving it to the user's Lemmy settings is left to the caller, since it needs an action.
+    pub fn set(&self, locale: Locale) {
+        set_cookie(LOCALE_COOKIE, locale.code());
+        self.picked.set(Some(locale));
+    }
+}
+
+// Works out the language and provides it as context. Needs to be called after provide_site.
+// The language saved in Lemmy wins, and is read from the site resource rather than in an effect so the server renders the page in it too.
+pub fn provide_i18n(cx: Scope) {
+    let requested = get_cookie(cx, LOCALE_COOKIE)
+        .and_then(|value| Locale::from_code(&value))
+        .or_else(|| requested_locale(cx))
+        .unwrap_or_default();
+    // Only the language is read out of the site, since every message looks it up
+    let saved = Signal::derive(cx, move || {
+        with_site(cx, |site| {
+            site.my_user.as_ref().and_then(|my_user| {
+                Locale::from_code(&my_user.local_user_view.local_user.interface_language)
+            })
+        })
+        .flatten()
+    });
+    provide_context(
+        cx,
+        I18nContext {
+            requested,
+            saved,
+            picked: create_rw_signal(cx, None),
+        },
+    );
+
+    // The saved language is copied into the cookie, so pages the server renders before the site has loaded are already in it
+    create_effect(cx, move |_| {
+        if let Some(saved) = saved.get() {
+            if saved != requested {
+                set_cookie(LOCALE_COOKIE, saved.code());
+            }
+        }
+    });
+}
+
+// The current language, which is English for anything rendered outside the App
+pub fn use_locale(cx: Scope) -> Locale {
+    use_context::<I18nContext>(cx)
+        .map(|i18n| i18n.get())
+        .unwrap_or_default()
+}
+
+// Looks up a message in the current language. A message missing from every locale shows its id, so it's easy to spot.
+pub fn translate(cx: Scope, id: &str) -> String {
+    LOCALES
+        .lookup(&use_locale(cx).language_identifier(), id)
+        .unwrap_or_else(|| id.to_string())
+}
+
+// Looks up a message that has arguments, such as { $count } for plurals
+pub fn translate_with(cx: Scope, id: &str, args: &[(&str, FluentValue<'static>)]) -> String {
+    let args = args.iter().cloned().collect::<HashMap<_, _>>();
+    LOCALES
+        .lookup_with_args(&use_locale(cx).language_identifier(), id, &args)
+        .unwrap_or_else(|| id.to_string())
+}
+
+// A message for view!, which changes along with the language
+pub fn t(cx: Scope, id: &'static str) -> impl Fn() -> String + Copy + 'static {
+    move || translate(cx, id)
+}
+
+// A message with a number in it, which picks the right plural for the language, such as 1 Comment or 2 Comments
+pub fn t_count(cx: Scope, id: &'static str, count: i64) -> impl Fn() -> String + Copy + 'static {
+    move || translate_with(cx, id, &[("count", count.into())])
+}
+
+// How long ago something was, such as 3h ago
+pub fn translate_age(cx: Scope, age: Age) -> String {
+    let (id, count) = match age {
+        Age::JustNow => return translate(cx, "time-just-now"),
+        Age::Minutes(count) => ("time-minutes-ago", count),
+        Age::Hours(count) => ("time-hours-ago", count),
+        Age::Days(count) => ("time-days-ago", count),
+        Age::Months(count) => ("time-months-ago", count),
+        Age::Years(count) => ("time-years-ago", count),
+    };
+
+    translate_with(cx, id, &[("count", count.into())])
+}
+
+// The name of a sort, such as Top - Week
+pub fn translate_sort(cx: Scope, sort: &SortType) -> String {
+    match sort {
+        SortType::Active => translate(cx, "sort-active"),
+        SortType::Hot => translate(cx, "sort-hot"),
+        SortType::New => translate(cx, "sort-new"),
+        SortType::Old => translate(cx, "sort-old"),
+        SortType::MostComments => translate(cx, "sort-most-comments"),
+        SortType::NewComments => translate(cx, "sort-new-comments"),
+        SortType::TopHour => translate(cx, "sort-top-hour"),
+        SortType::TopSixHour => translate(cx, "sort-top-six-hour"),
+        SortType::TopTwelveHour => translate(cx, "sort-top-twelve-hour"),
+        SortType::TopDay => translate(cx, "sort-top-day"),
+        SortType::TopWeek => translate(cx, "sort-top-week"),
+        SortType::TopMonth => translate(cx, "sort-top-month"),
+        SortType::TopThreeMonths => translate(cx, "sort-top-three-months"),
+        SortType::TopSixMonths => translate(cx, "sort-top-six-months"),
+        SortType::TopNineMonths => translate(cx, "sort-top-nine-months"),
+        SortType::TopYear => translate(cx, "sort-top-year"),
+        SortType::TopAll => translate(cx, "sort-top-all"),
+    }
+}
+
+// The name of a listing, such as Local
+pub fn translate_listing(cx: Scope, listing: &ListingType) -> String {
+    match listing {
+        ListingType::All => translate(cx, "listing-all"),
+        ListingType::Local => translate(cx, "listing-local"),
+        ListingType::Subscribed => translate(cx, "listing-subscribed"),
+    }
+}
+
+// The button in the header for picking the language of the UI
+#[component]
+pub fn LanguageMenu(cx: Scope) -> impl IntoView {
+    let i18n = use_context::<I18nContext>(cx).expect("provide_i18n should be called by the App");
+
+    let save_action = create_action(cx, move |locale: &Locale| {
+        let locale = *locale;
+        async move {
+            let Some(auth) = get_auth_token(cx) else {
+                return;
+            };
+
+            let url_constructor = ApiUrlConstructor {
+                endpoint: api_endpoints::PutEndpoint::SAVE_USER_SETTINGS.to_string(),
+                id: None,
+                params: None,
+            };
+
+            let form = SaveUserSettings {
+                auth,
+                interface_language: Some(locale.code().to_string()),
+                ..Default::default()
+            };
+
+            // The site is fetched again so my_user has the new language, otherwise it would be switched back the next time the site loads
+            if save_user_settings(cx, &api_post_url_builder(url_constructor), &form)
+                .await
+                .is_ok()
+            {
+                refetch_site(cx);
+            }
+        }
+    });
+
+    view! { cx,
+        <div class="dropdown">
+            <button
+                type="button"
+                class="btn btn-primary dropdown-toggle"
+                title=t(cx, "language")
+                aria-label=t(cx, "language")
+                data-bs-toggle="dropdown"
+                aria-expanded="false"
+            >
+                <i aria-hidden="true" class="bi bi-translate"></i>
+            </button>
+            <ul class="dropdown-menu dropdown-menu-end">
+                {Locale::ALL
+                    .into_iter()
+                    .map(|locale| {
+                        view! { cx,
+                            <li>
+                                <button
+                                    type="button"
+                                    class="dropdown-item"
+                                    class:active=move || i18n.get() == locale
+                                    aria-pressed=move || (i18n.get() == locale).to_string()
+                                    lang=locale.code()
+                                    on:click=move |_| {
+                                        i18n.set(locale);
+                                        save_action.dispatch(locale);
+                                    }
+                                >
+                                    {locale.native_name()}
+                                </button>
+                            </li>
+                        }
+                    })
+                    .collect_view(cx)}
+            </ul>
+        </div>
+    }
+}
@@ -0,0 +1,198 @@
+use leptos::*;
+use wasm_bindgen::JsCast;
+
+use crate::api::image::{delete_image, upload_image};
+use crate::api::structs::*;
+use crate::components::i18n::{t, translate};
+
+// TODO - image_upload.rs:
+// Support for uploading videos, once pictrs on Lemmy supports them
+// Resize large images before uploading them
+
+// Adds the URL of an uploaded image to the text, either as a Markdown image or as the whole value of a URL field
+fn insert_image(text: RwSignal<String>, url: &str, markdown: bool) {
+    if markdown {
+        text.update(|text| {
+            if !text.is_empty() && !text.ends_with('\n') {
+                text.push('\n');
+            }
+            text.push_str(&format!("![]({})\n", url));
+        })
+    } else {
+        text.set(url.to_string());
+    }
+}
+
+// Takes the URL of a deleted image back out of the text, so a broken image isn't left behind
+fn remove_image(text: RwSignal<String>, url: &str, markdown: bool) {
+    if markdown {
+        text.update(|text| *text = text.replace(&format!("![]({})\n", url), ""));
+    } else if text.get() == url {
+        text.set(String::new());
+    }
+}
+
+// Pulls the image files out of a FileList, ignoring anything else that was pasted or dropped
+fn image_files(files: Option<web_sys::FileList>) -> Vec<web_sys::File> {
+    let Some(files) = files else {
+        return Vec::new();
+    };
+
+    (0..files.length())
+        .filter_map(|index| files.get(index))
+        .filter(|file| file.type_().starts_with("image/"))
+        .collect()
+}
+
+// Wraps a Markdown editor or URL field so images can be uploaded to pictrs by pasting, dragging and dropping, or picking a file.
+// The URL of each uploaded image is added to the text, and the images uploaded here can be deleted again with their delete token.
+#[component]
+pub fn ImageUpload(
+    cx: Scope,
+    text: RwSignal<String>,
+    children: Children,
+    #[prop(optional)] markdown: bool,
+) -> impl IntoView {
+    let progress = create_rw_signal::<Option<u32>>(cx, None);
+    let uploaded = create_rw_signal::<Vec<UploadImageResponse>>(cx, Vec::new());
+
+    let upload_action = create_action(cx, move |file: &web_sys::File| {
+        let file = file.clone();
+        async move {
+            progress.set(Some(0));
+            let result = upload_image(file, move |percent| progress.set(Some(percent))).await;
+            progress.set(None);
+
+            let response = result.map_err(|err| err.to_string())?;
+            insert_image(text, &response.url.clone().unwrap_or_default(), markdown);
+            uploaded.update(|uploaded| uploaded.push(response));
+            Ok::<(), String>(())
+        }
+    });
+    let upload_result = upload_action.value();
+
+    let delete_action = create_action(cx, move |response: &UploadImageResponse| {
+        let response = response.clone();
+        let no_delete_token = translate(cx, "upload-no-delete-token");
+        async move {
+            let image_file = response
+                .files
+                .first()
+                .cloned()
+                .ok_or(no_delete_token)?;
+            delete_image(&image_file)
+                .await
+                .map_err(|err| err.to_string())?;
+
+            let url = response.url.unwrap_or_default();
+            remove_image(text, &url, markdown);
+            uploaded.update(|uploaded| uploaded.retain(|image| image.url.as_deref() != Some(&url)));
+            Ok::<(), String>(())
+        }
+    });
+    let delete_result = delete_action.value();
+
+    let upload_files = move |files: Option<web_sys::FileList>| {
+        for file in image_files(files) {
+            upload_action.dispatch(file);
+        }
+    };
+
+    view! { cx,
+        <div
+            on:paste=move |ev| {
+                let files = ev
+                    .unchecked_ref::<web_sys::ClipboardEvent>()
+                    .clipboard_data()
+                    .and_then(|data| data.files());
+                if files.as_ref().map(|files| files.length() > 0).unwrap_or_default() {
+                    ev.prevent_default();
+                    upload_files(files);
+                }
+            }
+            on:dragover=move |ev: web_sys::DragEvent| ev.prevent_default()
+            on:drop=move |ev: web_sys::DragEvent| {
+                let files = ev.data_transfer().and_then(|data| data.files());
+                if files.as_ref().map(|files| files.length() > 0).unwrap_or_default() {
+                    ev.prevent_default();
+                    upload_files(files);
+                }
+            }
+        >
+            {children(cx)}
+            <div class="d-flex align-items-center gap-2 mt-2">
+                <label class="btn btn-outline-secondary btn-sm mb-0" title=t(cx, "upload-image-title")>
+                    <i aria-hidden="true" class="bi bi-image"></i>
+                    " "
+                    {t(cx, "upload-image")}
+                    <input
+                        type="file"
+                        accept="image/*"
+                        class="d-none"
+                        multiple=markdown
+                        on:change=move |ev| {
+                            upload_files(event_target::<web_sys::HtmlInputElement>(&ev).files())
+                        }
+                    />
+                </label>
+                <small class="text-body-secondary">{t(cx, "upload-paste-or-drop")}</small>
+            </div>
+            {move || {
+                progress
+                    .get()
+                    .map(|percent| {
+                        view! { cx,
+                            <div
+                                class="progress mt-2"
+                                role="progressbar"
+                                aria-label=t(cx, "upload-progress")
+                                aria-valuenow=percent
+                                aria-valuemin="0"
+                                aria-valuemax="100"
+                            >
+                                <div
+                                    class="progress-bar progress-bar-striped progress-bar-animated"
+                                    style=format!("width: {}%", percent)
+                                >
+                                    {format!("{}%", percent)}
+                                </div>
+                            </div>
+                        }
+                    })
+            }}
+            {move || match (upload_result.get(), delete_result.get()) {
+                (Some(Err(error)), _) | (_, Some(Err(error))) => {
+                    view! { cx, <div class="alert alert-warning mt-2 mb-0">{error}</div> }
+                        .into_view(cx)
+                }
+                _ => ().into_view(cx),
+            }}
+            <ul class="list-unstyled mb-0">
+                {move || {
+                    uploaded
+                        .get()
+                        .into_iter()
+                        .map(|image| {
+                            let url = image.url.clone().unwrap_or_default();
+                            view! { cx,
+                                <li class="d-flex align-items-center gap-2 mt-2">
+                                    <img src=url.clone() alt=t(cx, "upload-uploaded-image") width="32" height="32" class="rounded"/>
+                                    <a href=url.clone() target="_blank" class="text-truncate small">
+                                        {url}
+                                    </a>
+                                    <button
+                                        type="button"
+                                        class="btn btn-outline-danger btn-sm"
+                                        on:click=move |_| delete_action.dispatch(image.clone())
+                                    >
+                                        {t(cx, "upload-delete")}
+                                    </button>
+                                </li>
+                            }
+                        })
+                        .collect_view(cx)
+                }}
+            </ul>
+        </div>
+    }
+}
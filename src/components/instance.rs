@@ -1,15 +1,27 @@
-use serde::Deserialize;
+use cfg_if::cfg_if;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use thiserror::Error;
+
+// TODO - instance.rs:
+// Reload the configuration in browsers that already have the page open, instead of only for new page loads
+
+// The instance's configuration is loaded by the server when it starts, from Instance.toml or the file set in WOLFRS_CONFIG, and can be reloaded with SIGHUP.
+// The server renders it into every page, which is where the browser reads it from, so one build of the WASM works with any instance.
 
 #[derive(Debug, Deserialize)]
 pub struct InstanceToml {
     pub instance: Instance,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Instance {
     pub name: String,
     pub slogan: String,
     pub url: String,
+    // The URL Wolfrs itself is served at, for canonical links and the sitemap, when it isn't the same as the Lemmy url
+    #[serde(default)]
+    pub public_url: Option<String>,
     pub api_version: String,
     pub donation_url: String,
     pub source_code_url: String,
@@ -20,10 +32,338 @@ pub struct Instance {
     pub logo_width: i32,
     pub logo_height: i32,
     pub favicon_name: String,
+    #[serde(default = "default_enable_nsfw")]
+    pub enable_nsfw: bool,
+    #[serde(default)]
+    pub image_proxy: bool,
+    #[serde(default = "default_image_proxy_cache_dir")]
+    pub image_proxy_cache_dir: String,
+    // How many megabytes of images the image proxy keeps on disk before the oldest are removed
+    #[serde(default = "default_image_proxy_cache_size_mb")]
+    pub image_proxy_cache_size_mb: u64,
+    // How many seconds anonymous responses from each API endpoint are cached for on the server
+    #[serde(default)]
+    pub api_cache_ttls: HashMap<String, u64>,
+    // Whether the cache's hit and miss counts are served at /metrics/api_cache, which is off so they aren't public unless asked for
+    #[serde(default)]
+    pub api_cache_metrics: bool,
+    // The instance's colours and font, which are laid over Bootstrap's defaults
+    #[serde(default)]
+    pub palette: InstancePalette,
+}
+
+fn default_enable_nsfw() -> bool {
+    true
+}
+
+fn default_image_proxy_cache_dir() -> String {
+    "image_cache".to_string()
+}
+
+fn default_image_proxy_cache_size_mb() -> u64 {
+    1024
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InstancePalette {
+    // A CSS font stack for the whole site, such as "Inter, system-ui, sans-serif"
+    #[serde(default)]
+    pub font_family: Option<String>,
+    #[serde(default)]
+    pub light: PaletteColours,
+    #[serde(default)]
+    pub dark: PaletteColours,
+}
+
+// The colours for one theme, as #rrggbb hex codes. Any that are left out keep Bootstrap's colour.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PaletteColours {
+    #[serde(default)]
+    pub primary: Option<String>,
+    #[serde(default)]
+    pub secondary: Option<String>,
+    #[serde(default)]
+    pub accent: Option<String>,
+}
+
+impl PaletteColours {
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &String)> {
+        [
+            ("primary", &self.primary),
+            ("secondary", &self.secondary),
+            ("accent", &self.accent),
+        ]
+        .into_iter()
+        .filter_map(|(name, colour)| colour.as_ref().map(|colour| (name, colour)))
+    }
+}
+
+// Splits a #rrggbb colour into its red, green, and blue parts, which Bootstrap needs for its -rgb variables
+pub fn hex_colour_rgb(colour: &str) -> Option<(u8, u8, u8)> {
+    let hex = colour.strip_prefix('#')?;
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |range: std::ops::Range<usize>| u8::from_str_radix(&hex[range], 16).ok();
+
+    Some((channel(0..2)?, channel(2..4)?, channel(4..6)?))
+}
+
+#[derive(Debug, Error)]
+pub enum InstanceError {
+    #[error("Couldn't read the instance configuration from {path}: {source}")]
+    Read {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("Couldn't parse the instance configuration in {path}: {source}")]
+    Parse {
+        path: String,
+        source: toml::de::Error,
+    },
+    #[error("The environment variable {variable} must be true or false, not {value:?}")]
+    Override { variable: &'static str, value: String },
+    #[error("The instance configuration is invalid: {0}")]
+    Invalid(String),
+    #[error("The instance configuration wasn't found in the page, it should be rendered by the server")]
+    Missing,
+}
+
+// The id of the <script> element the server renders the configuration into
+pub const INSTANCE_ELEMENT_ID: &str = "wolfrs-instance";
+
+impl Instance {
+    // Settings from the environment take priority over the file, so the same file can be used for staging and production
+    fn apply_env_overrides(&mut self) -> Result<(), InstanceError> {
+        let text_overrides: [(&'static str, &mut String); 4] = [
+            ("WOLFRS_LEMMY_URL", &mut self.url),
+            ("WOLFRS_API_VERSION", &mut self.api_version),
+            ("WOLFRS_INSTANCE_NAME", &mut self.name),
+            ("WOLFRS_IMAGE_PROXY_CACHE_DIR", &mut self.image_proxy_cache_dir),
+        ];
+        for (variable, setting) in text_overrides {
+            if let Ok(value) = std::env::var(variable) {
+                *setting = value;
+            }
+        }
+
+        let flag_overrides: [(&'static str, &mut bool); 2] = [
+            ("WOLFRS_ENABLE_NSFW", &mut self.enable_nsfw),
+            ("WOLFRS_IMAGE_PROXY", &mut self.image_proxy),
+        ];
+        for (variable, setting) in flag_overrides {
+            if let Ok(value) = std::env::var(variable) {
+                *setting = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| InstanceError::Override { variable, value })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // The URL pages are linked to from other sites and search engines, such as https://pawb.social, without a trailing /
+    pub fn site_url(&self) -> &str {
+        self.public_url.as_deref().unwrap_or(&self.url)
+    }
+
+    // Catches the mistakes that would otherwise only show up as a broken page or failed API calls
+    fn validate(mut self) -> Result<Self, InstanceError> {
+        self.url = self.url.trim().trim_end_matches('/').to_string();
+        if !(self.url.starts_with("https://") || self.url.starts_with("http://")) {
+            return Err(InstanceError::Invalid(format!(
+                "url must start with https:// or http://, not {:?}",
+                self.url
+            )));
+        }
+        if let Some(public_url) = &mut self.public_url {
+            *public_url = public_url.trim().trim_end_matches('/').to_string();
+            if !(public_url.starts_with("https://") || public_url.starts_with("http://")) {
+                return Err(InstanceError::Invalid(format!(
+                    "public_url must start with https:// or http://, not {:?}",
+                    public_url
+                )));
+            }
+        }
+        if self.name.trim().is_empty() {
+            return Err(InstanceError::Invalid("name can't be empty".to_string()));
+        }
+        if self.api_version.trim().is_empty() {
+            return Err(InstanceError::Invalid("api_version can't be empty".to_string()));
+        }
+        if self.logo_width <= 0 || self.logo_height <= 0 {
+            return Err(InstanceError::Invalid(
+                "logo_width and logo_height must be greater than 0".to_string(),
+            ));
+        }
+        if self.image_proxy && self.image_proxy_cache_dir.trim().is_empty() {
+            return Err(InstanceError::Invalid(
+                "image_proxy_cache_dir can't be empty when image_proxy is turned on".to_string(),
+            ));
+        }
+        // The palette ends up in a <style> element, so anything that isn't a plain colour or font stack is refused
+        for (theme, colours) in [("light", &self.palette.light), ("dark", &self.palette.dark)] {
+            for (name, colour) in colours.iter() {
+                if hex_colour_rgb(colour).is_none() {
+                    return Err(InstanceError::Invalid(format!(
+                        "palette.{}.{} must be a #rrggbb colour, not {:?}",
+                        theme, name, colour
+                    )));
+                }
+            }
+        }
+        if let Some(font_family) = &self.palette.font_family {
+            if font_family.trim().is_empty() || font_family.contains([';', '{', '}', '<', '>']) {
+                return Err(InstanceError::Invalid(format!(
+                    "palette.font_family must be a CSS font stack, not {:?}",
+                    font_family
+                )));
+            }
+        }
+
+        Ok(self)
+    }
+}
+
+cfg_if! { if #[cfg(all(target_arch = "wasm32", not(feature = "ssr")))] {
+    use std::cell::OnceCell;
+
+    thread_local! {
+        static INSTANCE: OnceCell<Instance> = OnceCell::new();
+    }
+
+    // Reads the configuration the server rendered into the page, which only has to be done once
+    fn read_from_page() -> Result<Instance, InstanceError> {
+        let json = leptos::document()
+            .get_element_by_id(INSTANCE_ELEMENT_ID)
+            .and_then(|element| element.text_content())
+            .ok_or(InstanceError::Missing)?;
+
+        serde_json::from_str(&json).map_err(|_| InstanceError::Missing)
+    }
+
+    pub fn get_instance_details() -> Result<Instance, Box<dyn std::error::Error>> {
+        INSTANCE.with(|instance| {
+            if let Some(instance) = instance.get() {
+                return Ok(instance.clone());
+            }
+            let loaded = read_from_page()?;
+            Ok(instance.get_or_init(|| loaded).clone())
+        })
+    }
+} else {
+    use std::sync::{Arc, OnceLock, RwLock};
+
+    static INSTANCE: OnceLock<RwLock<Arc<Instance>>> = OnceLock::new();
+
+    // The path of the configuration file, which can be moved with WOLFRS_CONFIG
+    pub fn instance_config_path() -> String {
+        std::env::var("WOLFRS_CONFIG").unwrap_or_else(|_| "Instance.toml".to_string())
+    }
+
+    // Reads, overrides, and validates the configuration without replacing the one in use
+    pub fn read_instance_details() -> Result<Instance, InstanceError> {
+        let path = instance_config_path();
+        let toml_str = std::fs::read_to_string(&path).map_err(|source| InstanceError::Read {
+            path: path.clone(),
+            source,
+        })?;
+        let mut instance = toml::from_str::<InstanceToml>(&toml_str)
+            .map_err(|source| InstanceError::Parse { path, source })?
+            .instance;
+
+        instance.apply_env_overrides()?;
+        instance.validate()
+    }
+
+    // Loads the configuration and puts it in use, which is done when the server starts and again on SIGHUP.
+    // If the new configuration is invalid the old one is kept.
+    pub fn load_instance_details() -> Result<(), InstanceError> {
+        let instance = Arc::new(read_instance_details()?);
+
+        match INSTANCE.get() {
+            Some(current) => {
+                if let Ok(mut current) = current.write() {
+                    *current = instance;
+                }
+            }
+            None => {
+                let _ = INSTANCE.set(RwLock::new(instance));
+            }
+        }
+
+        Ok(())
+    }
+
+    // The configuration is loaded on first use if the server didn't load it at startup, such as in tests
+    pub fn get_instance_details() -> Result<Instance, Box<dyn std::error::Error>> {
+        if INSTANCE.get().is_none() {
+            load_instance_details()?;
+        }
+
+        let instance = INSTANCE
+            .get()
+            .ok_or(InstanceError::Missing)?
+            .read()
+            .map_err(|_| InstanceError::Missing)?;
+
+        Ok(Instance::clone(&instance))
+    }
+}}
+
+// The part of the configuration the browser reads. Settings only the server uses, such as where the image cache is kept,
+// are left out of the page and take their defaults when the browser reads it back as an Instance.
+#[derive(Serialize)]
+struct PageInstance<'a> {
+    name: &'a str,
+    slogan: &'a str,
+    url: &'a str,
+    public_url: Option<&'a str>,
+    api_version: &'a str,
+    donation_url: &'a str,
+    source_code_url: &'a str,
+    documentation_url: &'a str,
+    logo_name: &'a str,
+    no_logo_text: bool,
+    logo_text_name: &'a str,
+    logo_width: i32,
+    logo_height: i32,
+    favicon_name: &'a str,
+    enable_nsfw: bool,
+    image_proxy: bool,
+    palette: &'a InstancePalette,
+}
+
+impl<'a> From<&'a Instance> for PageInstance<'a> {
+    fn from(instance: &'a Instance) -> Self {
+        PageInstance {
+            name: &instance.name,
+            slogan: &instance.slogan,
+            url: &instance.url,
+            public_url: instance.public_url.as_deref(),
+            api_version: &instance.api_version,
+            donation_url: &instance.donation_url,
+            source_code_url: &instance.source_code_url,
+            documentation_url: &instance.documentation_url,
+            logo_name: &instance.logo_name,
+            no_logo_text: instance.no_logo_text,
+            logo_text_name: &instance.logo_text_name,
+            logo_width: instance.logo_width,
+            logo_height: instance.logo_height,
+            favicon_name: &instance.favicon_name,
+            enable_nsfw: instance.enable_nsfw,
+            image_proxy: instance.image_proxy,
+            palette: &instance.palette,
+        }
+    }
 }
 
-pub fn get_instance_details() -> Result<Instance, Box<dyn std::error::Error>> {
-    let toml_str = include_str!("../../Instance.toml");
-    let instance_toml: InstanceToml = toml::from_str(toml_str)?;
-    Ok(instance_toml.instance)
+// The configuration as JSON for the page, with < escaped so nothing in it can close the <script> element early
+pub fn instance_details_json() -> String {
+    get_instance_details()
+        .ok()
+        .and_then(|instance| serde_json::to_string(&PageInstance::from(&instance)).ok())
+        .unwrap_or_default()
+        .replace('<', "\\u003c")
 }
@@ -0,0 +1,262 @@
+use markdown::*;
+
+use crate::api::structs::CustomEmojiView;
+use crate::components::custom_emoji::replace_custom_emojis;
+use crate::components::instance::*;
+use crate::components::sanitize::sanitize_html;
+
+// TODO - markdown.rs:
+// Render the spoiler title as inline Markdown instead of plain text
+// Support for Lemmy's video embeds
+
+// This is the renderer for Lemmy's flavour of Markdown, which is GFM with a few additions from the markdown-it plugins Lemmy-UI uses:
+//  - `::: spoiler title` blocks, which become a <details> element
+//  - `^superscript^` and `~subscript~`
+//  - `!community@host` and `@user@host` mentions, which link to the local routes for them
+//  - `:shortcode:` custom emojis
+// Raw HTML in the Markdown is always escaped rather than rendered, and the output is sanitized before it is returned.
+
+// Escapes text before it is placed in HTML, either as text or inside of an attribute
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+// Runs replace on every piece of text in the HTML, leaving the tags themselves alone along with anything inside of the skipped tags
+pub fn replace_in_text(html: &str, skip_tags: &[&str], replace: impl Fn(&str) -> String) -> String {
+    let tag_regex = regex::Regex::new(r"<[^>]*>").unwrap();
+
+    let mut output = String::with_capacity(html.len());
+    let mut last_end = 0;
+    // Counts how many of the skipped tags the current text is inside of
+    let mut skip_depth: i32 = 0;
+
+    for tag in tag_regex.find_iter(html) {
+        let text = &html[last_end..tag.start()];
+        if skip_depth == 0 {
+            output.push_str(&replace(text));
+        } else {
+            output.push_str(text);
+        }
+
+        let tag_name = tag
+            .as_str()
+            .trim_start_matches('<')
+            .split(|c: char| c.is_whitespace() || c == '>')
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        if let Some(closing_name) = tag_name.strip_prefix('/') {
+            if skip_tags.contains(&closing_name) {
+                skip_depth = (skip_depth - 1).max(0);
+            }
+        } else if skip_tags.contains(&tag_name.as_str()) {
+            skip_depth += 1;
+        }
+
+        output.push_str(tag.as_str());
+        last_end = tag.end();
+    }
+
+    let text = &html[last_end..];
+    if skip_depth == 0 {
+        output.push_str(&replace(text));
+    } else {
+        output.push_str(text);
+    }
+
+    output
+}
+
+// GFM along with the changes needed for Lemmy's flavour
+fn lemmy_options() -> Options {
+    let mut options = Options::gfm();
+    // Lemmy uses ~text~ for subscript, so only ~~text~~ is a strikethrough
+    options.parse.gfm_strikethrough_single_tilde = false;
+    options
+}
+
+// Returns the title of a spoiler if the line opens one, which is "Spoiler" when no title is given
+fn spoiler_title(line: &str) -> Option<String> {
+    let spoiler_regex = regex::Regex::new(r"^:::\s*spoiler(?:\s+(.*))?$").unwrap();
+    spoiler_regex.captures(line.trim()).map(|captures| {
+        captures
+            .get(1)
+            .map(|title| title.as_str().trim().to_string())
+            .filter(|title| !title.is_empty())
+            .unwrap_or("Spoiler".to_string())
+    })
+}
+
+// Returns the fence that opens or closes a code block, so spoiler markers inside of code are left alone
+fn code_fence(line: &str) -> Option<&str> {
+    let line = line.trim_start();
+    if line.starts_with("```") {
+        Some("```")
+    } else if line.starts_with("~~~") {
+        Some("~~~")
+    } else {
+        None
+    }
+}
+
+// Renders the Markdown in chunks, with each spoiler block rendered on its own and wrapped in a <details> element.
+// This is done outside of the Markdown parser since it doesn't support custom containers.
+fn render_blocks(text: &str, options: &Options) -> String {
+    let lines = text.split('\n').collect::<Vec<_>>();
+
+    let mut html = String::new();
+    let mut chunk: Vec<&str> = Vec::new();
+    let mut fence: Option<&str> = None;
+    let mut index = 0;
+
+    let flush = |chunk: &mut Vec<&str>, html: &mut String| {
+        if !chunk.is_empty() {
+            html.push_str(&to_html_with_options(&chunk.join("\n"), options).unwrap_or_default());
+            chunk.clear();
+        }
+    };
+
+    while index < lines.len() {
+        let line = lines[index];
+
+        if let Some(open_fence) = fence {
+            if code_fence(line) == Some(open_fence) {
+                fence = None;
+            }
+        } else if let Some(new_fence) = code_fence(line) {
+            fence = Some(new_fence);
+        } else if let Some(title) = spoiler_title(line) {
+            if let Some(close) = closing_marker(&lines, index + 1) {
+                flush(&mut chunk, &mut html);
+                html.push_str(&format!(
+                    "<details><summary>{}</summary>\n{}</details>\n",
+                    escape_html(&title),
+                    render_blocks(&lines[index + 1..close].join("\n"), options)
+                ));
+                index = close + 1;
+                continue;
+            }
+        }
+
+        chunk.push(line);
+        index += 1;
+    }
+    flush(&mut chunk, &mut html);
+
+    html
+}
+
+// Finds the line of the ::: that closes a spoiler, taking nested spoilers and code blocks into account
+fn closing_marker(lines: &[&str], start: usize) -> Option<usize> {
+    let mut depth = 1;
+    let mut fence: Option<&str> = None;
+
+    for (index, line) in lines.iter().enumerate().skip(start) {
+        if let Some(open_fence) = fence {
+            if code_fence(line) == Some(open_fence) {
+                fence = None;
+            }
+        } else if let Some(new_fence) = code_fence(line) {
+            fence = Some(new_fence);
+        } else if spoiler_title(line).is_some() {
+            depth += 1;
+        } else if line.trim() == ":::" {
+            depth -= 1;
+            if depth == 0 {
+                return Some(index);
+            }
+        }
+    }
+
+    None
+}
+
+// Mentions of communities on this instance link to /c/name, and those on other instances to /c/name@host
+fn mention_link(sigil: &str, name: &str, host: &str) -> String {
+    let instance_host = get_instance_details()
+        .map(|instance| {
+            instance
+                .url
+                .trim_start_matches("https://")
+                .trim_start_matches("http://")
+                .trim_end_matches('/')
+                .to_string()
+        })
+        .unwrap_or_default();
+
+    let route = if sigil == "!" { "c" } else { "u" };
+    let path = if host == instance_host {
+        name.to_string()
+    } else {
+        format!("{}@{}", name, host)
+    };
+
+    format!(
+        "<a href=\"/{}/{}\" class=\"mention\">{}{}@{}</a>",
+        route, path, sigil, name, host
+    )
+}
+
+// Turns !community@host and @user@host into links to the local routes for them
+fn link_mentions(html: &str) -> String {
+    // GFM autolinks the name@host part as an email address, so those links are swapped out first
+    let autolink_regex =
+        regex::Regex::new(r#"([!@])<a href="mailto:([A-Za-z0-9_]+)@([A-Za-z0-9.\-]+)">[^<]*</a>"#)
+            .unwrap();
+    let html = autolink_regex.replace_all(html, |captures: &regex::Captures| {
+        mention_link(&captures[1], &captures[2], &captures[3])
+    });
+
+    let mention_regex = regex::Regex::new(
+        r"(^|[^\w/@!.])([!@])([A-Za-z0-9_]+)@([A-Za-z0-9\-]+(?:\.[A-Za-z0-9\-]+)+)",
+    )
+    .unwrap();
+    replace_in_text(&html, &["a", "code", "pre"], |text| {
+        mention_regex
+            .replace_all(text, |captures: &regex::Captures| {
+                format!(
+                    "{}{}",
+                    &captures[1],
+                    mention_link(&captures[2], &captures[3], &captures[4])
+                )
+            })
+            .to_string()
+    })
+}
+
+// Turns ^text^ into superscript and ~text~ into subscript, which like in Lemmy can't contain spaces
+fn superscript_and_subscript(html: &str) -> String {
+    let superscript_regex = regex::Regex::new(r"\^([^\^\s]+)\^").unwrap();
+    let subscript_regex = regex::Regex::new(r"~([^~\s]+)~").unwrap();
+
+    replace_in_text(html, &["code", "pre"], |text| {
+        let text = superscript_regex.replace_all(text, "<sup>$1</sup>");
+        subscript_regex
+            .replace_all(&text, "<sub>$1</sub>")
+            .to_string()
+    })
+}
+
+// Images in posts can be any size, so they are scaled down to fit and only loaded once they are scrolled to
+fn responsive_images(html: &str) -> String {
+    html.replace(
+        "<img src=",
+        "<img class=\"img-fluid\" loading=\"lazy\" src=",
+    )
+}
+
+// Renders Markdown from Lemmy into sanitized HTML, with the site's custom emojis swapped in for their :shortcode:.
+// This is the only way user supplied content should reach inner_html.
+pub fn render_markdown(text: &str, custom_emojis: &[CustomEmojiView]) -> String {
+    let html = render_blocks(text, &lemmy_options());
+    let html = link_mentions(&html);
+    let html = superscript_and_subscript(&html);
+    let html = responsive_images(&html);
+
+    let html = replace_custom_emojis(&html, custom_emojis);
+
+    sanitize_html(&html)
+}
@@ -0,0 +1,405 @@
+use leptos::html::Textarea;
+use leptos::*;
+
+use crate::components::custom_emoji::{use_custom_emojis, EmojiPicker};
+use crate::components::i18n::{t, translate};
+use crate::components::image_upload::ImageUpload;
+use crate::components::markdown::render_markdown;
+use crate::components::shortcuts::shortcuts_enabled;
+
+// TODO - markdown_editor.rs:
+// Tables and image alt text in the toolbar
+// Undo history that includes the toolbar actions
+
+// These are the length limits Lemmy enforces for the different kinds of Markdown text
+pub const POST_BODY_MAX_LENGTH: usize = 50000;
+pub const BODY_MAX_LENGTH: usize = 10000;
+pub const BIO_MAX_LENGTH: usize = 300;
+
+// The formatting that can be applied to the selected text with the toolbar or keyboard shortcuts
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MarkdownFormat {
+    Bold,
+    Italic,
+    Strikethrough,
+    Superscript,
+    Subscript,
+    Link,
+    Quote,
+    Code,
+    Spoiler,
+    List,
+    Header,
+}
+
+impl MarkdownFormat {
+    // The Bootstrap icon of the toolbar button for each format
+    fn icon(&self) -> &'static str {
+        match self {
+            MarkdownFormat::Bold => "bi-type-bold",
+            MarkdownFormat::Italic => "bi-type-italic",
+            MarkdownFormat::Strikethrough => "bi-type-strikethrough",
+            MarkdownFormat::Superscript => "bi-superscript",
+            MarkdownFormat::Subscript => "bi-subscript",
+            MarkdownFormat::Link => "bi-link-45deg",
+            MarkdownFormat::Quote => "bi-quote",
+            MarkdownFormat::Code => "bi-code",
+            MarkdownFormat::Spoiler => "bi-eye-slash",
+            MarkdownFormat::List => "bi-list-ul",
+            MarkdownFormat::Header => "bi-type-h1",
+        }
+    }
+
+    // The title of the toolbar button for each format
+    fn title(&self, cx: Scope) -> String {
+        match self {
+            MarkdownFormat::Bold => translate(cx, "editor-bold"),
+            MarkdownFormat::Italic => translate(cx, "editor-italic"),
+            MarkdownFormat::Strikethrough => translate(cx, "editor-strikethrough"),
+            MarkdownFormat::Superscript => translate(cx, "editor-superscript"),
+            MarkdownFormat::Subscript => translate(cx, "editor-subscript"),
+            MarkdownFormat::Link => translate(cx, "editor-link"),
+            MarkdownFormat::Quote => translate(cx, "editor-quote"),
+            MarkdownFormat::Code => translate(cx, "editor-code"),
+            MarkdownFormat::Spoiler => translate(cx, "editor-spoiler"),
+            MarkdownFormat::List => translate(cx, "editor-list"),
+            MarkdownFormat::Header => translate(cx, "editor-header"),
+        }
+    }
+
+    // The text that is inserted and selected when the format is applied without anything selected
+    fn placeholder(&self, cx: Scope) -> String {
+        match self {
+            MarkdownFormat::Bold => translate(cx, "editor-bold-placeholder"),
+            MarkdownFormat::Italic => translate(cx, "editor-italic-placeholder"),
+            MarkdownFormat::Strikethrough => translate(cx, "editor-strikethrough-placeholder"),
+            MarkdownFormat::Superscript => translate(cx, "editor-superscript-placeholder"),
+            MarkdownFormat::Subscript => translate(cx, "editor-subscript-placeholder"),
+            MarkdownFormat::Link => translate(cx, "editor-link-placeholder"),
+            MarkdownFormat::Code => translate(cx, "editor-code-placeholder"),
+            MarkdownFormat::Spoiler => translate(cx, "editor-spoiler-placeholder"),
+            MarkdownFormat::Quote | MarkdownFormat::List | MarkdownFormat::Header => String::new(),
+        }
+    }
+
+    // Applies the format to the selected text, returning the new text along with the byte range that should be selected afterwards.
+    // The placeholder is used instead when nothing is selected, and the title names spoilers.
+    fn apply(
+        &self,
+        text: &str,
+        start: usize,
+        end: usize,
+        placeholder: &str,
+        title: &str,
+    ) -> (String, usize, usize) {
+        let selected = &text[start..end];
+
+        let spoiler_start = format!("\n::: spoiler {}\n", title);
+        let (before, after, placeholder) = match self {
+            MarkdownFormat::Bold => ("**", "**", placeholder),
+            MarkdownFormat::Italic => ("*", "*", placeholder),
+            MarkdownFormat::Strikethrough => ("~~", "~~", placeholder),
+            MarkdownFormat::Superscript => ("^", "^", placeholder),
+            MarkdownFormat::Subscript => ("~", "~", placeholder),
+            MarkdownFormat::Code if selected.contains('\n') => ("```\n", "\n```", ""),
+            MarkdownFormat::Code => ("`", "`", placeholder),
+            MarkdownFormat::Spoiler => (spoiler_start.as_str(), "\n:::\n", placeholder),
+            MarkdownFormat::Link => {
+                // The URL is selected afterwards, so it can be typed over straight away
+                let label = if selected.is_empty() {
+                    placeholder
+                } else {
+                    selected
+                };
+                let inserted = format!("[{}](https://)", label);
+                let url_start = start + label.len() + 3;
+                let new_text = format!("{}{}{}", &text[..start], inserted, &text[end..]);
+                return (new_text, url_start, url_start + "https://".len());
+            }
+            MarkdownFormat::Quote | MarkdownFormat::List | MarkdownFormat::Header => {
+                let prefix = match self {
+                    MarkdownFormat::Quote => "> ",
+                    MarkdownFormat::List => "- ",
+                    _ => "# ",
+                };
+                // Line formats apply to every line the selection touches, so the selection is widened to whole lines
+                let line_start = text[..start]
+                    .rfind('\n')
+                    .map(|index| index + 1)
+                    .unwrap_or(0);
+                let line_end = text[end..]
+                    .find('\n')
+                    .map(|index| end + index)
+                    .unwrap_or(text.len());
+                let lines = text[line_start..line_end]
+                    .split('\n')
+                    .map(|line| format!("{}{}", prefix, line))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                let new_text = format!("{}{}{}", &text[..line_start], lines, &text[line_end..]);
+                return (new_text, line_start, line_start + lines.len());
+            }
+        };
+
+        let selected = if selected.is_empty() {
+            placeholder
+        } else {
+            selected
+        };
+        let new_text = format!(
+            "{}{}{}{}{}",
+            &text[..start],
+            before,
+            selected,
+            after,
+            &text[end..]
+        );
+        let selection_start = start + before.len();
+        (new_text, selection_start, selection_start + selected.len())
+    }
+}
+
+const TOOLBAR: [MarkdownFormat; 11] = [
+    MarkdownFormat::Bold,
+    MarkdownFormat::Italic,
+    MarkdownFormat::Strikethrough,
+    MarkdownFormat::Header,
+    MarkdownFormat::Link,
+    MarkdownFormat::Quote,
+    MarkdownFormat::Code,
+    MarkdownFormat::List,
+    MarkdownFormat::Spoiler,
+    MarkdownFormat::Superscript,
+    MarkdownFormat::Subscript,
+];
+
+// The textarea reports its selection in UTF-16 code units, while Rust strings are indexed by bytes
+fn utf16_to_byte_index(text: &str, utf16_index: u32) -> usize {
+    let mut utf16_count = 0;
+    for (byte_index, character) in text.char_indices() {
+        if utf16_count >= utf16_index as usize {
+            return byte_index;
+        }
+        utf16_count += character.len_utf16();
+    }
+    text.len()
+}
+
+fn byte_to_utf16_index(text: &str, byte_index: usize) -> u32 {
+    text[..byte_index].encode_utf16().count() as u32
+}
+
+// Drafts are kept in local storage under a key for each composer, so they survive a reload or an accidental navigation
+#[cfg(not(feature = "ssr"))]
+fn draft_storage_key(draft_key: &str) -> String {
+    format!("wolfrs-draft-{}", draft_key)
+}
+
+#[cfg(not(feature = "ssr"))]
+fn load_draft(draft_key: &str) -> Option<String> {
+    window()
+        .local_storage()
+        .ok()??
+        .get_item(&draft_storage_key(draft_key))
+        .ok()?
+}
+
+#[cfg(feature = "ssr")]
+fn load_draft(_draft_key: &str) -> Option<String> {
+    None
+}
+
+#[cfg(not(feature = "ssr"))]
+fn save_draft(draft_key: &str, text: &str) {
+    if let Ok(Some(storage)) = window().local_storage() {
+        let key = draft_storage_key(draft_key);
+        let _ = if text.trim().is_empty() {
+            storage.remove_item(&key)
+        } else {
+            storage.set_item(&key, text)
+        };
+    }
+}
+
+#[cfg(feature = "ssr")]
+fn save_draft(_draft_key: &str, _text: &str) {}
+
+// Removes the saved draft for a composer, which should be called once whatever it was for has been submitted
+pub fn clear_draft(draft_key: &str) {
+    save_draft(draft_key, "");
+}
+
+// How the editor is laid out, either as tabs that switch between writing and previewing or with both side by side
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum EditorView {
+    Write,
+    Preview,
+    SideBySide,
+}
+
+// The Markdown editor shared by every composer, with a formatting toolbar, a live preview, and draft autosaving.
+// The draft_key should be unique to what is being written, such as "comment-reply-1234", so drafts don't overwrite each other.
+#[component]
+pub fn MarkdownEditor(
+    cx: Scope,
+    text: RwSignal<String>,
+    draft_key: String,
+    #[prop(default = BODY_MAX_LENGTH)] max_length: usize,
+    #[prop(default = 8)] rows: u32,
+    #[prop(optional, into)] placeholder: String,
+) -> impl IntoView {
+    let (editor_view, set_editor_view) = create_signal(cx, EditorView::Write);
+    let textarea_ref = create_node_ref::<Textarea>(cx);
+
+    // Restores the saved draft once the editor is in the browser, then keeps saving the text as it changes
+    let storage_key = draft_key.clone();
+    create_effect(cx, move |restored: Option<()>| {
+        if restored.is_none() && text.get_untracked().is_empty() {
+            if let Some(draft) = load_draft(&storage_key) {
+                text.set(draft);
+            }
+        }
+        save_draft(&storage_key, &text.get());
+    });
+
+    let apply_format = move |format: MarkdownFormat| {
+        let Some(textarea) = textarea_ref.get() else {
+            return;
+        };
+        let current = text.get_untracked();
+        let start = utf16_to_byte_index(
+            &current,
+            textarea.selection_start().ok().flatten().unwrap_or(0),
+        );
+        let end = utf16_to_byte_index(
+            &current,
+            textarea.selection_end().ok().flatten().unwrap_or(0),
+        )
+        .max(start);
+
+        let (new_text, selection_start, selection_end) =
+            format.apply(&current, start, end, &format.placeholder(cx), &format.title(cx));
+        text.set(new_text.clone());
+
+        // Puts the cursor back into the textarea with the formatted text selected
+        textarea.set_value(&new_text);
+        let _ = textarea.focus();
+        let _ = textarea.set_selection_range(
+            byte_to_utf16_index(&new_text, selection_start),
+            byte_to_utf16_index(&new_text, selection_end),
+        );
+    };
+
+    let on_keydown = move |ev: ev::KeyboardEvent| {
+        if !(ev.ctrl_key() || ev.meta_key()) {
+            return;
+        }
+        // Ctrl+Enter sends what has been written, the same as the composer's submit button
+        if ev.key() == "Enter" {
+            let form = textarea_ref.get().and_then(|textarea| textarea.form());
+            if let Some(form) = form.filter(|_| shortcuts_enabled(cx)) {
+                ev.prevent_default();
+                let _ = form.request_submit();
+            }
+            return;
+        }
+        let format = match ev.key().to_lowercase().as_str() {
+            "b" => MarkdownFormat::Bold,
+            "i" => MarkdownFormat::Italic,
+            "k" => MarkdownFormat::Link,
+            "e" => MarkdownFormat::Code,
+            _ => return,
+        };
+        ev.prevent_default();
+        apply_format(format);
+    };
+
+    let character_count = move || text.with(|text| text.chars().count());
+
+    let tab = move |view: EditorView, label: String| {
+        view! { cx,
+            <li class="nav-item">
+                <button
+                    type="button"
+                    class="nav-link"
+                    class:active=move || editor_view.get() == view
+                    on:click=move |_| set_editor_view.set(view)
+                >
+                    {label}
+                </button>
+            </li>
+        }
+    };
+
+    let preview = move || {
+        let html = render_markdown(&text.get(), &use_custom_emojis(cx));
+        view! { cx, <div class="markdown border rounded p-2 h-100" inner_html=html></div> }
+    };
+
+    view! { cx,
+        <div class="markdown-editor">
+            <ul class="nav nav-tabs mb-2">
+                {move || tab(EditorView::Write, translate(cx, "editor-write"))}
+                {move || tab(EditorView::Preview, translate(cx, "editor-preview"))}
+                {move || tab(EditorView::SideBySide, translate(cx, "editor-side-by-side"))}
+            </ul>
+            <div class="btn-toolbar gap-1 mb-2" role="toolbar" aria-label=t(cx, "editor-formatting")>
+                <div class="btn-group btn-group-sm flex-wrap" role="group">
+                    {TOOLBAR
+                        .into_iter()
+                        .map(|format| {
+                            let title = move || format.title(cx);
+                            view! { cx,
+                                <button
+                                    type="button"
+                                    class="btn btn-outline-secondary"
+                                    title=title
+                                    aria-label=title
+                                    disabled=move || editor_view.get() == EditorView::Preview
+                                    on:click=move |_| apply_format(format)
+                                >
+                                    <i aria-hidden="true" class=format!("bi {}", format.icon())></i>
+                                </button>
+                            }
+                        })
+                        .collect_view(cx)}
+                </div>
+                <EmojiPicker text=text/>
+            </div>
+            <div class="row g-2">
+                <div
+                    class="col"
+                    class:d-none=move || editor_view.get() == EditorView::Preview
+                >
+                    <ImageUpload text=text markdown=true>
+                        <textarea
+                            class="form-control font-monospace"
+                            rows=rows
+                            placeholder=placeholder.clone()
+                            node_ref=textarea_ref
+                            prop:value=move || text.get()
+                            on:input=move |ev| text.set(event_target_value(&ev))
+                            on:keydown=on_keydown
+                        ></textarea>
+                    </ImageUpload>
+                </div>
+                {move || {
+                    if editor_view.get() == EditorView::Write {
+                        ().into_view(cx)
+                    } else {
+                        view! { cx, <div class="col">{preview}</div> }.into_view(cx)
+                    }
+                }}
+            </div>
+            <div class=move || {
+                if character_count() > max_length {
+                    "form-text text-end text-danger"
+                } else {
+                    "form-text text-end"
+                }
+            }>
+                {move || format!("{} / {}", character_count(), max_length)}
+            </div>
+        </div>
+    }
+}
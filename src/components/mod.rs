@@ -1,11 +1,29 @@
+pub mod a11y;
+pub mod admin;
 pub mod bindings;
 pub mod comments;
+pub mod community_header;
+pub mod custom_emoji;
 pub mod feed;
+pub mod feed_layout;
 pub mod footer;
 pub mod header;
+pub mod i18n;
+pub mod image_upload;
 pub mod instance;
+pub mod markdown;
+pub mod markdown_editor;
 pub mod notifications;
+pub mod nsfw;
 pub mod pagination;
+pub mod post_media;
 pub mod post_view;
+pub mod sanitize;
+pub mod seo;
+pub mod shortcuts;
 pub mod sidecards;
+pub mod site;
+pub mod subscribe_button;
+pub mod theme;
+pub mod time_ago;
 pub mod trending;
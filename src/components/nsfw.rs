@@ -0,0 +1,112 @@
+use leptos::*;
+
+use crate::components::i18n::t;
+use crate::components::instance::get_instance_details;
+use crate::components::site::use_my_user;
+
+// TODO - nsfw.rs:
+// Let users choose to show NSFW media without the blur once user settings exist
+// Remember revealed posts for the rest of the session
+
+// How NSFW media is shown to the current viewer
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NsfwVisibility {
+    // The media isn't rendered at all, only a placeholder saying that it is NSFW
+    Hidden,
+    // The media is blurred until it is clicked
+    Blurred,
+}
+
+// Instances that forbid NSFW content can turn it off in Instance.toml, which hides NSFW posts and communities entirely
+pub fn nsfw_enabled() -> bool {
+    get_instance_details()
+        .map(|instance| instance.enable_nsfw)
+        .unwrap_or_default()
+}
+
+// Whether a post or community should be left out completely, because the instance doesn't allow NSFW content
+pub fn is_nsfw_forbidden(nsfw: bool) -> bool {
+    nsfw && !nsfw_enabled()
+}
+
+// NSFW media stays hidden until the site has loaded, so it never flashes up for someone who has show_nsfw turned off.
+// Anonymous users don't have the setting, so it is hidden for them too.
+pub fn use_nsfw_visibility(cx: Scope) -> NsfwVisibility {
+    let show_nsfw = use_my_user(cx)
+        .map(|my_user| my_user.local_user_view.local_user.show_nsfw)
+        .unwrap_or_default();
+
+    if show_nsfw && nsfw_enabled() {
+        NsfwVisibility::Blurred
+    } else {
+        NsfwVisibility::Hidden
+    }
+}
+
+// The badge shown next to the title of NSFW posts and communities
+#[component]
+pub fn NsfwBadge(cx: Scope) -> impl IntoView {
+    view! { cx, <span class="badge text-bg-danger align-middle ms-2">{t(cx, "nsfw-badge")}</span> }
+}
+
+// Wraps media that may be NSFW, blurring it with an overlay that reveals it when clicked, or hiding it for users who don't want to see NSFW content.
+// Anything that isn't NSFW is rendered as it is.
+// The children are rendered again each time the blur changes, so anything costly in them, such as proxying image URLs, should be worked out before they are passed in.
+#[component]
+pub fn NsfwBlur(
+    cx: Scope,
+    nsfw: bool,
+    children: ChildrenFn,
+    // Use a smaller overlay for things like community icons in a table
+    #[prop(optional)] compact: bool,
+) -> impl IntoView {
+    let revealed = create_rw_signal(cx, false);
+
+    move || {
+        if !nsfw || revealed.get() {
+            return children(cx).into_view(cx);
+        }
+
+        match use_nsfw_visibility(cx) {
+            NsfwVisibility::Hidden if compact => view! { cx,
+                <span class="nsfw-hidden d-inline-flex align-items-center justify-content-center rounded bg-body-secondary small" title=t(cx, "nsfw-badge")>
+                    <i aria-hidden="true" class="bi bi-eye-slash"></i>
+                </span>
+            }
+            .into_view(cx),
+            NsfwVisibility::Hidden => view! { cx,
+                <div class="nsfw-hidden d-flex flex-column align-items-center justify-content-center rounded bg-body-secondary text-body-secondary p-3">
+                    <i aria-hidden="true" class="bi bi-eye-slash fs-2"></i>
+                    <span class="small">{t(cx, "nsfw-hidden")}</span>
+                </div>
+            }
+            .into_view(cx),
+            NsfwVisibility::Blurred => view! { cx,
+                <div class=if compact {
+                    "position-relative d-inline-block overflow-hidden rounded align-middle"
+                } else {
+                    "position-relative overflow-hidden rounded"
+                }>
+                    <div class="nsfw-blur" aria-hidden="true">
+                        {children(cx)}
+                    </div>
+                    <button
+                        type="button"
+                        class="btn position-absolute top-0 start-0 w-100 h-100 d-flex flex-column align-items-center justify-content-center text-light border-0"
+                        title=t(cx, "nsfw-reveal")
+                        on:click=move |ev| {
+                            // The media may be inside of a link, which shouldn't be followed when revealing it
+                            ev.prevent_default();
+                            ev.stop_propagation();
+                            revealed.set(true);
+                        }
+                    >
+                        <i aria-hidden="true" class="bi bi-eye" class:fs-2=!compact></i>
+                        {(!compact).then(|| view! { cx, <span class="small">{t(cx, "nsfw-click-to-reveal")}</span> })}
+                    </button>
+                </div>
+            }
+            .into_view(cx),
+        }
+    }
+}
@@ -1,12 +1,89 @@
+use std::collections::HashMap;
+
 use leptos::*;
 use leptos_router::*;
+use serde::Serialize;
+
+use crate::components::i18n::{t, translate_with};
 
 // TODO: Improve handling of clicked buttons
 // Improve potential styling
 
+// The query string for a page. The page number is always included, but with a cursor it is only shown to the user and the cursor decides which posts are loaded.
+#[derive(Serialize)]
+struct PageQuery {
+    page: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cursor: Option<String>,
+}
+
+pub fn page_href(page: i32, cursor: Option<String>) -> String {
+    serde_html_form::to_string(PageQuery { page, cursor })
+        .map(|query| format!("?{}", query))
+        .unwrap_or_else(|_| format!("?page={}", page))
+}
+
+// The rest of the current query string without the page and cursor, such as the tab and sorting on a user's profile
+fn query_without_paging(query: &ParamsMap) -> Option<String> {
+    let kept = query
+        .0
+        .iter()
+        .filter(|(key, _)| !matches!(key.as_str(), "page" | "cursor"))
+        .collect::<Vec<_>>();
+
+    serde_html_form::to_string(kept)
+        .ok()
+        .filter(|kept| !kept.is_empty())
+}
+
+// The same as page_href, but keeps the rest of the current query string
+fn page_href_keeping(query: &ParamsMap, page: i32, cursor: Option<String>) -> String {
+    let href = page_href(page, cursor);
+    match query_without_paging(query) {
+        Some(kept) => format!("{}&{}", href, kept),
+        None => href,
+    }
+}
+
+// The href of the first page of a feed, which keeps the rest of the current query string
+pub fn first_page_href(query: &ParamsMap) -> String {
+    query_without_paging(query)
+        .map(|kept| format!("?{}", kept))
+        .unwrap_or_default()
+}
+
+// The cursors for a feed on Lemmy versions that support cursor based paging.
+// A cursor points at the post a page starts from, so pages don't shift as new posts come in on Hot and Active.
+#[derive(Clone, Copy)]
+pub struct PageCursors {
+    // The cursor each page that has been visited was loaded with, so Previous goes back to the same posts
+    pub pages: RwSignal<HashMap<i32, String>>,
+    // The cursor for the page after the current one, from the last response
+    pub next: Signal<Option<String>>,
+}
+
+impl PageCursors {
+    pub fn new(cx: Scope, next: Signal<Option<String>>) -> Self {
+        PageCursors {
+            pages: create_rw_signal(cx, HashMap::new()),
+            next,
+        }
+    }
+
+    // Pages that were never visited, such as the one before a shared link, fall back to the page number
+    fn href(&self, query: &ParamsMap, page: i32) -> String {
+        page_href_keeping(query, page, self.pages.with(|pages| pages.get(&page).cloned()))
+    }
+}
+
 // Pagination component for pages that require it
 #[component]
-pub fn Pagination(cx: Scope) -> impl IntoView {
+pub fn Pagination(
+    cx: Scope,
+    // Feeds that support cursors pass them in, everything else pages by number
+    #[prop(optional)]
+    cursors: Option<PageCursors>,
+) -> impl IntoView {
     let query = use_query_map(cx);
     let page = move || {
         query
@@ -14,15 +91,26 @@ pub fn Pagination(cx: Scope) -> impl IntoView {
             .unwrap_or(1)
     };
 
+    let previous_href = move || {
+        query.with(|query| match cursors {
+            Some(cursors) => cursors.href(query, page() - 1),
+            None => page_href_keeping(query, page() - 1, None),
+        })
+    };
+    let next_href = move || {
+        let next_cursor = cursors.and_then(|cursors| cursors.next.get());
+        query.with(|query| page_href_keeping(query, page() + 1, next_cursor))
+    };
+
     view! { cx,
-        <nav aria-label="Feed Page Navigation">
+        <nav aria-label=t(cx, "page-navigation")>
             <ul class="pagination justify-content-center">
                 {move || {
                     if page() > 1 {
                         view! { cx,
                             <li class="page-item">
-                                <A class="page-link" href=move || format!("?page={}", page() - 1)>
-                                    Previous
+                                <A class="page-link" href=previous_href>
+                                    {t(cx, "page-previous")}
                                 </A>
                             </li>
                         }
@@ -31,7 +119,7 @@ pub fn Pagination(cx: Scope) -> impl IntoView {
                         view! { cx,
                             <li class="page-item disabled">
                                 <A class="page-link" href="">
-                                    Previous
+                                    {t(cx, "page-previous")}
                                 </A>
                             </li>
                         }
@@ -39,12 +127,11 @@ pub fn Pagination(cx: Scope) -> impl IntoView {
                 }}
                 <li class="page-item">
                     <A class="page-link" href=move || format!("")>
-                        Page:
-                        {page}
+                        {move || translate_with(cx, "page-number", &[("page", page().into())])}
                     </A>
                 </li> <li class="page-item">
-                    <A class="page-link" href=move || format!("?page={}", page() + 1)>
-                        Next
+                    <A class="page-link" href=next_href>
+                        {t(cx, "page-next")}
                     </A>
                 </li>
             </ul>
@@ -0,0 +1,349 @@
+use leptos::*;
+
+use crate::api::structs::*;
+use crate::components::i18n::{t, translate};
+use crate::image_proxy::{proxied_image, proxied_image_url};
+
+// TODO - post_media.rs:
+// Turn imgur .gifv links into the .mp4 they point to
+// Audio posts, and a gallery for posts with more than one image
+// Keyboard navigation between images in the lightbox
+
+// The kinds of media a post's URL can point to, which decides how the post is rendered in the feed and on its own page
+#[derive(Clone, Debug, PartialEq)]
+pub enum PostMedia {
+    // A text post, or a URL that isn't http or https
+    None,
+    Image(String),
+    Gif(String),
+    Video(String),
+    // A player from another site, such as YouTube or PeerTube, taken from the embed_video_url Lemmy found for the link
+    Embed(String),
+    Link(String),
+}
+
+const IMAGE_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "webp", "avif", "bmp", "svg"];
+const VIDEO_EXTENSIONS: &[&str] = &["mp4", "webm", "ogv", "mov", "m4v"];
+
+// Only http and https URLs are ever placed in an href or src, since the URL of a post can come from any instance
+fn is_http_url(url: &str) -> bool {
+    let url = url.trim().to_lowercase();
+    url.starts_with("https://") || url.starts_with("http://")
+}
+
+// The lowercase file extension of a URL, ignoring the query string and fragment
+fn url_extension(url: &str) -> Option<String> {
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    let file_name = path.rsplit('/').next().unwrap_or_default();
+    file_name
+        .rsplit_once('.')
+        .map(|(_, extension)| extension.to_lowercase())
+}
+
+// The domain a link goes to, which is shown on its link card
+pub fn url_domain(url: &str) -> String {
+    url.trim()
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .split(['/', '?', '#'])
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches("www.")
+        .to_string()
+}
+
+impl PostMedia {
+    // Works out what a post links to, first by the extension of its URL and then by whether Lemmy found an embeddable player for it
+    pub fn from_post(post: &Post) -> Self {
+        let Some(url) = post.url.clone().filter(|url| is_http_url(url)) else {
+            return PostMedia::None;
+        };
+
+        match url_extension(&url).as_deref() {
+            Some("gif") => return PostMedia::Gif(url),
+            Some(extension) if IMAGE_EXTENSIONS.contains(&extension) => {
+                return PostMedia::Image(url)
+            }
+            Some(extension) if VIDEO_EXTENSIONS.contains(&extension) => {
+                return PostMedia::Video(url)
+            }
+            _ => {}
+        }
+
+        match post.embed_video_url.clone().filter(|url| is_http_url(url)) {
+            // Some sites give a video file as their embed rather than a player
+            Some(embed_url)
+                if url_extension(&embed_url)
+                    .map(|extension| VIDEO_EXTENSIONS.contains(&extension.as_str()))
+                    .unwrap_or_default() =>
+            {
+                PostMedia::Video(embed_url)
+            }
+            Some(embed_url) => PostMedia::Embed(embed_url),
+            None => PostMedia::Link(url),
+        }
+    }
+
+    // Whether the media can be expanded inline in the feed, links are always shown as a card instead
+    pub fn is_expandable(&self) -> bool {
+        !matches!(self, PostMedia::None | PostMedia::Link(_))
+    }
+
+    // The image shown in the thumbnail column of the feed, which falls back to the full image for image posts without a thumbnail
+    pub fn thumbnail(&self, post: &Post) -> Option<String> {
+        let thumbnail = post.thumbnail_url.clone().filter(|url| is_http_url(url));
+        match self {
+            PostMedia::None => Some("/static/default_assets/comment.png".to_string()),
+            PostMedia::Image(url) | PostMedia::Gif(url) => thumbnail.or(Some(url.clone())),
+            PostMedia::Link(_) => {
+                thumbnail.or(Some("/static/default_assets/external-link.png".to_string()))
+            }
+            PostMedia::Video(_) | PostMedia::Embed(_) => thumbnail,
+        }
+    }
+}
+
+// Each post has its own lightbox, so the id is made unique with the post's id
+fn lightbox_id(post_id: i32) -> String {
+    format!("post-media-lightbox-{}", post_id)
+}
+
+// The Bootstrap modal that shows an image at full size when it is clicked
+#[component]
+pub fn MediaLightbox(cx: Scope, post_id: i32, image_url: String, title: String) -> impl IntoView {
+    let modal_id = lightbox_id(post_id);
+    let label_id = format!("{}-label", modal_id);
+
+    view! { cx,
+        <div
+            class="modal fade"
+            id=modal_id
+            tabindex="-1"
+            aria-labelledby=label_id.clone()
+            aria-hidden="true"
+        >
+            <div class="modal-dialog modal-xl modal-dialog-centered">
+                <div class="modal-content bg-transparent border-0">
+                    <div class="modal-header border-0">
+                        <h1 class="modal-title fs-5 text-truncate" id=label_id>
+                            {title.clone()}
+                        </h1>
+                        <button
+                            type="button"
+                            class="btn-close"
+                            data-bs-dismiss="modal"
+                            aria-label=t(cx, "close")
+                        ></button>
+                    </div>
+                    <div class="modal-body text-center p-0">
+                        <a href=image_url.clone() target="_blank" rel="nofollow noopener ugc">
+                            <img src=proxied_image_url(&image_url, None) class="img-fluid rounded" alt=title/>
+                        </a>
+                    </div>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+// The thumbnail column of a feed item.
+// Clicking an image opens it in the lightbox, clicking a video or embed expands it inline, and clicking a link opens it in a new tab.
+#[component]
+pub fn PostThumbnail(
+    cx: Scope,
+    post: Post,
+    media: PostMedia,
+    expanded: RwSignal<bool>,
+) -> impl IntoView {
+    let thumbnail = media.thumbnail(&post);
+    let has_thumbnail = thumbnail.is_some();
+    let is_gif = matches!(media, PostMedia::Gif(_));
+
+    let thumbnail_image = move |cx: Scope| {
+        view! { cx,
+            <img
+                src=proxied_image(thumbnail.clone(), Some(256))
+                class="img-fluid rounded-start"
+                alt=""
+                loading="lazy"
+                style="max-height: 300px;"
+            />
+        }
+    };
+
+    match media {
+        PostMedia::Image(url) | PostMedia::Gif(url) => {
+            view! { cx,
+                <button
+                    type="button"
+                    class="btn p-0 border-0 position-relative"
+                    title=t(cx, "media-view-full-image")
+                    data-bs-toggle="modal"
+                    data-bs-target=format!("#{}", lightbox_id(post.id))
+                >
+                    {thumbnail_image(cx)}
+                    {is_gif
+                        .then(|| {
+                            view! { cx,
+                                <span class="badge text-bg-dark position-absolute bottom-0 end-0 m-1">
+                                    "GIF"
+                                </span>
+                            }
+                        })}
+                </button>
+                <MediaLightbox post_id=post.id image_url=url title=post.name/>
+            }
+            .into_view(cx)
+        }
+        PostMedia::Video(_) | PostMedia::Embed(_) => {
+            view! { cx,
+                <button
+                    type="button"
+                    class="btn p-0 border-0 position-relative"
+                    title=t(cx, "media-play-video")
+                    on:click=move |_| expanded.update(|expanded| *expanded = !*expanded)
+                >
+                    {if has_thumbnail {
+                        thumbnail_image(cx).into_view(cx)
+                    } else {
+                        view! { cx, <i aria-hidden="true" class="bi bi-film display-1 text-secondary"></i> }.into_view(cx)
+                    }}
+                    <i aria-hidden="true" class="bi bi-play-circle-fill fs-1 position-absolute top-50 start-50 translate-middle text-light"></i>
+                </button>
+            }
+            .into_view(cx)
+        }
+        PostMedia::Link(url) => {
+            view! { cx,
+                <a href=url target="_blank" rel="nofollow noopener ugc" title=t(cx, "media-open-link")>
+                    {thumbnail_image(cx)}
+                </a>
+            }
+            .into_view(cx)
+        }
+        PostMedia::None => thumbnail_image(cx).into_view(cx),
+    }
+}
+
+// A card for links that aren't media, with the title and description Lemmy fetched from the page
+#[component]
+pub fn LinkCard(cx: Scope, post: Post, url: String) -> impl IntoView {
+    let domain = url_domain(&url);
+
+    view! { cx,
+        <a
+            href=url
+            target="_blank"
+            rel="nofollow noopener ugc"
+            class="card text-reset text-decoration-none mb-2"
+        >
+            <div class="card-body py-2">
+                <div class="small text-body-secondary text-truncate">
+                    <i aria-hidden="true" class="bi bi-box-arrow-up-right"></i>
+                    " "
+                    {domain}
+                </div>
+                {post
+                    .embed_title
+                    .map(|embed_title| view! { cx, <div class="fw-semibold">{embed_title}</div> })}
+                {post
+                    .embed_description
+                    .map(|embed_description| {
+                        view! { cx, <div class="small text-body-secondary">{embed_description}</div> }
+                    })}
+            </div>
+        </a>
+    }
+}
+
+// The media of a post rendered in full, which is shown in the feed once expanded and straight away on the post's page.
+// Links are always shown as a link card, since they don't take up much room.
+#[component]
+pub fn PostMediaView(
+    cx: Scope,
+    post: Post,
+    media: PostMedia,
+    expanded: RwSignal<bool>,
+) -> impl IntoView {
+    if let PostMedia::Link(url) = media {
+        return view! { cx, <LinkCard post=post url=url/> }.into_view(cx);
+    }
+
+    (move || {
+        if !expanded.get() {
+            return ().into_view(cx);
+        }
+
+        match media.clone() {
+            PostMedia::Image(url) | PostMedia::Gif(url) => view! { cx,
+                <button
+                    type="button"
+                    class="btn p-0 border-0 mb-2"
+                    title=t(cx, "media-view-full-image")
+                    data-bs-toggle="modal"
+                    data-bs-target=format!("#{}", lightbox_id(post.id))
+                >
+                    <img
+                        src=proxied_image_url(&url, Some(1024))
+                        class="img-fluid rounded"
+                        alt=post.name.clone()
+                        style="max-height: 80vh;"
+                    />
+                </button>
+            }
+            .into_view(cx),
+            PostMedia::Video(url) => view! { cx,
+                <video
+                    src=url
+                    poster=proxied_image(post.thumbnail_url.clone().filter(|url| is_http_url(url)), Some(1024))
+                    class="w-100 rounded mb-2"
+                    style="max-height: 80vh;"
+                    controls=true
+                    preload="metadata"
+                ></video>
+            }
+            .into_view(cx),
+            // The player comes from another site, so it is sandboxed and doesn't get a referrer
+            PostMedia::Embed(url) => view! { cx,
+                <div class="ratio ratio-16x9 mb-2">
+                    <iframe
+                        src=url
+                        title=post.name.clone()
+                        class="rounded"
+                        loading="lazy"
+                        referrerpolicy="no-referrer"
+                        sandbox="allow-scripts allow-same-origin allow-popups allow-presentation"
+                        allow="fullscreen; picture-in-picture; encrypted-media"
+                        allowfullscreen=true
+                    ></iframe>
+                </div>
+            }
+            .into_view(cx),
+            PostMedia::Link(_) | PostMedia::None => ().into_view(cx),
+        }
+    })
+    .into_view(cx)
+}
+
+// The button that expands or collapses the media of a feed item
+#[component]
+pub fn ExpandMediaButton(cx: Scope, expanded: RwSignal<bool>) -> impl IntoView {
+    let label = move || {
+        if expanded.get() { translate(cx, "media-collapse") } else { translate(cx, "media-expand") }
+    };
+
+    view! { cx,
+        <button
+            type="button"
+            class="btn btn-link link-secondary p-0 ms-2 align-baseline"
+            title=label
+            aria-label=label
+            aria-expanded=move || expanded.get().to_string()
+            on:click=move |_| expanded.update(|expanded| *expanded = !*expanded)
+        >
+            <i aria-hidden="true" class=move || {
+                if expanded.get() { "bi bi-arrows-angle-contract" } else { "bi bi-arrows-angle-expand" }
+            }></i>
+        </button>
+    }
+}
@@ -1,26 +1,28 @@
 use leptos::*;
 use leptos_meta::*;
 use leptos_router::*;
-use markdown::*;
 
 use crate::api::structs::*;
 use crate::components::comments::Comments;
 use crate::components::feed::FeedItem;
+use crate::components::custom_emoji::use_custom_emojis;
+use crate::components::i18n::{t, t_count, translate};
+use crate::components::markdown::render_markdown;
+use crate::components::site::use_all_languages;
+use crate::components::time_ago::TimeAgo;
+use crate::image_proxy::proxy_image_sources;
 
 // TODO - post_view.rs:
-// Handle when there is no actual post body for an external link
 // Fix clicking on the PostItem title taking the page to an unreachable route
 // onclick functionality for voting, favoriting, crossposting, and reporting
 // Better handling for mobile layouts
 // Implement community avatars for posts in sensible manner
-// Finish fleshing out PostItem for stuff like language, edited status, date, etc
-// Finish implementing Markdown styling for blockquotes and superscripts (Apparently that's not a gfm thing?)
 // Improve overall styling for the post component box itself
 
 // The component box that holds the post body and contents itself
 #[component]
 pub fn PostView(cx: Scope, post: Resource<i32, Option<GetPostResponse>>) -> impl IntoView {
-    let err_msg = "Error loading this post: ";
+    let err_msg = t(cx, "error-loading-post");
 
     view! { cx,
         <div class="container overflow-hidden">
@@ -29,7 +31,7 @@ pub fn PostView(cx: Scope, post: Resource<i32, Option<GetPostResponse>>) -> impl
                 view! { cx,
                     <div class="d-flex align-items-center">
                         <h1>
-                            Loading...
+                            {t(cx, "loading")}
                         </h1>
                         <div class="spinner-grow ms-auto" role="status" aria-hidden="true"></div>
                     </div>
@@ -39,16 +41,13 @@ pub fn PostView(cx: Scope, post: Resource<i32, Option<GetPostResponse>>) -> impl
                     post.read(cx)
                         .map(|res| match res {
                             None => {
-                                view! { cx, <div>{format!("{err_msg}")}</div> }
+                                view! { cx, <div>{err_msg}</div> }
                             }
                             Some(res) => {
+                                let custom_emojis = use_custom_emojis(cx);
                                 let post_body = match res.post_view.post.body.clone() {
                                     Some(text) => {
-                                        markdown::to_html_with_options(
-                                                text.as_str(),
-                                                &Options::gfm(),
-                                            )
-                                            .unwrap()
+                                        proxy_image_sources(&render_markdown(text.as_str(), &custom_emojis), Some(1024))
                                     }
                                     None => "".to_string(),
                                 };
@@ -57,11 +56,16 @@ pub fn PostView(cx: Scope, post: Resource<i32, Option<GetPostResponse>>) -> impl
                                     <div>
                                         <div>
                                             <br/>
+                                            <PostStatus post=res.post_view.post.clone()/>
                                             <div class="card">
                                                 <div class="card-header pb-0">
-                                                    <FeedItem post_view=leptos::MaybeSignal::Static(
-                                                        res.post_view.clone(),
-                                                    )/>
+                                                    <FeedItem
+                                                        post_view=leptos::MaybeSignal::Static(
+                                                            res.post_view.clone(),
+                                                        )
+                                                        expand_media=true
+                                                    />
+                                                    <PostDetails post=res.post_view.post.clone()/>
                                                 </div>
                                                 <div class="card-body">
                                                     <Transition fallback=move || {
@@ -69,7 +73,7 @@ pub fn PostView(cx: Scope, post: Resource<i32, Option<GetPostResponse>>) -> impl
                                                         view! { cx,
                                                             <div class="d-flex align-items-center">
                                                                 <h1>
-                                                                    Loading...
+                                                                    {t(cx, "loading")}
                                                                 </h1>
                                                                 <div
                                                                     class="spinner-grow ms-auto"
@@ -85,13 +89,14 @@ pub fn PostView(cx: Scope, post: Resource<i32, Option<GetPostResponse>>) -> impl
                                                 </div>
                                             </div>
                                         </div>
+                                        <CrossPosts cross_posts=res.cross_posts.clone()/>
                                         <br/>
                                         <Transition fallback=move || {
                                             // Handles the loading screen while waiting for a reply from the API
                                             view! { cx,
                                                 <div class="d-flex align-items-center">
                                                     <h1>
-                                                        Loading...
+                                                        {t(cx, "loading")}
                                                     </h1>
                                                     <div
                                                         class="spinner-grow ms-auto"
@@ -114,3 +119,153 @@ pub fn PostView(cx: Scope, post: Resource<i32, Option<GetPostResponse>>) -> impl
         </div>
     }
 }
+
+// The banner above a post that is locked, or was deleted by its creator or removed by a moderator
+#[component]
+fn PostStatus(cx: Scope, post: Post) -> impl IntoView {
+    let (class, icon, message) = if post.removed {
+        ("alert-danger", "bi bi-shield-exclamation", translate(cx, "post-removed-by-moderator"))
+    } else if post.deleted {
+        ("alert-secondary", "bi bi-trash", translate(cx, "post-deleted-by-creator"))
+    } else if post.locked {
+        ("alert-warning", "bi bi-lock", translate(cx, "post-locked"))
+    } else {
+        return None;
+    };
+
+    Some(view! { cx,
+        <div class=format!("alert {} mb-3", class) role="status">
+            <i aria-hidden="true" class=icon></i>
+            " "
+            {message}
+        </div>
+    })
+}
+
+// When the post was made and edited, its language, and the tools for sharing it
+#[component]
+fn PostDetails(cx: Scope, post: Post) -> impl IntoView {
+    // Undetermined is language 0, which isn't worth showing
+    let language = (post.language_id != 0)
+        .then(|| {
+            use_all_languages(cx)
+                .into_iter()
+                .find(|language| language.id == post.language_id)
+                .map(|language| language.name)
+        })
+        .flatten();
+
+    let edited = post.updated.clone().map(|updated| {
+        view! { cx,
+            <span>
+                " · "
+                <i aria-hidden="true" class="bi bi-pencil"></i>
+                " "
+                <TimeAgo time=updated label="time-edited"/>
+            </span>
+        }
+    });
+
+    let copied = create_rw_signal(cx, false);
+    let copy_link = move |_| {
+        copy_page_link();
+        copied.set(true);
+    };
+
+    view! { cx,
+        <div class="d-flex flex-wrap align-items-center gap-2 small text-body-secondary py-2">
+            <span>
+                <i aria-hidden="true" class="bi bi-clock"></i>
+                " "
+                <TimeAgo time=post.published.clone()/>
+            </span>
+            {edited}
+            {language.map(|language| view! { cx,
+                <span>
+                    " · "
+                    <i aria-hidden="true" class="bi bi-translate"></i>
+                    " "
+                    {language}
+                </span>
+            })}
+            <div class="ms-auto d-flex gap-2">
+                <button type="button" class="btn btn-sm btn-outline-secondary" on:click=copy_link>
+                    <i aria-hidden="true" class=move || if copied.get() { "bi bi-clipboard-check" } else { "bi bi-link-45deg" }></i>
+                    " "
+                    {move || if copied.get() { translate(cx, "post-copied") } else { translate(cx, "post-copy-link") }}
+                </button>
+                // Posts from other instances link back to where they were made, local posts link to their ActivityPub id
+                <a
+                    class="btn btn-sm btn-outline-secondary"
+                    href=post.ap_id.clone()
+                    rel="external noopener"
+                    target="_blank"
+                >
+                    <i aria-hidden="true" class="bi bi-box-arrow-up-right"></i>
+                    " "
+                    {if post.local { t(cx, "post-permalink") } else { t(cx, "post-view-original") }}
+                </a>
+            </div>
+        </div>
+    }
+}
+
+// Copies the link to the page the post is on, which only does anything in the browser
+fn copy_page_link() {
+    #[cfg(not(feature = "ssr"))]
+    {
+        use wasm_bindgen::JsCast;
+
+        // The Clipboard API is still unstable in web-sys, so writeText is called through JavaScript instead
+        let navigator = window().navigator();
+        let Ok(href) = window().location().href() else {
+            return;
+        };
+        let write_text = js_sys::Reflect::get(&navigator, &"clipboard".into())
+            .ok()
+            .filter(|clipboard| !clipboard.is_undefined())
+            .and_then(|clipboard| {
+                let write_text = js_sys::Reflect::get(&clipboard, &"writeText".into()).ok()?;
+                Some((clipboard, write_text.dyn_into::<js_sys::Function>().ok()?))
+            });
+        if let Some((clipboard, write_text)) = write_text {
+            let _ = write_text.call1(&clipboard, &href.into());
+        }
+    }
+}
+
+// The other communities the same link was posted to
+#[component]
+fn CrossPosts(cx: Scope, cross_posts: Vec<PostView>) -> impl IntoView {
+    if cross_posts.is_empty() {
+        return None;
+    }
+
+    Some(view! { cx,
+        <div class="card mt-3">
+            <div class="card-header">
+                <i aria-hidden="true" class="bi bi-shuffle"></i>
+                " "
+                {t(cx, "post-cross-posted-to")}
+            </div>
+            <ul class="list-group list-group-flush">
+                {cross_posts
+                    .into_iter()
+                    .map(|cross_post| {
+                        let comments = cross_post.counts.comments.unwrap_or_default();
+                        view! { cx,
+                            <li class="list-group-item d-flex justify-content-between align-items-center">
+                                <a href=format!("/post/{}", cross_post.post.id)>
+                                    {cross_post.community.title.clone()}
+                                </a>
+                                <span class="small text-body-secondary">
+                                    {t_count(cx, "comments-count", comments.into())}
+                                </span>
+                            </li>
+                        }
+                    })
+                    .collect_view(cx)}
+            </ul>
+        </div>
+    })
+}
@@ -0,0 +1,317 @@
+// TODO - sanitize.rs:
+// Replace this with a proper HTML parser based sanitizer if one can be used for both SSR and hydrate without bloating the WASM bundle
+
+// This is the last step before any user supplied HTML is given to inner_html.
+// The Markdown renderer already escapes raw HTML, so this is insurance against anything slipping through the renderer or the post-processing done on its output.
+// Only the tags and attributes the renderer produces are kept, every URL is checked, and links are given rel="nofollow noopener ugc".
+
+// The tags that are kept, along with the attributes that are allowed on each of them
+const ALLOWED_TAGS: &[(&str, &[&str])] = &[
+    (
+        "a",
+        &[
+            "href",
+            "title",
+            "class",
+            "id",
+            "aria-label",
+            "aria-describedby",
+            "data-footnote-ref",
+            "data-footnote-backref",
+        ],
+    ),
+    ("blockquote", &[]),
+    ("br", &[]),
+    ("code", &["class"]),
+    ("del", &[]),
+    ("details", &[]),
+    ("em", &[]),
+    ("h1", &["id", "class"]),
+    ("h2", &["id", "class"]),
+    ("h3", &["id", "class"]),
+    ("h4", &["id", "class"]),
+    ("h5", &["id", "class"]),
+    ("h6", &["id", "class"]),
+    ("hr", &[]),
+    (
+        "img",
+        &["src", "alt", "title", "class", "loading", "width", "height"],
+    ),
+    ("input", &["type", "checked", "disabled"]),
+    ("li", &["id"]),
+    ("ol", &["start"]),
+    ("p", &[]),
+    ("pre", &[]),
+    ("section", &["class", "data-footnotes"]),
+    ("strong", &[]),
+    ("sub", &[]),
+    ("summary", &[]),
+    ("sup", &[]),
+    ("table", &[]),
+    ("tbody", &[]),
+    ("td", &["align"]),
+    ("th", &["align"]),
+    ("thead", &[]),
+    ("tr", &[]),
+    ("ul", &[]),
+];
+
+// Tags that are self closing, so they are written as <br /> like the Markdown renderer does
+const VOID_TAGS: &[&str] = &["br", "hr", "img", "input"];
+
+// Tags whose content is dropped along with the tag, since their content isn't meant to be shown as text
+const DROPPED_CONTENT_TAGS: &[&str] = &[
+    "script", "style", "iframe", "object", "embed", "template", "noscript", "textarea", "title",
+    "xmp", "noembed", "noframes", "svg", "math",
+];
+
+// The classes that the renderer adds, anything else in a class attribute is removed
+fn is_allowed_class(class: &str) -> bool {
+    matches!(
+        class,
+        "mention"
+            | "img-fluid"
+            | "custom-emoji"
+            | "footnotes"
+            | "sr-only"
+            | "data-footnote-backref"
+    ) || (class.starts_with("language-")
+        && class[9..]
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '+' || c == '#'))
+}
+
+// Decodes the character references a browser would decode in an attribute, so that something like java&#115;cript: can't sneak past the URL check
+fn decode_entities(value: &str) -> String {
+    let entity_regex = regex::Regex::new(r"&(#[xX][0-9a-fA-F]+|#[0-9]+|[A-Za-z]+);?").unwrap();
+
+    entity_regex
+        .replace_all(value, |captures: &regex::Captures| {
+            let entity = &captures[1];
+            let decoded = if let Some(hex) = entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+            {
+                u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+            } else if let Some(decimal) = entity.strip_prefix('#') {
+                decimal.parse::<u32>().ok().and_then(char::from_u32)
+            } else {
+                match entity.to_lowercase().as_str() {
+                    "amp" => Some('&'),
+                    "lt" => Some('<'),
+                    "gt" => Some('>'),
+                    "quot" => Some('"'),
+                    "apos" => Some('\''),
+                    "colon" => Some(':'),
+                    "tab" => Some('\t'),
+                    "newline" => Some('\n'),
+                    "nbsp" => Some('\u{a0}'),
+                    _ => None,
+                }
+            };
+
+            decoded
+                .map(|character| character.to_string())
+                .unwrap_or_else(|| captures[0].to_string())
+        })
+        .to_string()
+}
+
+// Escapes a decoded attribute value so it can be placed back inside of double quotes
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+// Checks a URL against the schemes that are allowed, with relative URLs always being allowed.
+// Browsers ignore whitespace and control characters in a scheme, so they are removed before checking it.
+fn is_safe_url(url: &str, allowed_schemes: &[&str]) -> bool {
+    let url = url
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .collect::<String>()
+        .to_lowercase();
+
+    match url.find(':') {
+        // A colon after the path, query, or fragment has started isn't a scheme
+        Some(colon) if !url[..colon].contains(['/', '?', '#']) => {
+            allowed_schemes.contains(&&url[..colon])
+        }
+        _ => true,
+    }
+}
+
+// Links that leave the site open in a new tab, while links to routes on Wolfrs itself stay in the same tab
+fn is_external_url(url: &str) -> bool {
+    let url = url.trim().to_lowercase();
+    url.starts_with("http://") || url.starts_with("https://") || url.starts_with("//")
+}
+
+// Rebuilds an allowed tag with only its allowed attributes, checking the values of the ones that could be dangerous
+fn sanitize_tag(name: &str, attributes: &str, allowed_attributes: &[&str]) -> String {
+    let attribute_regex = regex::Regex::new(
+        r#"([A-Za-z_:][-A-Za-z0-9_:.]*)(?:\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'=<>`]+)))?"#,
+    )
+    .unwrap();
+
+    let mut output = format!("<{}", name);
+    let mut href = None;
+
+    for captures in attribute_regex.captures_iter(attributes) {
+        let attribute = captures[1].to_lowercase();
+        if !allowed_attributes.contains(&attribute.as_str()) {
+            continue;
+        }
+
+        let value = captures
+            .get(2)
+            .or(captures.get(3))
+            .or(captures.get(4))
+            .map(|value| decode_entities(value.as_str()));
+
+        let value = match (attribute.as_str(), value) {
+            ("href", Some(value)) => {
+                if !is_safe_url(&value, &["http", "https", "mailto"]) {
+                    continue;
+                }
+                href = Some(value.clone());
+                value
+            }
+            ("src", Some(value)) => {
+                if !is_safe_url(&value, &["http", "https"]) {
+                    continue;
+                }
+                value
+            }
+            ("class", Some(value)) => {
+                let classes = value
+                    .split_whitespace()
+                    .filter(|class| is_allowed_class(class))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                if classes.is_empty() {
+                    continue;
+                }
+                classes
+            }
+            // Ids could clobber global names in the page, so only the prefixed ones from footnotes are kept
+            ("id", Some(value)) => {
+                if !(value.starts_with("user-content-") || value == "footnote-label") {
+                    continue;
+                }
+                value
+            }
+            ("type", Some(value)) => {
+                if value != "checkbox" {
+                    continue;
+                }
+                value
+            }
+            ("loading", Some(value)) => {
+                if value != "lazy" {
+                    continue;
+                }
+                value
+            }
+            ("width" | "height" | "start", Some(value)) => {
+                if value.is_empty() || !value.chars().all(|c| c.is_ascii_digit()) {
+                    continue;
+                }
+                value
+            }
+            ("align", Some(value)) => {
+                if !matches!(value.as_str(), "left" | "right" | "center") {
+                    continue;
+                }
+                value
+            }
+            // Task list checkboxes are only for display
+            ("disabled", _) => continue,
+            (_, Some(value)) => value,
+            (_, None) => String::new(),
+        };
+
+        output.push_str(&format!(" {}=\"{}\"", attribute, escape_attribute(&value)));
+    }
+
+    match name {
+        "a" => {
+            output.push_str(" rel=\"nofollow noopener ugc\"");
+            if href.as_deref().map(is_external_url).unwrap_or_default() {
+                output.push_str(" target=\"_blank\"");
+            }
+        }
+        "input" => output.push_str(" disabled=\"\""),
+        _ => {}
+    }
+
+    if VOID_TAGS.contains(&name) {
+        output.push_str(" />");
+    } else {
+        output.push('>');
+    }
+
+    output
+}
+
+// Removes everything from the HTML that isn't on the allowlist, so it is safe to give to inner_html
+pub fn sanitize_html(html: &str) -> String {
+    let tag_regex =
+        regex::Regex::new(r#"<(/?)([A-Za-z][A-Za-z0-9]*)((?:[^>"']|"[^"]*"|'[^']*')*)>"#).unwrap();
+
+    let mut output = String::with_capacity(html.len());
+    let mut last_end = 0;
+    // The tag whose content is currently being dropped, such as a <script>
+    let mut dropping: Option<String> = None;
+
+    for tag in tag_regex.captures_iter(html) {
+        let whole = tag.get(0).unwrap();
+        let closing = !tag[1].is_empty();
+        let name = tag[2].to_lowercase();
+        let attributes = &tag[3];
+
+        let text = &html[last_end..whole.start()];
+        last_end = whole.end();
+
+        if let Some(dropped) = &dropping {
+            if closing && &name == dropped {
+                dropping = None;
+            }
+            continue;
+        }
+
+        // Any < in the text that isn't part of a tag is escaped, so it can't start one once combined with something else
+        output.push_str(&text.replace('<', "&lt;").replace('>', "&gt;"));
+
+        if DROPPED_CONTENT_TAGS.contains(&name.as_str()) {
+            if !closing && !attributes.trim_end().ends_with('/') {
+                dropping = Some(name);
+            }
+            continue;
+        }
+
+        let Some((_, allowed_attributes)) = ALLOWED_TAGS
+            .iter()
+            .find(|(allowed_name, _)| *allowed_name == name)
+        else {
+            continue;
+        };
+
+        if closing {
+            if !VOID_TAGS.contains(&name.as_str()) {
+                output.push_str(&format!("</{}>", name));
+            }
+        } else {
+            output.push_str(&sanitize_tag(&name, attributes, allowed_attributes));
+        }
+    }
+
+    if dropping.is_none() {
+        output.push_str(&html[last_end..].replace('<', "&lt;").replace('>', "&gt;"));
+    }
+
+    output
+}
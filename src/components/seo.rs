@@ -0,0 +1,321 @@
+use leptos::*;
+use leptos_meta::*;
+use serde_json::json;
+
+use crate::api::structs::*;
+use crate::components::community_header::community_handle;
+use crate::components::instance::*;
+use crate::components::markdown::render_markdown;
+use crate::components::post_media::PostMedia;
+use crate::components::site::use_site_view;
+use crate::timestamps::Timestamp;
+
+// TODO - seo.rs:
+// Give the pages without a PageHead their own title, since they keep the last page's title after navigating in the browser
+
+// Search engines cut descriptions off at around this many characters
+pub const DESCRIPTION_MAX_LENGTH: usize = 160;
+
+// The <title> of a page, which is its own title followed by the instance's name, or the name and slogan for pages without one
+pub fn page_title(page: &str, instance_name: &str, slogan: &str) -> String {
+    match (page.trim(), slogan.trim()) {
+        ("", "") => instance_name.to_string(),
+        ("", slogan) => format!("{}: {}", instance_name, slogan),
+        (page, _) => format!("{} - {}", page, instance_name),
+    }
+}
+
+// A plain text summary of some Markdown, such as a post's body, for the description of a page.
+// Longer text is cut at the last whole word and ended with an ellipsis.
+pub fn plain_text_summary(markdown: &str, max_length: usize) -> String {
+    let tags = regex::Regex::new(r"<[^>]*>").unwrap();
+    let html = render_markdown(markdown, &[]);
+    let text = tags
+        .replace_all(&html, " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&amp;", "&");
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if text.chars().count() <= max_length {
+        return text;
+    }
+    // One character is kept free for the ellipsis
+    let cut = text.chars().take(max_length - 1).collect::<String>();
+    let cut = match cut.rfind(' ') {
+        Some(space) if space > 0 => &cut[..space],
+        _ => cut.as_str(),
+    };
+
+    format!("{}…", cut.trim_end_matches(|c: char| c.is_ascii_punctuation()))
+}
+
+// The robots meta tag for a page, or None to leave it to the crawler.
+// Private instances are kept out of search engines entirely, and NSFW pages are kept out of search results.
+pub fn robots_policy(nsfw: bool, private_instance: bool) -> Option<&'static str> {
+    if private_instance {
+        Some("noindex, nofollow")
+    } else if nsfw {
+        Some("noindex")
+    } else {
+        None
+    }
+}
+
+// A path on this site as a full URL, which links from other sites and search engines need
+pub fn absolute_url(site_url: &str, path: &str) -> String {
+    if path.starts_with("https://") || path.starts_with("http://") {
+        return path.to_string();
+    }
+    format!("{}{}", site_url.trim_end_matches('/'), path)
+}
+
+// Content that federated here from another instance points search engines at the original, so it isn't indexed once for every instance
+fn canonical_url(site_url: &str, local: bool, local_path: &str, original_url: &str) -> String {
+    if local || original_url.is_empty() {
+        absolute_url(site_url, local_path)
+    } else {
+        original_url.to_string()
+    }
+}
+
+// Images in link previews have to be full URLs, so Wolfrs' own placeholders are left out
+fn preview_image(url: Option<String>) -> Option<String> {
+    url.filter(|url| url.starts_with("https://") || url.starts_with("http://"))
+}
+
+// The structured data search engines read a post from, as a schema.org DiscussionForumPosting in JSON-LD.
+// < is escaped so nothing in a post can close the <script> element early.
+pub fn post_json_ld(post_view: &PostView, url: &str) -> String {
+    let post = &post_view.post;
+    let creator = &post_view.creator;
+
+    let mut posting = json!({
+        "@context": "https://schema.org",
+        "@type": "DiscussionForumPosting",
+        "headline": post.name,
+        "url": url,
+        "author": {
+            "@type": "Person",
+            "name": creator.display_name.clone().unwrap_or_else(|| creator.name.clone()),
+            "url": creator.actor_id,
+        },
+        "interactionStatistic": [
+            {
+                "@type": "InteractionCounter",
+                "interactionType": "https://schema.org/CommentAction",
+                "userInteractionCount": post_view.counts.comments.unwrap_or_default(),
+            },
+            {
+                "@type": "InteractionCounter",
+                "interactionType": "https://schema.org/LikeAction",
+                "userInteractionCount": post_view.counts.upvotes,
+            },
+        ],
+    });
+
+    if let Some(body) = post.body.as_deref().filter(|body| !body.trim().is_empty()) {
+        posting["text"] = json!(plain_text_summary(body, usize::MAX));
+    }
+    if let Some(published) = Timestamp::parse(&post.published) {
+        posting["datePublished"] = json!(published.to_iso());
+    }
+    if let Some(updated) = post.updated.as_deref().and_then(Timestamp::parse) {
+        posting["dateModified"] = json!(updated.to_iso());
+    }
+    if let Some(link) = preview_image(post.url.clone()) {
+        posting["sharedContent"] = json!({ "@type": "WebPage", "url": link });
+    }
+    if let Some(image) = preview_image(PostMedia::from_post(post).thumbnail(post)) {
+        if !(post.nsfw || post_view.community.nsfw) {
+            posting["image"] = json!(image);
+        }
+    }
+
+    posting.to_string().replace('<', "\\u003c")
+}
+
+// Everything about a page that goes in its <head> for search engines and link previews
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PageMeta {
+    // The page's own title, which is empty on the front page
+    pub title: String,
+    pub description: String,
+    pub canonical_url: String,
+    // The image for link previews, which NSFW pages never have so their previews are safe to show anywhere
+    pub image: Option<String>,
+    // The OpenGraph type, such as website, article, or profile
+    pub kind: &'static str,
+    pub nsfw: bool,
+    // The community a post is in
+    pub section: Option<String>,
+    pub json_ld: Option<String>,
+}
+
+impl PageMeta {
+    // A page that isn't about one post, community, or user, such as the front page or the communities list
+    pub fn for_page(title: String, description: String, path: &str, site_url: &str) -> Self {
+        PageMeta {
+            title,
+            description,
+            canonical_url: absolute_url(site_url, path),
+            kind: "website",
+            ..Default::default()
+        }
+    }
+
+    pub fn for_post(post_view: &PostView, site_url: &str) -> Self {
+        let post = &post_view.post;
+        let community = &post_view.community;
+        let nsfw = post.nsfw || community.nsfw;
+        let canonical_url = canonical_url(
+            site_url,
+            post.local,
+            &format!("/post/{}", post.id),
+            &post.ap_id,
+        );
+
+        // Link posts without a body are described by the page they link to, and anything else by its community
+        let description = post
+            .body
+            .as_deref()
+            .or(post.embed_description.as_deref())
+            .map(|body| plain_text_summary(body, DESCRIPTION_MAX_LENGTH))
+            .filter(|description| !description.is_empty())
+            .unwrap_or_else(|| community.title.clone());
+        let image = preview_image(PostMedia::from_post(post).thumbnail(post))
+            .or_else(|| preview_image(community.icon.clone()));
+
+        PageMeta {
+            title: post.name.clone(),
+            description,
+            json_ld: Some(post_json_ld(post_view, &canonical_url)),
+            canonical_url,
+            image: image.filter(|_| !nsfw),
+            kind: "article",
+            nsfw,
+            section: Some(community.title.clone()),
+        }
+    }
+
+    pub fn for_community(community: &Community, site_url: &str) -> Self {
+        let description = community
+            .description
+            .as_deref()
+            .map(|description| plain_text_summary(description, DESCRIPTION_MAX_LENGTH))
+            .filter(|description| !description.is_empty())
+            .unwrap_or_else(|| community_handle(community));
+
+        PageMeta {
+            title: community.title.clone(),
+            description,
+            canonical_url: canonical_url(
+                site_url,
+                community.local,
+                &format!("/community/{}", community.name),
+                &community.actor_id,
+            ),
+            image: preview_image(community.icon.clone()).filter(|_| !community.nsfw),
+            kind: "website",
+            nsfw: community.nsfw,
+            ..Default::default()
+        }
+    }
+
+    pub fn for_person(person: &Person, site_url: &str) -> Self {
+        let description = person
+            .bio
+            .as_deref()
+            .map(|bio| plain_text_summary(bio, DESCRIPTION_MAX_LENGTH))
+            .filter(|bio| !bio.is_empty())
+            .unwrap_or_else(|| format!("@{}", person.name));
+
+        PageMeta {
+            title: person
+                .display_name
+                .clone()
+                .unwrap_or_else(|| person.name.clone()),
+            description,
+            canonical_url: canonical_url(
+                site_url,
+                person.local,
+                &format!("/user/{}", person.name),
+                &person.actor_id,
+            ),
+            image: preview_image(person.avatar.clone()),
+            kind: "profile",
+            ..Default::default()
+        }
+    }
+}
+
+// The URL of this Wolfrs, for the pages to build their PageMeta with
+pub fn site_url() -> String {
+    get_instance_details()
+        .map(|instance| instance.site_url().to_string())
+        .unwrap_or_default()
+}
+
+// The title, description, canonical link, OpenGraph and Twitter card tags, robots policy, and structured data for a page.
+// Pages that load what they are about render this once it has loaded, and their routes use SsrMode::Async so it makes it into the <head>.
+#[component]
+pub fn PageHead(cx: Scope, meta: PageMeta) -> impl IntoView {
+    let site_name = get_instance_details()
+        .map(|instance| instance.name)
+        .unwrap_or_default();
+    let preview_title = if meta.title.is_empty() {
+        site_name.clone()
+    } else {
+        meta.title.clone()
+    };
+    let twitter_card = if meta.image.is_some() {
+        "summary_large_image"
+    } else {
+        "summary"
+    };
+
+    view! { cx,
+        <Title text=meta.title.clone()/>
+        <Meta name="description" content=meta.description.clone()/>
+        <Link rel="canonical" href=meta.canonical_url.clone()/>
+        <Meta property="og:type" content=meta.kind/>
+        <Meta property="og:site_name" content=site_name/>
+        <Meta property="og:title" content=preview_title.clone()/>
+        <Meta property="og:description" content=meta.description.clone()/>
+        <Meta property="og:url" content=meta.canonical_url/>
+        <Meta name="twitter:card" content=twitter_card/>
+        <Meta name="twitter:title" content=preview_title/>
+        <Meta name="twitter:description" content=meta.description/>
+        {meta
+            .image
+            .map(|image| {
+                view! { cx,
+                    <Meta property="og:image" content=image.clone()/>
+                    <Meta name="twitter:image" content=image/>
+                }
+            })}
+        {meta.section.map(|section| view! { cx, <Meta property="article:section" content=section/> })}
+        {robots_policy(meta.nsfw, false).map(|robots| view! { cx, <Meta name="robots" content=robots/> })}
+        {meta
+            .json_ld
+            .map(|json_ld| view! { cx, <script type="application/ld+json" inner_html=json_ld></script> })}
+    }
+}
+
+// The robots policy for the whole instance, which keeps private instances out of search engines. Needs the site, so it is rendered by the App.
+#[component]
+pub fn InstanceRobots(cx: Scope) -> impl IntoView {
+    view! { cx,
+        <Transition fallback=move || ()>
+            {move || {
+                let private_instance = use_site_view(cx)
+                    .is_some_and(|site_view| site_view.local_site.private_instance);
+                robots_policy(false, private_instance)
+                    .map(|robots| view! { cx, <Meta name="robots" content=robots/> })
+            }}
+        </Transition>
+    }
+}
@@ -0,0 +1,401 @@
+use leptos::*;
+#[cfg(not(feature = "ssr"))]
+use leptos_router::{use_navigate, NavigateOptions, NavigationError};
+
+use crate::api::{get_cookie, set_cookie};
+use crate::components::i18n::t;
+
+// TODO - shortcuts.rs:
+// Move the on/off switch into the settings page once it exists, and keep it in the help as well
+// Let people change which keys are used
+// Add a and z to vote once voting exists, and g i for the inbox once the notifications page exists
+
+// Shortcuts are on unless they have been turned off, which is remembered in a cookie like the theme
+const SHORTCUTS_COOKIE: &str = "wolfrs_shortcuts";
+
+// The attributes the shortcuts look for on the focused post or comment.
+// Buttons are found by their data-shortcut, and links are read from the item itself.
+pub const ACTION_ATTRIBUTE: &str = "data-shortcut";
+pub const LINK_ATTRIBUTE: &str = "data-link";
+pub const COMMENTS_LINK_ATTRIBUTE: &str = "data-comments-link";
+
+// The id of the help dialog, which is focused when it opens
+const HELP_DIALOG_ID: &str = "shortcuts-help";
+
+// Every keyboard shortcut, in the order they are listed in the help
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Shortcut {
+    NextItem,
+    PreviousItem,
+    OpenLink,
+    OpenComments,
+    CollapseComment,
+    GoHome,
+    Search,
+    // Handled by the Markdown editor itself, since it is the one shortcut that works while typing
+    Submit,
+    Help,
+}
+
+impl Shortcut {
+    pub const ALL: [Shortcut; 9] = [
+        Shortcut::NextItem,
+        Shortcut::PreviousItem,
+        Shortcut::OpenLink,
+        Shortcut::OpenComments,
+        Shortcut::CollapseComment,
+        Shortcut::GoHome,
+        Shortcut::Search,
+        Shortcut::Submit,
+        Shortcut::Help,
+    ];
+
+    // The keys as KeyboardEvent.key names them, with a space between keys that are pressed one after the other.
+    // They follow Reddit Enhancement Suite where it has the same shortcut, so they are already familiar.
+    pub fn keys(&self) -> &'static str {
+        match self {
+            Shortcut::NextItem => "j",
+            Shortcut::PreviousItem => "k",
+            Shortcut::OpenLink => "o",
+            Shortcut::OpenComments => "c",
+            Shortcut::CollapseComment => "Enter",
+            Shortcut::GoHome => "g h",
+            Shortcut::Search => "/",
+            Shortcut::Submit => "Ctrl+Enter",
+            Shortcut::Help => "?",
+        }
+    }
+
+    // What the shortcut does, in the locale files
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            Shortcut::NextItem => "shortcut-next",
+            Shortcut::PreviousItem => "shortcut-previous",
+            Shortcut::OpenLink => "shortcut-open-link",
+            Shortcut::OpenComments => "shortcut-open-comments",
+            Shortcut::CollapseComment => "shortcut-collapse",
+            Shortcut::GoHome => "shortcut-home",
+            Shortcut::Search => "shortcut-search",
+            Shortcut::Submit => "shortcut-submit",
+            Shortcut::Help => "shortcut-help",
+        }
+    }
+
+    // Whether the shortcut works anywhere on the page, rather than only in a text box
+    fn is_global(&self) -> bool {
+        *self != Shortcut::Submit
+    }
+}
+
+// What a key press means, given the key pressed before it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KeyMatch {
+    Shortcut(Shortcut),
+    // The first key of a shortcut such as g h, which waits for the next key
+    Prefix,
+    None,
+}
+
+// Works out which shortcut a key press is. A key that doesn't finish a two key shortcut is tried on its own,
+// so pressing g by mistake doesn't swallow the j after it.
+pub fn match_keys(previous: Option<&str>, key: &str) -> KeyMatch {
+    let pressed = match previous {
+        Some(previous) => format!("{} {}", previous, key),
+        None => key.to_string(),
+    };
+    let global = Shortcut::ALL
+        .into_iter()
+        .filter(|shortcut| shortcut.is_global());
+
+    if let Some(shortcut) = global.clone().find(|shortcut| shortcut.keys() == pressed) {
+        return KeyMatch::Shortcut(shortcut);
+    }
+    if previous.is_some() {
+        return match_keys(None, key);
+    }
+    if global
+        .into_iter()
+        .any(|shortcut| shortcut.keys().starts_with(&format!("{} ", key)))
+    {
+        return KeyMatch::Prefix;
+    }
+
+    KeyMatch::None
+}
+
+// Whether shortcuts are turned on, and whether the help is open, which are provided as context by the App
+#[derive(Clone, Copy)]
+pub struct ShortcutsContext {
+    pub enabled: RwSignal<bool>,
+    pub help_open: RwSignal<bool>,
+}
+
+impl ShortcutsContext {
+    pub fn set_enabled(&self, enabled: bool) {
+        set_cookie(SHORTCUTS_COOKIE, if enabled { "on" } else { "off" });
+        self.enabled.set(enabled);
+    }
+
+    // Opens or closes the help, moving focus into it when it opens so Escape and Tab work straight away
+    pub fn toggle_help(&self) {
+        self.help_open.update(|open| *open = !*open);
+        #[cfg(not(feature = "ssr"))]
+        request_animation_frame(|| focus_element(HELP_DIALOG_ID));
+    }
+}
+
+pub fn provide_shortcuts(cx: Scope) {
+    let enabled = get_cookie(cx, SHORTCUTS_COOKIE).as_deref() != Some("off");
+
+    provide_context(
+        cx,
+        ShortcutsContext {
+            enabled: create_rw_signal(cx, enabled),
+            help_open: create_rw_signal(cx, false),
+        },
+    );
+}
+
+pub fn use_shortcuts(cx: Scope) -> ShortcutsContext {
+    use_context::<ShortcutsContext>(cx).expect("provide_shortcuts should be called by the App")
+}
+
+// Whether shortcuts are turned on, which is true for anything rendered outside the App
+pub fn shortcuts_enabled(cx: Scope) -> bool {
+    use_context::<ShortcutsContext>(cx)
+        .map(|shortcuts| shortcuts.enabled.get_untracked())
+        .unwrap_or(true)
+}
+
+// Listens for shortcuts across the whole page, and shows the help when ? is pressed. Needs to be inside the Router.
+#[component]
+pub fn KeyboardShortcuts(cx: Scope) -> impl IntoView {
+    let shortcuts = use_shortcuts(cx);
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        use crate::components::a11y::{in_text_field, is_typing, on_window_keydown};
+
+        let navigate = use_navigate(cx);
+        // The first key of a two key shortcut, while waiting for the second
+        let pending = store_value(cx, None::<String>);
+
+        on_window_keydown(cx, move |ev| {
+            // Escape closes the help, or leaves a text box so the shortcuts work again
+            if ev.key() == "Escape" {
+                if shortcuts.help_open.get_untracked() {
+                    shortcuts.help_open.set(false);
+                    ev.prevent_default();
+                } else if in_text_field(&ev) {
+                    blur_active_element();
+                }
+                return;
+            }
+            if !shortcuts.enabled.get_untracked() || is_typing(&ev) {
+                pending.set_value(None);
+                return;
+            }
+
+            let key = ev.key();
+            match match_keys(pending.get_value().as_deref(), &key) {
+                KeyMatch::Prefix => {
+                    pending.set_value(Some(key));
+                    ev.prevent_default();
+                }
+                KeyMatch::None => pending.set_value(None),
+                KeyMatch::Shortcut(shortcut) => {
+                    pending.set_value(None);
+                    if run_shortcut(shortcut, shortcuts, &navigate) {
+                        ev.prevent_default();
+                    }
+                }
+            }
+        });
+    }
+
+    view! { cx, <ShortcutsHelp shortcuts=shortcuts/> }
+}
+
+// Does what a shortcut says, returning false if there was nothing for it to do so the key press is left alone
+#[cfg(not(feature = "ssr"))]
+fn run_shortcut(
+    shortcut: Shortcut,
+    shortcuts: ShortcutsContext,
+    navigate: &impl Fn(&str, NavigateOptions) -> Result<(), NavigationError>,
+) -> bool {
+    use crate::components::a11y::{focus_item, focused_item, COMMENT_ATTRIBUTE};
+
+    let open = |href: String| {
+        // Links to other sites are left to the browser, the router only knows Wolfrs' own pages
+        if href.starts_with('/') {
+            let _ = navigate(&href, Default::default());
+        } else {
+            let _ = window().location().set_href(&href);
+        }
+        true
+    };
+
+    match shortcut {
+        Shortcut::NextItem => focus_item(true),
+        Shortcut::PreviousItem => focus_item(false),
+        Shortcut::OpenLink => focused_item()
+            .and_then(|item| item.get_attribute(LINK_ATTRIBUTE))
+            .is_some_and(open),
+        Shortcut::OpenComments => focused_item()
+            .and_then(|item| item.get_attribute(COMMENTS_LINK_ATTRIBUTE))
+            .is_some_and(open),
+        // Enter only collapses the comment itself, so it still follows a link or presses a button inside it
+        Shortcut::CollapseComment => {
+            document()
+                .active_element()
+                .is_some_and(|active| active.has_attribute(COMMENT_ATTRIBUTE))
+                && click_in_focused_item("collapse")
+        }
+        Shortcut::GoHome => open("/".to_string()),
+        Shortcut::Search => {
+            click_element("search-toggle");
+            focus_element("searchBox");
+            true
+        }
+        Shortcut::Help => {
+            shortcuts.toggle_help();
+            true
+        }
+        Shortcut::Submit => false,
+    }
+}
+
+// Presses a button in the focused post or comment, found by its data-shortcut
+#[cfg(not(feature = "ssr"))]
+fn click_in_focused_item(action: &str) -> bool {
+    use wasm_bindgen::JsCast;
+
+    crate::components::a11y::focused_item()
+        .and_then(|item| {
+            item.query_selector(&format!("[{}=\"{}\"]", ACTION_ATTRIBUTE, action))
+                .ok()
+                .flatten()
+        })
+        .and_then(|button| button.dyn_into::<web_sys::HtmlElement>().ok())
+        .map(|button| button.click())
+        .is_some()
+}
+
+#[cfg(not(feature = "ssr"))]
+fn html_element(id: &str) -> Option<web_sys::HtmlElement> {
+    use wasm_bindgen::JsCast;
+
+    document()
+        .get_element_by_id(id)
+        .and_then(|element| element.dyn_into::<web_sys::HtmlElement>().ok())
+}
+
+#[cfg(not(feature = "ssr"))]
+fn click_element(id: &str) {
+    if let Some(element) = html_element(id) {
+        element.click();
+    }
+}
+
+#[cfg(not(feature = "ssr"))]
+fn focus_element(id: &str) {
+    if let Some(element) = html_element(id) {
+        let _ = element.focus();
+    }
+}
+
+#[cfg(not(feature = "ssr"))]
+fn blur_active_element() {
+    use wasm_bindgen::JsCast;
+
+    if let Some(active) = document()
+        .active_element()
+        .and_then(|active| active.dyn_into::<web_sys::HtmlElement>().ok())
+    {
+        let _ = active.blur();
+    }
+}
+
+// The keys of a shortcut, each in its own <kbd>
+fn shortcut_keys(cx: Scope, shortcut: Shortcut) -> impl IntoView {
+    shortcut
+        .keys()
+        .split(' ')
+        .enumerate()
+        .map(|(index, key)| {
+            view! { cx,
+                {(index > 0).then_some(" ")}
+                <kbd>{key}</kbd>
+            }
+        })
+        .collect_view(cx)
+}
+
+// The list of shortcuts, with the switch for turning them off
+#[component]
+fn ShortcutsHelp(cx: Scope, shortcuts: ShortcutsContext) -> impl IntoView {
+    let close = move |_| shortcuts.help_open.set(false);
+
+    move || {
+        shortcuts.help_open.get().then(|| {
+            view! { cx,
+                <div
+                    class="modal d-block"
+                    id=HELP_DIALOG_ID
+                    tabindex="-1"
+                    role="dialog"
+                    aria-modal="true"
+                    aria-labelledby="shortcuts-help-title"
+                >
+                    <div class="modal-dialog modal-dialog-scrollable">
+                        <div class="modal-content">
+                            <div class="modal-header">
+                                <h2 class="modal-title fs-5" id="shortcuts-help-title">
+                                    {t(cx, "shortcuts-title")}
+                                </h2>
+                                <button
+                                    type="button"
+                                    class="btn-close"
+                                    aria-label=t(cx, "close")
+                                    on:click=close
+                                ></button>
+                            </div>
+                            <div class="modal-body">
+                                <div class="form-check form-switch mb-2">
+                                    <input
+                                        class="form-check-input"
+                                        type="checkbox"
+                                        role="switch"
+                                        id="shortcuts-enabled"
+                                        prop:checked=move || shortcuts.enabled.get()
+                                        on:change=move |ev| shortcuts.set_enabled(event_target_checked(&ev))
+                                    />
+                                    <label class="form-check-label" for="shortcuts-enabled">
+                                        {t(cx, "shortcuts-enabled")}
+                                    </label>
+                                </div>
+                                <p class="small text-body-secondary">{t(cx, "shortcuts-typing")}</p>
+                                <table class="table table-sm mb-0">
+                                    <tbody>
+                                        {Shortcut::ALL
+                                            .into_iter()
+                                            .map(|shortcut| {
+                                                view! { cx,
+                                                    <tr>
+                                                        <td class="text-nowrap">{shortcut_keys(cx, shortcut)}</td>
+                                                        <td>{t(cx, shortcut.message_id())}</td>
+                                                    </tr>
+                                                }
+                                            })
+                                            .collect_view(cx)}
+                                    </tbody>
+                                </table>
+                            </div>
+                        </div>
+                    </div>
+                </div>
+                <div class="modal-backdrop show" on:click=close></div>
+            }
+        })
+    }
+}
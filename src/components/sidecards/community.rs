@@ -1,50 +1,21 @@
 use leptos::*;
-use leptos_router::*;
-use markdown::*;
 
-use crate::api::community::get_community;
 use crate::api::structs::*;
-use crate::api::*;
-use crate::components::instance::*;
+use crate::components::admin::CommunityAdminTools;
+use crate::components::custom_emoji::use_custom_emojis;
+use crate::components::i18n::{t, translate};
+use crate::components::markdown::render_markdown;
+use crate::components::site::use_all_languages;
+use crate::image_proxy::{proxied_image, proxy_image_sources};
 
+// The community's description, moderators, languages, and statistics.
+// The community is fetched by the page, which shares it with the header.
 #[component]
-pub fn Sidecard(cx: Scope) -> impl IntoView {
-    let query = use_query_map(cx);
-    let page = move || {
-        query
-            .with(|q| q.get("page").and_then(|page| page.parse::<i32>().ok()))
-            .unwrap_or(1)
-    };
-
-    let params = use_params_map(cx);
-    let community_name = move || {
-        params
-            .with(|params| params.get("community_name").cloned())
-            .unwrap_or_default()
-            .parse::<String>()
-            .unwrap()
-    };
-
-    let sidebar = create_resource(cx, page, move |page| async move {
-        // This constructs the proper API URL for GetPosts or GetPersonDetails
-        let url_constructor = ApiUrlConstructor {
-            endpoint: api_endpoints::GetEndpoint::GET_COMMUNITY.to_string(),
-            id: None,
-            params: None,
-        };
-
-        let get_form = GetCommunity {
-            auth: None,
-            id: None,
-            name: Some(community_name()),
-        };
-
-        get_community(cx, &api_url_builder(cx, url_constructor, get_form))
-            .await
-            .ok()
-    });
-
-    let err_msg = "Error loading this post: ";
+pub fn Sidecard(
+    cx: Scope,
+    sidebar: Resource<String, Option<GetCommunityResponse>>,
+) -> impl IntoView {
+    let err_msg = t(cx, "error-loading-post");
 
     view! { cx,
         <div class="text-left">
@@ -53,7 +24,7 @@ pub fn Sidecard(cx: Scope) -> impl IntoView {
                 view! { cx,
                     <div class="d-flex align-items-center">
                         <h1>
-                            Loading...
+                            {t(cx, "loading")}
                         </h1>
                         <div class="spinner-grow ms-auto" role="status" aria-hidden="true"></div>
                     </div>
@@ -64,64 +35,47 @@ pub fn Sidecard(cx: Scope) -> impl IntoView {
                         .read(cx)
                         .map(|res| match res {
                             None => {
-                                view! { cx, <div>{format!("{err_msg}")}</div> }
+                                view! { cx, <div>{err_msg}</div> }
                             }
                             Some(res) => {
+                                let community = res.community_view.community.clone();
+                                let custom_emojis = use_custom_emojis(cx);
                                 let sidebar = match res.community_view.community.description.clone()
                                 {
                                     Some(text) => {
-                                        markdown::to_html_with_options(
-                                                text.as_str(),
-                                                &Options::gfm(),
-                                            )
-                                            .unwrap()
+                                        proxy_image_sources(&render_markdown(text.as_str(), &custom_emojis), Some(1024))
                                     }
                                     None => "".to_string(),
                                 };
-                                let icon_image = match res.community_view.community.icon {
-                                    Some(_) => res.community_view.community.icon,
-                                    _ => {
-                                        Option::Some(
-                                            "/static/default_assets/default-community.png".to_string(),
-                                        )
-                                    }
-                                };
-                                let banner_image = match res.community_view.community.banner {
-                                    Some(_) => res.community_view.community.banner,
-                                    _ => {
-                                        Option::Some(
-                                            "/static/default_assets/banners/wolfrs-default-community-banner.png"
-                                                .to_string(),
-                                        )
-                                    }
+                                // Lemmy treats an empty list as every language being allowed
+                                let all_languages = use_all_languages(cx);
+                                let languages = res
+                                    .discussion_languages
+                                    .iter()
+                                    .filter_map(|id| all_languages.iter().find(|language| language.id == *id))
+                                    .map(|language| language.name.clone())
+                                    .collect::<Vec<_>>();
+                                let languages = if languages.is_empty() {
+                                    translate(cx, "sidecard-any-language")
+                                } else {
+                                    languages.join(", ")
                                 };
+                                let counts = res.community_view.counts.clone();
 
                                 view! { cx,
                                     <div>
                                         // Community sidecard
                                         <div class="card text-left">
                                             <div class="card-header">
-                                                <img
-                                                    src=banner_image
-                                                    class="card-img-top"
-                                                    alt="Community banner"
-                                                />
+                                                <h5 class="card-title text-center">{t(cx, "sidecard-about")}</h5>
                                             </div>
                                             <div class="card-body">
-                                                <img
-                                                    src=icon_image
-                                                    class="img-fluid rounded"
-                                                    alt="Icon for the community"
-                                                />
-                                                <br/>
-                                                <br/>
-                                                <h4 class="card-title text-center">
-                                                    {res.community_view.community.title}
-                                                </h4>
-                                                <hr/>
                                                 <div class="markdown" inner_html=sidebar></div>
                                                 <hr/>
-                                                <h6>"Moderators"</h6>
+                                                <h6>{t(cx, "sidecard-languages")}</h6>
+                                                <p class="small text-body-secondary">{languages}</p>
+                                                <hr/>
+                                                <h6>{t(cx, "sidecard-moderators")}</h6>
                                                 <ul class="list-group list-group-flush">
                                                     {res
                                                         .moderators
@@ -170,8 +124,8 @@ pub fn Sidecard(cx: Scope) -> impl IntoView {
                                                                         <li class="list-group-item">
                                                                             <a href=format!("{}", moderator_link)>
                                                                                 <img
-                                                                                    src=admin_avatar
-                                                                                    alt="mdo"
+                                                                                    src=proxied_image(admin_avatar, Some(64))
+                                                                                    alt=""
                                                                                     width="32"
                                                                                     height="32"
                                                                                     class="rounded"
@@ -186,67 +140,54 @@ pub fn Sidecard(cx: Scope) -> impl IntoView {
                                                         })
                                                         .collect::<Vec<_>>()}
                                                 </ul>
+                                                <CommunityAdminTools community=community/>
                                             </div>
                                         </div>
                                         <br/>
                                         // Community Statistics Card
                                         <div class="card text-left">
                                             <div class="card-header">
-                                                <h5 class="card-title text-center">"Statistics"</h5>
+                                                <h5 class="card-title text-center">{t(cx, "sidecard-statistics")}</h5>
                                             </div>
                                             <div class="card-body">
-                                                <table class="table table-dark">
-                                                    <thead>
+                                                <table class="table table-sm mb-0">
+                                                    <tbody>
+                                                        <tr>
+                                                            <th scope="row">{t(cx, "sidecard-subscribers")}</th>
+                                                            <td class="text-end">{counts.subscribers}</td>
+                                                        </tr>
+                                                        <tr>
+                                                            <th scope="row">{t(cx, "sidecard-posts")}</th>
+                                                            <td class="text-end">{counts.posts}</td>
+                                                        </tr>
                                                         <tr>
-                                                            <th scope="col">"Users"</th>
-                                                            <th scope="col">
-                                                                <div class="vr"></div>
-                                                            </th>
-                                                            <th scope="col">"Activity"</th>
+                                                            <th scope="row">{t(cx, "sidecard-comments")}</th>
+                                                            <td class="text-end">{counts.comments}</td>
                                                         </tr>
-                                                    </thead>
+                                                        <tr>
+                                                            <th scope="row">{t(cx, "sidecard-moderators")}</th>
+                                                            <td class="text-end">{res.moderators.len()}</td>
+                                                        </tr>
+                                                    </tbody>
+                                                </table>
+                                                <h6 class="mt-3">{t(cx, "sidecard-active-users")}</h6>
+                                                <table class="table table-sm mb-0">
                                                     <tbody>
                                                         <tr>
-                                                            <td>
-                                                                {format!("{} Users", res.community_view.counts.subscribers)}
-                                                            </td>
-                                                            <td>
-                                                                <div class="vr"></div>
-                                                            </td>
-                                                            <td>
-                                                                {format!("{} Posts", res.community_view.counts.posts)}
-                                                            </td>
+                                                            <th scope="row">{t(cx, "sidecard-day")}</th>
+                                                            <td class="text-end">{counts.users_active_day}</td>
                                                         </tr>
                                                         <tr>
-                                                            <td>
-                                                                {format!(
-                                                                    "{} / Day", res.community_view.counts.users_active_day
-                                                                )}
-
-                                                            </td>
-                                                            <td>
-                                                                <div class="vr"></div>
-                                                            </td>
-                                                            <td>
-                                                                {format!("{} Comments", res.community_view.counts.comments)}
-                                                            </td>
+                                                            <th scope="row">{t(cx, "sidecard-week")}</th>
+                                                            <td class="text-end">{counts.users_active_week}</td>
                                                         </tr>
                                                         <tr>
-                                                            <td>
-                                                                {format!(
-                                                                    "{} / Month", res.community_view.counts.users_active_month
-                                                                )}
-
-                                                            </td>
-                                                            <td>
-                                                                <div class="vr"></div>
-                                                            </td>
-                                                            <td>
-                                                                // This should eventually become the rank of the community on the Instance
-                                                                // This will be for another time...
-                                                                {format!("{:?} Moderators", res.moderators.clone().len())}
-
-                                                            </td>
+                                                            <th scope="row">{t(cx, "sidecard-month")}</th>
+                                                            <td class="text-end">{counts.users_active_month}</td>
+                                                        </tr>
+                                                        <tr>
+                                                            <th scope="row">{t(cx, "sidecard-half-year")}</th>
+                                                            <td class="text-end">{counts.users_active_half_year}</td>
                                                         </tr>
                                                     </tbody>
                                                 </table>
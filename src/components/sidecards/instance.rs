@@ -1,38 +1,19 @@
 use leptos::*;
-use leptos_router::*;
-use markdown::*;
 
-use crate::api::site::get_site;
-use crate::api::structs::*;
-use crate::api::*;
+use crate::components::i18n::{t, t_count};
 use crate::components::instance::*;
 use crate::components::sidecards::trending::TrendingCommunities;
+use crate::components::custom_emoji::use_custom_emojis;
+use crate::components::markdown::render_markdown;
+use crate::components::site::use_site_resource;
+use crate::image_proxy::{proxied_image, proxy_image_sources};
 
 #[component]
 pub fn Sidecard(cx: Scope) -> impl IntoView {
-    let query = use_query_map(cx);
-    let page = move || {
-        query
-            .with(|q| q.get("page").and_then(|page| page.parse::<i32>().ok()))
-            .unwrap_or(1)
-    };
+    // The site is fetched once by the App and shared, rather than fetched again for the sidecard
+    let sidebar = use_site_resource(cx);
 
-    let sidebar = create_resource(cx, page, move |page| async move {
-        // This constructs the proper API URL for GetPosts or GetPersonDetails
-        let url_constructor = ApiUrlConstructor {
-            endpoint: api_endpoints::GetEndpoint::GET_SITE.to_string(),
-            id: None,
-            params: None,
-        };
-
-        let get_form = GetSite { auth: None };
-
-        get_site(cx, &api_url_builder(cx, url_constructor, get_form))
-            .await
-            .ok()
-    });
-
-    let err_msg = "Error loading this post: ";
+    let err_msg = t(cx, "error-loading-post");
 
     view! { cx,
         <div>
@@ -41,7 +22,7 @@ pub fn Sidecard(cx: Scope) -> impl IntoView {
                 view! { cx,
                     <div class="d-flex align-items-center">
                         <h1>
-                            Loading...
+                            {t(cx, "loading")}
                         </h1>
                         <div class="spinner-grow ms-auto" role="status" aria-hidden="true"></div>
                     </div>
@@ -52,16 +33,13 @@ pub fn Sidecard(cx: Scope) -> impl IntoView {
                         .read(cx)
                         .map(|res| match res {
                             None => {
-                                view! { cx, <div>{format!("{err_msg}")}</div> }
+                                view! { cx, <div>{err_msg}</div> }
                             }
                             Some(res) => {
+                                let custom_emojis = use_custom_emojis(cx);
                                 let sidebar = match res.site_view.site.sidebar.clone() {
                                     Some(text) => {
-                                        markdown::to_html_with_options(
-                                                text.as_str(),
-                                                &Options::gfm(),
-                                            )
-                                            .unwrap()
+                                        proxy_image_sources(&render_markdown(text.as_str(), &custom_emojis), Some(1024))
                                     }
                                     None => "".to_string(),
                                 };
@@ -89,16 +67,16 @@ pub fn Sidecard(cx: Scope) -> impl IntoView {
                                         <div class="card text-left">
                                             <div class="card-header">
                                                 <img
-                                                    src=banner_image
+                                                    src=proxied_image(banner_image, Some(1024))
                                                     class="card-img-top"
-                                                    alt="Community banner"
+                                                    alt=""
                                                 />
                                             </div>
                                             <div class="card-body">
                                                 <img
-                                                    src=icon_image
+                                                    src=proxied_image(icon_image, Some(512))
                                                     class="img-fluid rounded"
-                                                    alt="Icon for the community"
+                                                    alt=""
                                                 />
                                                 <br/>
                                                 <br/>
@@ -111,7 +89,7 @@ pub fn Sidecard(cx: Scope) -> impl IntoView {
                                                 <hr/>
                                                 <div class="markdown" inner_html=sidebar></div>
                                                 <hr/>
-                                                <h6>"Admins"</h6>
+                                                <h6>{t(cx, "sidecard-admins")}</h6>
                                                 <ul class="list-group list-group-flush">
                                                     {res
                                                         .admins
@@ -134,8 +112,8 @@ pub fn Sidecard(cx: Scope) -> impl IntoView {
                                                                         <li class="list-group-item">
                                                                             <a href=format!("/user/{}", admin.person.name)>
                                                                                 <img
-                                                                                    src=admin_avatar
-                                                                                    alt="mdo"
+                                                                                    src=proxied_image(admin_avatar, Some(64))
+                                                                                    alt=""
                                                                                     width="32"
                                                                                     height="32"
                                                                                     class="rounded"
@@ -158,55 +136,49 @@ pub fn Sidecard(cx: Scope) -> impl IntoView {
                                         // Statistics Card
                                         <div class="card text-left">
                                             <div class="card-header">
-                                                <h5 class="card-title text-center">"Statistics"</h5>
+                                                <h5 class="card-title text-center">{t(cx, "sidecard-statistics")}</h5>
                                             </div>
                                             <div class="card-body">
                                                 <table class="table table-dark">
                                                     <thead>
                                                         <tr>
-                                                            <th scope="col">"Users"</th>
+                                                            <th scope="col">{t(cx, "sidecard-users")}</th>
                                                             <th scope="col">
                                                                 <div class="vr"></div>
                                                             </th>
-                                                            <th scope="col">"Activity"</th>
+                                                            <th scope="col">{t(cx, "sidecard-activity")}</th>
                                                         </tr>
                                                     </thead>
                                                     <tbody>
                                                         <tr>
-                                                            <td>{format!("{} Users", res.site_view.counts.users)}</td>
+                                                            <td>{t_count(cx, "sidecard-users-count", res.site_view.counts.users.into())}</td>
                                                             <td>
                                                                 <div class="vr"></div>
                                                             </td>
                                                             <td>
-                                                                {format!(
-                                                                    "{} Communities", res.site_view.counts.communities
-                                                                )}
+                                                                {t_count(cx, "sidecard-communities-count", res.site_view.counts.communities.into())}
 
                                                             </td>
                                                         </tr>
                                                         <tr>
                                                             <td>
-                                                                {format!("{} / Day", res.site_view.counts.users_active_day)}
+                                                                {t_count(cx, "sidecard-per-day", res.site_view.counts.users_active_day.into())}
                                                             </td>
                                                             <td>
                                                                 <div class="vr"></div>
                                                             </td>
-                                                            <td>{format!("{} Posts", res.site_view.counts.posts)}</td>
+                                                            <td>{t_count(cx, "sidecard-posts-count", res.site_view.counts.posts.into())}</td>
                                                         </tr>
                                                         <tr>
                                                             <td>
-                                                                {format!(
-                                                                    "{} / Month", res.site_view.counts.users_active_month
-                                                                )}
+                                                                {t_count(cx, "sidecard-per-month", res.site_view.counts.users_active_month.into())}
 
                                                             </td>
                                                             <td>
                                                                 <div class="vr"></div>
                                                             </td>
                                                             <td>
-                                                                {format!(
-                                                                    "{:?} Comments", res.site_view.counts.comments.unwrap()
-                                                                )}
+                                                                {t_count(cx, "comments-count", res.site_view.counts.comments.unwrap_or_default().into())}
 
                                                             </td>
                                                         </tr>
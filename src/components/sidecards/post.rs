@@ -1,11 +1,15 @@
 use leptos::*;
-use markdown::*;
 
 use crate::api::structs::*;
+use crate::components::admin::{CommunityAdminTools, PostAdminTools};
+use crate::components::custom_emoji::use_custom_emojis;
+use crate::components::i18n::{t, t_count};
+use crate::components::markdown::render_markdown;
+use crate::image_proxy::{proxied_image, proxy_image_sources};
 
 #[component]
 pub fn Sidecard(cx: Scope, sidebar: Resource<i32, Option<GetPostResponse>>) -> impl IntoView {
-    let err_msg = "Error loading sidecard: ";
+    let err_msg = t(cx, "error-loading-sidecard");
 
     view! { cx,
         <div class="text-left">
@@ -14,7 +18,7 @@ pub fn Sidecard(cx: Scope, sidebar: Resource<i32, Option<GetPostResponse>>) -> i
                 view! { cx,
                     <div class="d-flex align-items-center">
                         <h1>
-                            Loading...
+                            {t(cx, "loading")}
                         </h1>
                         <div class="spinner-grow ms-auto" role="status" aria-hidden="true"></div>
                     </div>
@@ -25,17 +29,16 @@ pub fn Sidecard(cx: Scope, sidebar: Resource<i32, Option<GetPostResponse>>) -> i
                         .read(cx)
                         .map(|res| match res {
                             None => {
-                                view! { cx, <div>{format!("{err_msg}")}</div> }
+                                view! { cx, <div>{err_msg}</div> }
                             }
                             Some(res) => {
+                                let community = res.community_view.community.clone();
+                                let post_id = res.post_view.post.id;
+                                let custom_emojis = use_custom_emojis(cx);
                                 let sidebar = match res.community_view.community.description.clone()
                                 {
                                     Some(text) => {
-                                        markdown::to_html_with_options(
-                                                text.as_str(),
-                                                &Options::gfm(),
-                                            )
-                                            .unwrap()
+                                        proxy_image_sources(&render_markdown(text.as_str(), &custom_emojis), Some(1024))
                                     }
                                     None => "".to_string(),
                                 };
@@ -63,16 +66,16 @@ pub fn Sidecard(cx: Scope, sidebar: Resource<i32, Option<GetPostResponse>>) -> i
                                         <div class="card text-left">
                                             <div class="card-header">
                                                 <img
-                                                    src=banner_image
+                                                    src=proxied_image(banner_image, Some(1024))
                                                     class="card-img-top"
-                                                    alt="Community banner"
+                                                    alt=""
                                                 />
                                             </div>
                                             <div class="card-body">
                                                 <img
-                                                    src=icon_image
+                                                    src=proxied_image(icon_image, Some(512))
                                                     class="img-fluid rounded"
-                                                    alt="Icon for the community"
+                                                    alt=""
                                                 />
                                                 <br/>
                                                 <br/>
@@ -82,7 +85,7 @@ pub fn Sidecard(cx: Scope, sidebar: Resource<i32, Option<GetPostResponse>>) -> i
                                                 <hr/>
                                                 <div class="markdown" inner_html=sidebar></div>
                                                 <hr/>
-                                                <h6>"Moderators"</h6>
+                                                <h6>{t(cx, "sidecard-moderators")}</h6>
                                                 <ul class="list-group list-group-flush">
                                                     {res
                                                         .moderators
@@ -131,8 +134,8 @@ pub fn Sidecard(cx: Scope, sidebar: Resource<i32, Option<GetPostResponse>>) -> i
                                                                         <li class="list-group-item">
                                                                             <a href=format!("{}", moderator_link)>
                                                                                 <img
-                                                                                    src=admin_avatar
-                                                                                    alt="mdo"
+                                                                                    src=proxied_image(admin_avatar, Some(64))
+                                                                                    alt=""
                                                                                     width="32"
                                                                                     height="32"
                                                                                     class="rounded"
@@ -147,56 +150,54 @@ pub fn Sidecard(cx: Scope, sidebar: Resource<i32, Option<GetPostResponse>>) -> i
                                                         })
                                                         .collect::<Vec<_>>()}
                                                 </ul>
+                                                <CommunityAdminTools community=community/>
+                                                <PostAdminTools post_id=post_id/>
                                             </div>
                                         </div>
                                         <br/>
                                         // Community Statistics Card
                                         <div class="card text-left">
                                             <div class="card-header">
-                                                <h5 class="card-title text-center">"Statistics"</h5>
+                                                <h5 class="card-title text-center">{t(cx, "sidecard-statistics")}</h5>
                                             </div>
                                             <div class="card-body">
                                                 <table class="table table-dark">
                                                     <thead>
                                                         <tr>
-                                                            <th scope="col">"Users"</th>
+                                                            <th scope="col">{t(cx, "sidecard-users")}</th>
                                                             <th scope="col">
                                                                 <div class="vr"></div>
                                                             </th>
-                                                            <th scope="col">"Activity"</th>
+                                                            <th scope="col">{t(cx, "sidecard-activity")}</th>
                                                         </tr>
                                                     </thead>
                                                     <tbody>
                                                         <tr>
                                                             <td>
-                                                                {format!("{} Users", res.community_view.counts.subscribers)}
+                                                                {t_count(cx, "sidecard-users-count", res.community_view.counts.subscribers.into())}
                                                             </td>
                                                             <td>
                                                                 <div class="vr"></div>
                                                             </td>
                                                             <td>
-                                                                {format!("{} Posts", res.community_view.counts.posts)}
+                                                                {t_count(cx, "sidecard-posts-count", res.community_view.counts.posts.into())}
                                                             </td>
                                                         </tr>
                                                         <tr>
                                                             <td>
-                                                                {format!(
-                                                                    "{} / Day", res.community_view.counts.users_active_day
-                                                                )}
+                                                                {t_count(cx, "sidecard-per-day", res.community_view.counts.users_active_day.into())}
 
                                                             </td>
                                                             <td>
                                                                 <div class="vr"></div>
                                                             </td>
                                                             <td>
-                                                                {format!("{} Comments", res.community_view.counts.comments)}
+                                                                {t_count(cx, "comments-count", res.community_view.counts.comments.into())}
                                                             </td>
                                                         </tr>
                                                         <tr>
                                                             <td>
-                                                                {format!(
-                                                                    "{} / Month", res.community_view.counts.users_active_month
-                                                                )}
+                                                                {t_count(cx, "sidecard-per-month", res.community_view.counts.users_active_month.into())}
 
                                                             </td>
                                                             <td>
@@ -205,7 +206,7 @@ pub fn Sidecard(cx: Scope, sidebar: Resource<i32, Option<GetPostResponse>>) -> i
                                                             <td>
                                                                 // This should eventually become the rank of the community on the Instance
                                                                 // This will be for another time...
-                                                                {format!("{:?} Moderators", res.moderators.clone().len())}
+                                                                {t_count(cx, "sidecard-moderators-count", res.moderators.clone().len() as i64)}
 
                                                             </td>
                                                         </tr>
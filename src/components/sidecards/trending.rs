@@ -8,7 +8,9 @@ use crate::api::site::get_site;
 use crate::api::structs::*;
 use crate::api::user::get_person_details;
 use crate::api::*;
+use crate::components::i18n::t;
 use crate::components::instance::*;
+use crate::image_proxy::proxied_image;
 
 #[component]
 pub fn TrendingCommunities(cx: Scope) -> impl IntoView {
@@ -44,13 +46,13 @@ pub fn TrendingCommunities(cx: Scope) -> impl IntoView {
             .ok()
     });
 
-    let err_msg = "Error loading this post: ";
+    let err_msg = t(cx, "error-loading-post");
 
     view! { cx,
         <div>
             <div class="card text-left">
                 <div class="card-header">
-                    <h5 class="card-title text-center">"Trending Communities"</h5>
+                    <h5 class="card-title text-center">{t(cx, "sidecard-trending-communities")}</h5>
                 </div>
                 <div class="card-body">
                     <Transition fallback=move || {
@@ -58,7 +60,7 @@ pub fn TrendingCommunities(cx: Scope) -> impl IntoView {
                         view! { cx,
                             <div class="d-flex align-items-center">
                                 <h1>
-                                    Loading...
+                                    {t(cx, "loading")}
                                 </h1>
                                 <div
                                     class="spinner-grow ms-auto"
@@ -73,7 +75,7 @@ pub fn TrendingCommunities(cx: Scope) -> impl IntoView {
                                 .read(cx)
                                 .map(|res| match res {
                                     None => {
-                                        view! { cx, <div>{format!("{err_msg}")}</div> }
+                                        view! { cx, <div>{err_msg}</div> }
                                     }
                                     Some(res) => {
 
@@ -121,8 +123,8 @@ fn TrendingCommunityItems(cx: Scope, communities: Vec<CommunityView>) -> impl In
                                         {format!("{:?}. ", ranking)}
                                         <a href=format!("/community/{}", item.community.name)>
                                             <img
-                                                src=community_avatar
-                                                alt="mdo"
+                                                src=proxied_image(community_avatar, Some(64))
+                                                alt=""
                                                 width="32"
                                                 height="32"
                                                 class="rounded"
@@ -1,15 +1,20 @@
 use leptos::*;
-use markdown::*;
 
 use crate::api::structs::*;
+use crate::components::admin::PersonAdminTools;
+use crate::components::i18n::{t, t_count};
 use crate::components::instance::*;
+use crate::components::custom_emoji::use_custom_emojis;
+use crate::components::markdown::render_markdown;
+use crate::components::time_ago::TimeAgo;
+use crate::image_proxy::{proxied_image, proxy_image_sources};
 
 #[component]
 pub fn Sidecard(
     cx: Scope,
     sidebar: Resource<i32, Option<GetPersonDetailsResponse>>,
 ) -> impl IntoView {
-    let err_msg = "Error loading this post: ";
+    let err_msg = t(cx, "error-loading-post");
 
     view! { cx,
         <div class="text-left">
@@ -18,7 +23,7 @@ pub fn Sidecard(
                 view! { cx,
                     <div class="d-flex align-items-center">
                         <h1>
-                            Loading...
+                            {t(cx, "loading")}
                         </h1>
                         <div class="spinner-grow ms-auto" role="status" aria-hidden="true"></div>
                     </div>
@@ -29,16 +34,15 @@ pub fn Sidecard(
                         .read(cx)
                         .map(|res| match res {
                             None => {
-                                view! { cx, <div>{format!("{err_msg}")}</div> }
+                                view! { cx, <div>{err_msg}</div> }
                             }
                             Some(res) => {
+                                let person = res.person_view.person.clone();
+                                let joined = res.person_view.person.published.clone();
+                                let custom_emojis = use_custom_emojis(cx);
                                 let sidebar = match res.person_view.person.bio.clone() {
                                     Some(text) => {
-                                        markdown::to_html_with_options(
-                                                text.as_str(),
-                                                &Options::gfm(),
-                                            )
-                                            .unwrap()
+                                        proxy_image_sources(&render_markdown(text.as_str(), &custom_emojis), Some(1024))
                                     }
                                     None => "".to_string(),
                                 };
@@ -66,16 +70,16 @@ pub fn Sidecard(
                                         <div class="card text-left">
                                             <div class="card-header">
                                                 <img
-                                                    src=banner_image
+                                                    src=proxied_image(banner_image, Some(1024))
                                                     class="card-img-top"
-                                                    alt="Community banner"
+                                                    alt=""
                                                 />
                                             </div>
                                             <div class="card-body">
                                                 <img
-                                                    src=icon_image
+                                                    src=proxied_image(icon_image, Some(512))
                                                     class="img-fluid rounded"
-                                                    alt="Icon for the community"
+                                                    alt=""
                                                 />
                                                 <br/>
                                                 <br/>
@@ -94,6 +98,11 @@ pub fn Sidecard(
                                                         </h4>
                                                     }
                                                 }}
+                                                <p class="text-center small text-body-secondary">
+                                                    <i aria-hidden="true" class="bi bi-calendar3"></i>
+                                                    " "
+                                                    <TimeAgo time=joined label="time-joined"/>
+                                                </p>
 
                                                 <hr/>
                                                 <div class="markdown" inner_html=sidebar></div>
@@ -101,7 +110,7 @@ pub fn Sidecard(
                                                 {if !res.moderates.is_empty() {
                                                     view! { cx,
                                                         <div>
-                                                            <h6>"Moderates"</h6>
+                                                            <h6>{t(cx, "sidecard-moderates")}</h6>
                                                             <ul class="list-group list-group-flush">
                                                                 {res
                                                                     .moderates
@@ -150,8 +159,8 @@ pub fn Sidecard(
                                                                                     <li class="list-group-item">
                                                                                         <a href=format!("{}", moderator_link)>
                                                                                             <img
-                                                                                                src=community_avatar
-                                                                                                alt="mdo"
+                                                                                                src=proxied_image(community_avatar, Some(64))
+                                                                                                alt=""
                                                                                                 width="32"
                                                                                                 height="32"
                                                                                                 class="rounded"
@@ -204,49 +213,48 @@ pub fn Sidecard(
                                                     view! { cx, <div></div> }
                                                 }}
 
+                                                <PersonAdminTools person=person/>
                                             </div>
                                         </div>
                                         <br/>
                                         // User Statistics
                                         <div class="card text-left">
                                             <div class="card-header">
-                                                <h5 class="card-title text-center">"Statistics"</h5>
+                                                <h5 class="card-title text-center">{t(cx, "sidecard-statistics")}</h5>
                                             </div>
                                             <div class="card-body">
                                                 <table class="table table-dark">
                                                     <thead>
                                                         <tr>
-                                                            <th scope="col">"Activity"</th>
+                                                            <th scope="col">{t(cx, "sidecard-activity")}</th>
                                                             <th scope="col">
                                                                 <div class="vr"></div>
                                                             </th>
-                                                            <th scope="col">"Karma"</th>
+                                                            <th scope="col">{t(cx, "sidecard-karma")}</th>
                                                         </tr>
                                                     </thead>
                                                     <tbody>
                                                         <tr>
                                                             <td>
-                                                                {format!("{} Posts", res.person_view.counts.post_count)}
+                                                                {t_count(cx, "sidecard-posts-count", res.person_view.counts.post_count.into())}
                                                             </td>
                                                             <td>
                                                                 <div class="vr"></div>
                                                             </td>
                                                             <td>
-                                                                {format!("{} Karma", res.person_view.counts.post_score)}
+                                                                {t_count(cx, "sidecard-karma-count", res.person_view.counts.post_score.into())}
                                                             </td>
                                                         </tr>
                                                         <tr>
                                                             <td>
-                                                                {format!(
-                                                                    "{} Comments", res.person_view.counts.comment_count
-                                                                )}
+                                                                {t_count(cx, "comments-count", res.person_view.counts.comment_count.into())}
 
                                                             </td>
                                                             <td>
                                                                 <div class="vr"></div>
                                                             </td>
                                                             <td>
-                                                                {format!("{} Karma", res.person_view.counts.comment_score)}
+                                                                {t_count(cx, "sidecard-karma-count", res.person_view.counts.comment_score.into())}
                                                             </td>
                                                         </tr>
                                                     </tbody>
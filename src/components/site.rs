@@ -0,0 +1,83 @@
+use leptos::*;
+
+use crate::api::site::get_site;
+use crate::api::structs::*;
+use crate::api::*;
+
+// TODO - site.rs:
+// Call refetch_site from the login and logout pages once they exist
+
+// The response from GetSite, which is provided as context by the App so nothing else has to fetch the site.
+// It is loaded once for each request on the server and once for each session in the browser, with the user's auth token so my_user is filled in for logged in users.
+#[derive(Copy, Clone)]
+pub struct SiteContext(pub Resource<(), Option<GetSiteResponse>>);
+
+// Fetches the site from GetSite and provides it as context for the rest of the app
+pub fn provide_site(cx: Scope) {
+    let site = create_resource(
+        cx,
+        || (),
+        move |_| async move {
+            // This constructs the proper API URL for GetSite
+            let url_constructor = ApiUrlConstructor {
+                endpoint: api_endpoints::GetEndpoint::GET_SITE.to_string(),
+                id: None,
+                params: None,
+            };
+
+            let get_form = GetSite {
+                auth: get_auth_token(cx),
+            };
+
+            get_site(cx, &api_url_builder(cx, url_constructor, get_form))
+                .await
+                .ok()
+        },
+    );
+
+    provide_context(cx, SiteContext(site));
+}
+
+// The site resource itself, for components that show a loading or error message while waiting on it
+pub fn use_site_resource(cx: Scope) -> Resource<(), Option<GetSiteResponse>> {
+    use_context::<SiteContext>(cx)
+        .expect("provide_site should be called by the App")
+        .0
+}
+
+// Runs f with the site once it has loaded, or returns None while it is loading or if it couldn't be fetched.
+// Reading the site inside a Transition or Suspense makes it wait for the site to load.
+pub fn with_site<T>(cx: Scope, f: impl FnOnce(&GetSiteResponse) -> T) -> Option<T> {
+    use_context::<SiteContext>(cx)?
+        .0
+        .with(cx, |site| site.as_ref().map(f))
+        .flatten()
+}
+
+pub fn use_site_view(cx: Scope) -> Option<SiteView> {
+    with_site(cx, |site| site.site_view.clone())
+}
+
+pub fn use_admins(cx: Scope) -> Vec<PersonView> {
+    with_site(cx, |site| site.admins.clone()).unwrap_or_default()
+}
+
+// The logged in user's details and settings, which is None for anonymous users
+pub fn use_my_user(cx: Scope) -> Option<MyUserInfo> {
+    with_site(cx, |site| site.my_user.clone()).flatten()
+}
+
+pub fn use_all_languages(cx: Scope) -> Vec<Language> {
+    with_site(cx, |site| site.all_languages.clone()).unwrap_or_default()
+}
+
+pub fn use_taglines(cx: Scope) -> Vec<Tagline> {
+    with_site(cx, |site| site.taglines.clone()).unwrap_or_default()
+}
+
+// Fetches the site again, which should be done whenever the user logs in or out, or an admin changes something that is part of it
+pub fn refetch_site(cx: Scope) {
+    if let Some(site) = use_context::<SiteContext>(cx) {
+        site.0.refetch();
+    }
+}
@@ -0,0 +1,68 @@
+use leptos::*;
+
+use crate::api::community::follow_community;
+use crate::api::structs::*;
+use crate::api::*;
+use crate::components::i18n::translate;
+
+// TODO - subscribe_button.rs:
+// Link to the login page instead of disabling the button once it exists
+
+// The button for subscribing to or unsubscribing from a community.
+// It shows the subscription Lemmy returns after each click, which is Pending while a remote instance hasn't accepted it yet.
+#[component]
+pub fn SubscribeButton(
+    cx: Scope,
+    community_id: i32,
+    subscribed: SubscribedType,
+    // A smaller button for tables and lists
+    #[prop(optional)]
+    small: bool,
+) -> impl IntoView {
+    let subscribed = create_rw_signal(cx, subscribed);
+    let logged_in = get_auth_token(cx).is_some();
+
+    let follow_action = create_action(cx, move |follow: &bool| {
+        let follow = *follow;
+        async move {
+            let url_constructor = ApiUrlConstructor {
+                endpoint: api_endpoints::PostEndpoint::FOLLOW_COMMUNITY.to_string(),
+                id: None,
+                params: None,
+            };
+
+            let form = FollowCommunity {
+                auth: get_auth_token(cx).unwrap_or_default(),
+                community_id,
+                follow,
+            };
+
+            if let Ok(res) = follow_community(cx, &api_post_url_builder(url_constructor), &form).await {
+                subscribed.set(res.community_view.subscribed);
+            }
+        }
+    });
+
+    let label = move || match subscribed.get() {
+        SubscribedType::Subscribed => translate(cx, "subscribe-unsubscribe"),
+        SubscribedType::Pending => translate(cx, "subscribe-pending"),
+        SubscribedType::NotSubscribed => translate(cx, "subscribe-subscribe"),
+    };
+
+    view! { cx,
+        <button
+            type="button"
+            class="btn text-nowrap"
+            class:btn-sm=small
+            class:btn-primary=move || subscribed.get() == SubscribedType::NotSubscribed
+            class:btn-outline-secondary=move || subscribed.get() != SubscribedType::NotSubscribed
+            disabled=move || !logged_in || follow_action.pending().get()
+            title=move || (!logged_in).then(|| translate(cx, "subscribe-log-in"))
+            on:click=move |_| {
+                follow_action.dispatch(subscribed.get_untracked() == SubscribedType::NotSubscribed)
+            }
+        >
+            {label}
+        </button>
+    }
+}
@@ -0,0 +1,331 @@
+use leptos::*;
+
+use crate::api::structs::*;
+use crate::api::user::save_user_settings;
+use crate::api::*;
+use crate::components::i18n::t;
+use crate::components::instance::*;
+use crate::components::site::{refetch_site, use_my_user};
+
+// TODO - theme.rs:
+// Move the theme picker into the settings page once it exists, and keep the header button as a shortcut
+
+// The theme is kept in a cookie so the server can render the page in it, which stops it flashing from light to dark when the page loads.
+// Logged in users also have it saved in their Lemmy settings, which is copied into the cookie when the site loads so their choice follows them to other browsers.
+const THEME_COOKIE: &str = "wolfrs_theme";
+
+// Runs before the page is shown, and again whenever the system theme changes, so Auto always matches prefers-color-scheme.
+// The server can't know the system theme, so Auto pages are rendered with an empty data-bs-theme and this fills it in.
+const AUTO_THEME_SCRIPT: &str = "(function () { var root = document.documentElement; var query = window.matchMedia('(prefers-color-scheme: dark)'); function apply() { if (root.getAttribute('data-wolfrs-theme') === 'auto') { root.setAttribute('data-bs-theme', query.matches ? 'dark' : 'light'); } } apply(); query.addEventListener('change', apply); })();";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ThemeMode {
+    Light,
+    Dark,
+    // Follows the theme set in the user's browser or operating system
+    #[default]
+    Auto,
+}
+
+impl ThemeMode {
+    pub const ALL: [ThemeMode; 3] = [ThemeMode::Light, ThemeMode::Dark, ThemeMode::Auto];
+
+    // The name of the theme in the locale files
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            ThemeMode::Light => "theme-light",
+            ThemeMode::Dark => "theme-dark",
+            ThemeMode::Auto => "theme-auto",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            ThemeMode::Light => "bi bi-sun",
+            ThemeMode::Dark => "bi bi-moon-stars",
+            ThemeMode::Auto => "bi bi-circle-half",
+        }
+    }
+
+    fn cookie_value(&self) -> &'static str {
+        match self {
+            ThemeMode::Light => "light",
+            ThemeMode::Dark => "dark",
+            ThemeMode::Auto => "auto",
+        }
+    }
+
+    fn from_cookie_value(value: &str) -> Option<Self> {
+        ThemeMode::ALL
+            .into_iter()
+            .find(|mode| mode.cookie_value() == value)
+    }
+
+    // The name of the matching theme in the Lemmy UI, which shares the setting with Wolfrs
+    fn lemmy_theme(&self) -> &'static str {
+        match self {
+            ThemeMode::Light => "litely",
+            ThemeMode::Dark => "darkly",
+            ThemeMode::Auto => "browser",
+        }
+    }
+
+    // The Lemmy UI has many more themes than Wolfrs, so they are matched on whether they are dark or light
+    fn from_lemmy_theme(theme: &str) -> Self {
+        let theme = theme.to_lowercase();
+        if theme.contains("dark") {
+            ThemeMode::Dark
+        } else if theme.contains("light") || theme.contains("litely") {
+            ThemeMode::Light
+        } else {
+            ThemeMode::Auto
+        }
+    }
+
+    // The value of Bootstrap's data-bs-theme attribute, which is left for AUTO_THEME_SCRIPT to set when rendering Auto on the server
+    fn bootstrap_theme(&self) -> Option<&'static str> {
+        match self {
+            ThemeMode::Light => Some("light"),
+            ThemeMode::Dark => Some("dark"),
+            ThemeMode::Auto => auto_bootstrap_theme(),
+        }
+    }
+}
+
+#[cfg(not(feature = "ssr"))]
+fn auto_bootstrap_theme() -> Option<&'static str> {
+    let prefers_dark = window()
+        .match_media("(prefers-color-scheme: dark)")
+        .ok()
+        .flatten()
+        .map(|query| query.matches())
+        .unwrap_or_default();
+
+    Some(if prefers_dark { "dark" } else { "light" })
+}
+
+#[cfg(feature = "ssr")]
+fn auto_bootstrap_theme() -> Option<&'static str> {
+    None
+}
+
+// The current theme, which is provided as context by the App
+#[derive(Clone, Copy)]
+pub struct ThemeContext {
+    // The theme from the cookie, which is used until the site has loaded and for anonymous users
+    cookie: ThemeMode,
+    // The theme saved in the logged in user's Lemmy settings
+    saved: Signal<Option<ThemeMode>>,
+    // The theme picked from the menu on this page, which is used until the site is fetched again with it saved
+    picked: RwSignal<Option<ThemeMode>>,
+}
+
+impl ThemeContext {
+    pub fn get(&self) -> ThemeMode {
+        self.picked
+            .get()
+            .or_else(|| self.saved.get())
+            .unwrap_or(self.cookie)
+    }
+
+    // Changes the theme for this browser. Saving it to the user's Lemmy settings is left to the caller, since it needs an action.
+    pub fn set(&self, mode: ThemeMode) {
+        set_cookie(THEME_COOKIE, mode.cookie_value());
+        self.picked.set(Some(mode));
+    }
+}
+
+// Reads the theme from its cookie and provides it as context. Needs to be called after provide_site.
+// The theme saved in Lemmy wins over the cookie, so changing it in another browser or the Lemmy UI is picked up here.
+// It is read from the site resource rather than in an effect, so the server renders the page in it too.
+pub fn provide_theme(cx: Scope) {
+    let cookie = get_cookie(cx, THEME_COOKIE)
+        .and_then(|value| ThemeMode::from_cookie_value(&value))
+        .unwrap_or_default();
+    let saved = Signal::derive(cx, move || {
+        use_my_user(cx)
+            .map(|my_user| ThemeMode::from_lemmy_theme(&my_user.local_user_view.local_user.theme))
+    });
+    provide_context(
+        cx,
+        ThemeContext {
+            cookie,
+            saved,
+            picked: create_rw_signal(cx, None),
+        },
+    );
+
+    // The saved theme is copied into the cookie, so pages the server renders before the site has loaded are already in it
+    create_effect(cx, move |_| {
+        if let Some(saved) = saved.get() {
+            if saved != cookie {
+                set_cookie(THEME_COOKIE, saved.cookie_value());
+            }
+        }
+    });
+}
+
+pub fn use_theme(cx: Scope) -> ThemeContext {
+    use_context::<ThemeContext>(cx).expect("provide_theme should be called by the App")
+}
+
+// The attributes for the <html> element, which Bootstrap and AUTO_THEME_SCRIPT read the theme from
+pub fn theme_attributes(cx: Scope) -> AdditionalAttributes {
+    let theme = use_theme(cx);
+
+    AdditionalAttributes::from(vec![
+        (
+            "data-bs-theme",
+            TextProp::from(move || theme.get().bootstrap_theme().unwrap_or_default().to_string()),
+        ),
+        (
+            "data-wolfrs-theme",
+            TextProp::from(move || theme.get().cookie_value().to_string()),
+        ),
+    ])
+}
+
+// Compiles the palette from Instance.toml into CSS variables that replace Bootstrap's own.
+// The colours have already been checked to be #rrggbb when the configuration was loaded, so they are safe to put in a <style> element.
+pub fn palette_css(palette: &InstancePalette) -> String {
+    let mut css = String::new();
+
+    if let Some(font_family) = &palette.font_family {
+        css.push_str(&format!(":root {{--bs-body-font-family: {};}} ", font_family));
+    }
+
+    // Pages that haven't had their theme set yet are light, as they are in Bootstrap
+    for (selector, colours) in [
+        (":root:not([data-bs-theme=dark])", &palette.light),
+        ("[data-bs-theme=dark]", &palette.dark),
+    ] {
+        let mut variables = String::new();
+        let mut rules = String::new();
+
+        for (name, colour) in colours.iter() {
+            let Some((red, green, blue)) = hex_colour_rgb(colour) else {
+                continue;
+            };
+            let rgb = format!("{}, {}, {}", red, green, blue);
+
+            match name {
+                "accent" => {
+                    variables.push_str(&format!(
+                        "--wolfrs-accent: {colour}; --bs-link-hover-color: {colour}; --bs-link-hover-color-rgb: {rgb}; "
+                    ));
+                    rules.push_str(&format!(
+                        "{selector} .nav-tabs .nav-link.active {{color: var(--wolfrs-accent);}} "
+                    ));
+                }
+                _ => {
+                    variables.push_str(&format!("--bs-{name}: {colour}; --bs-{name}-rgb: {rgb}; "));
+                    if name == "primary" {
+                        variables.push_str(&format!(
+                            "--bs-link-color: {colour}; --bs-link-color-rgb: {rgb}; "
+                        ));
+                    }
+                    // Bootstrap's buttons have their colours compiled in, so they are overridden separately
+                    rules.push_str(&format!(
+                        "{selector} .btn-{name} {{--bs-btn-bg: {colour}; --bs-btn-border-color: {colour}; --bs-btn-hover-bg: color-mix(in srgb, {colour} 85%, black); --bs-btn-hover-border-color: color-mix(in srgb, {colour} 85%, black); --bs-btn-active-bg: color-mix(in srgb, {colour} 75%, black); --bs-btn-active-border-color: color-mix(in srgb, {colour} 75%, black); --bs-btn-disabled-bg: {colour}; --bs-btn-disabled-border-color: {colour};}} "
+                    ));
+                }
+            }
+        }
+
+        if !variables.is_empty() {
+            css.push_str(&format!("{} {{{}}} ", selector, variables.trim_end()));
+        }
+        css.push_str(&rules);
+    }
+
+    css.trim_end().to_string()
+}
+
+// The inline script and palette for the top of the page.
+// They are rendered as plain elements rather than with leptos_meta, so their contents aren't escaped like text would be.
+#[component]
+pub fn ThemeHead(cx: Scope) -> impl IntoView {
+    let palette = get_instance_details()
+        .map(|instance| palette_css(&instance.palette))
+        .unwrap_or_default();
+
+    view! { cx,
+        <script inner_html=AUTO_THEME_SCRIPT></script>
+        <style inner_html=palette></style>
+    }
+}
+
+// The button in the header for switching between the light, dark, and auto themes
+#[component]
+pub fn ThemeMenu(cx: Scope) -> impl IntoView {
+    let theme = use_theme(cx);
+
+    let save_action = create_action(cx, move |mode: &ThemeMode| {
+        let mode = *mode;
+        async move {
+            let Some(auth) = get_auth_token(cx) else {
+                return;
+            };
+
+            let url_constructor = ApiUrlConstructor {
+                endpoint: api_endpoints::PutEndpoint::SAVE_USER_SETTINGS.to_string(),
+                id: None,
+                params: None,
+            };
+
+            let form = SaveUserSettings {
+                auth,
+                theme: Some(mode.lemmy_theme().to_string()),
+                ..Default::default()
+            };
+
+            // The site is fetched again so my_user has the new theme, otherwise it would be switched back the next time the site loads
+            if save_user_settings(cx, &api_post_url_builder(url_constructor), &form)
+                .await
+                .is_ok()
+            {
+                refetch_site(cx);
+            }
+        }
+    });
+
+    view! { cx,
+        <div class="dropdown">
+            <button
+                type="button"
+                class="btn btn-primary dropdown-toggle"
+                title=t(cx, "theme")
+                aria-label=t(cx, "theme")
+                data-bs-toggle="dropdown"
+                aria-expanded="false"
+            >
+                <i aria-hidden="true" class=move || theme.get().icon()></i>
+            </button>
+            <ul class="dropdown-menu dropdown-menu-end">
+                {ThemeMode::ALL
+                    .into_iter()
+                    .map(|mode| {
+                        view! { cx,
+                            <li>
+                                <button
+                                    type="button"
+                                    class="dropdown-item"
+                                    class:active=move || theme.get() == mode
+                                    aria-pressed=move || (theme.get() == mode).to_string()
+                                    on:click=move |_| {
+                                        theme.set(mode);
+                                        save_action.dispatch(mode);
+                                    }
+                                >
+                                    <i aria-hidden="true" class=mode.icon()></i>
+                                    " "
+                                    {t(cx, mode.message_id())}
+                                </button>
+                            </li>
+                        }
+                    })
+                    .collect_view(cx)}
+            </ul>
+        </div>
+    }
+}
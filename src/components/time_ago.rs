@@ -0,0 +1,77 @@
+use leptos::*;
+
+use crate::components::i18n::{translate_age, translate_with};
+use crate::timestamps::Timestamp;
+
+// TODO - time_ago.rs:
+// Stop the clock while the tab is hidden
+
+// How often the relative times on the page are updated in the browser
+#[cfg(not(feature = "ssr"))]
+const CLOCK_INTERVAL_SECONDS: u64 = 30;
+
+// The current time, which every TimeAgo reads so they all update together
+#[derive(Clone, Copy)]
+pub struct Clock(pub RwSignal<Timestamp>);
+
+// Provides the clock as context, and starts it ticking in the browser
+pub fn provide_clock(cx: Scope) {
+    let now = create_rw_signal(cx, Timestamp::now());
+
+    #[cfg(not(feature = "ssr"))]
+    if let Ok(handle) = set_interval_with_handle(
+        move || now.set(Timestamp::now()),
+        std::time::Duration::from_secs(CLOCK_INTERVAL_SECONDS),
+    ) {
+        on_cleanup(cx, move || handle.clear());
+    }
+
+    provide_context(cx, Clock(now));
+}
+
+fn use_now(cx: Scope) -> Timestamp {
+    use_context::<Clock>(cx)
+        .map(|clock| clock.0.get())
+        .unwrap_or_else(Timestamp::now)
+}
+
+// The full date and time for the tooltip. The browser shows it in the reader's time zone and language, the server doesn't know their time zone so it uses UTC.
+#[cfg(not(feature = "ssr"))]
+fn absolute_time(cx: Scope, time: Timestamp) -> String {
+    let date = js_sys::Date::new(&wasm_bindgen::JsValue::from_f64(time.unix() as f64 * 1000.0));
+    let locale = crate::components::i18n::use_locale(cx);
+    date.to_locale_string(locale.code(), &wasm_bindgen::JsValue::UNDEFINED)
+        .into()
+}
+
+#[cfg(feature = "ssr")]
+fn absolute_time(_cx: Scope, time: Timestamp) -> String {
+    time.to_utc_string()
+}
+
+// A timestamp from Lemmy shown as how long ago it was, such as 3h ago, with the full date and time when hovered.
+// Anything that can't be parsed is shown as it is.
+#[component]
+pub fn TimeAgo(
+    cx: Scope,
+    time: String,
+    // A message the time is put into as $time, such as time-edited for "edited 3h ago"
+    #[prop(optional)]
+    label: Option<&'static str>,
+) -> impl IntoView {
+    let with_label = move |time: String| match label {
+        Some(label) => translate_with(cx, label, &[("time", time.into())]),
+        None => time,
+    };
+
+    let Some(timestamp) = Timestamp::parse(&time) else {
+        return view! { cx, <span>{move || with_label(time.clone())}</span> }.into_view(cx);
+    };
+
+    view! { cx,
+        <time datetime=timestamp.to_iso() title=move || absolute_time(cx, timestamp)>
+            {move || with_label(translate_age(cx, timestamp.age_at(use_now(cx))))}
+        </time>
+    }
+    .into_view(cx)
+}
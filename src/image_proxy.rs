@@ -0,0 +1,373 @@
+use cfg_if::cfg_if;
+use serde::{Deserialize, Serialize};
+
+use crate::components::instance::*;
+
+// TODO - image_proxy.rs:
+// Resize GIFs without losing their animation
+
+// When the image proxy is turned on in Instance.toml, remote images are loaded through Wolfrs instead of straight from the federated instances that host them.
+// This keeps readers' IPs from being sent to every instance in their feed, and lets thumbnails and avatars be resized to the size they are shown at.
+
+// The widths images can be resized to, requested widths are rounded up to one of these so the cache doesn't fill with every possible size
+pub const IMAGE_PROXY_WIDTHS: &[u32] = &[64, 128, 256, 512, 1024, 2048];
+
+// The query string of the /image_proxy route
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ImageProxyQuery {
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+}
+
+pub fn image_proxy_enabled() -> bool {
+    get_instance_details()
+        .map(|instance| instance.image_proxy)
+        .unwrap_or_default()
+}
+
+// Rounds a requested width up to the nearest width the proxy resizes to
+fn proxy_width(width: u32) -> u32 {
+    IMAGE_PROXY_WIDTHS
+        .iter()
+        .copied()
+        .find(|proxy_width| *proxy_width >= width)
+        .unwrap_or(IMAGE_PROXY_WIDTHS[IMAGE_PROXY_WIDTHS.len() - 1])
+}
+
+// Rewrites the URL of a remote image to go through the image proxy, resized to the given width.
+// Local images and anything that isn't http or https are left as they are, as is everything when the proxy is turned off.
+pub fn proxied_image_url(url: &str, width: Option<u32>) -> String {
+    let is_remote = {
+        let url = url.trim().to_lowercase();
+        url.starts_with("https://") || url.starts_with("http://")
+    };
+    if !image_proxy_enabled() || !is_remote {
+        return url.to_string();
+    }
+
+    let query = ImageProxyQuery {
+        url: url.trim().to_string(),
+        width: width.map(proxy_width),
+    };
+    match serde_html_form::to_string(query) {
+        Ok(query) => format!("/image_proxy?{}", query),
+        Err(_) => url.to_string(),
+    }
+}
+
+// The same as proxied_image_url, for images that may not exist such as avatars and icons
+pub fn proxied_image(url: Option<String>, width: Option<u32>) -> Option<String> {
+    url.map(|url| proxied_image_url(&url, width))
+}
+
+// Rewrites the src of every image in sanitized HTML to go through the image proxy
+pub fn proxy_image_sources(html: &str, width: Option<u32>) -> String {
+    if !image_proxy_enabled() {
+        return html.to_string();
+    }
+
+    let src_regex = regex::Regex::new(r#"(<img\s[^>]*?src=")([^"]*)(")"#).unwrap();
+    src_regex
+        .replace_all(html, |captures: &regex::Captures| {
+            // The sanitizer escapes attribute values, so the URL is unescaped before it is encoded into the query string
+            let url = captures[2]
+                .replace("&quot;", "\"")
+                .replace("&lt;", "<")
+                .replace("&gt;", ">")
+                .replace("&amp;", "&");
+            format!(
+                "{}{}{}",
+                &captures[1],
+                proxied_image_url(&url, width).replace('&', "&amp;"),
+                &captures[3]
+            )
+        })
+        .to_string()
+}
+
+cfg_if! { if #[cfg(feature = "ssr")] {
+    use axum::{
+        extract::Query,
+        http::{header, StatusCode},
+        response::{IntoResponse, Response},
+    };
+    use image::{io::Reader as ImageReader, DynamicImage, ImageFormat, ImageOutputFormat};
+    use sha2::{Digest, Sha256};
+    use std::io::Cursor;
+    use std::net::{IpAddr, SocketAddr};
+    use std::path::{Path, PathBuf};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::{Duration, SystemTime};
+
+    // The largest remote image that will be fetched, which matches the default limit of pictrs
+    pub const MAX_PROXY_IMAGE_SIZE: usize = 25 * 1024 * 1024;
+
+    const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+    // Images larger than this in either direction aren't decoded, so a small file can't expand into gigabytes of memory
+    const MAX_IMAGE_DIMENSION: u32 = 10000;
+
+    // Proxied images never change for a URL and width, so browsers can keep them for a week
+    const CACHE_CONTROL: &str = "public, max-age=604800, immutable";
+
+    // Each URL and width is cached in its own file, named with a hash of both
+    fn cache_path(query: &ImageProxyQuery) -> PathBuf {
+        let mut hasher = Sha256::new();
+        hasher.update(query.url.as_bytes());
+        hasher.update(query.width.unwrap_or_default().to_be_bytes());
+
+        PathBuf::from(get_instance_details().unwrap().image_proxy_cache_dir)
+            .join(format!("{:x}", hasher.finalize()))
+    }
+
+    // Only addresses on the public internet can be fetched, so the proxy can't be used to reach the server's own network
+    fn is_public_address(ip: IpAddr) -> bool {
+        match ip {
+            IpAddr::V4(ip) => {
+                let [first, second, ..] = ip.octets();
+                !(ip.is_loopback()
+                    || ip.is_private()
+                    || ip.is_link_local()
+                    || ip.is_unspecified()
+                    || ip.is_broadcast()
+                    || ip.is_documentation()
+                    // Shared address space used by carrier-grade NAT
+                    || (first == 100 && (64..128).contains(&second)))
+            }
+            IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+                Some(ip) => is_public_address(IpAddr::V4(ip)),
+                None => {
+                    let first = ip.segments()[0];
+                    !(ip.is_loopback()
+                        || ip.is_unspecified()
+                        // Unique local and link-local addresses
+                        || (first & 0xfe00) == 0xfc00
+                        || (first & 0xffc0) == 0xfe80)
+                }
+            },
+        }
+    }
+
+    // Looks up the host and returns the address to connect to, as long as every address it has is public
+    async fn resolve_public_host(url: &reqwest::Url) -> Option<SocketAddr> {
+        let (Some(host), Some(port)) = (url.host_str(), url.port_or_known_default()) else {
+            return None;
+        };
+
+        let addresses = tokio::net::lookup_host((host.trim_matches(&['[', ']'][..]), port))
+            .await
+            .ok()?
+            .collect::<Vec<_>>();
+        if addresses.iter().all(|address| is_public_address(address.ip())) {
+            addresses.first().copied()
+        } else {
+            None
+        }
+    }
+
+    // Fetches the remote image, stopping once it goes over the size limit
+    async fn fetch_image(url: &str) -> Result<Vec<u8>, (StatusCode, String)> {
+        let url = reqwest::Url::parse(url)
+            .ok()
+            .filter(|url| matches!(url.scheme(), "http" | "https"))
+            .ok_or((StatusCode::BAD_REQUEST, "Only http and https images can be proxied".to_string()))?;
+
+        let Some(address) = resolve_public_host(&url).await else {
+            return Err((StatusCode::FORBIDDEN, "This image's host can't be proxied".to_string()));
+        };
+
+        // The client connects to the address that was checked instead of looking the host up again, so DNS can't point it somewhere else in between.
+        // Redirects aren't followed, since they could point somewhere that wasn't checked above.
+        let mut client = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .timeout(FETCH_TIMEOUT);
+        if let Some(host) = url.domain() {
+            client = client.resolve(host, address);
+        }
+        let client = client
+            .build()
+            .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+
+        let mut response = client
+            .get(url)
+            .send()
+            .await
+            .map_err(|err| (StatusCode::BAD_GATEWAY, format!("Error fetching image: {err}")))?;
+
+        if !response.status().is_success() {
+            return Err((StatusCode::BAD_GATEWAY, format!("Error fetching image: {}", response.status())));
+        }
+        if response.content_length().unwrap_or_default() as usize > MAX_PROXY_IMAGE_SIZE {
+            return Err((StatusCode::PAYLOAD_TOO_LARGE, "This image is too large to proxy".to_string()));
+        }
+
+        let mut bytes = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|err| (StatusCode::BAD_GATEWAY, format!("Error fetching image: {err}")))?
+        {
+            if bytes.len() + chunk.len() > MAX_PROXY_IMAGE_SIZE {
+                return Err((StatusCode::PAYLOAD_TOO_LARGE, "This image is too large to proxy".to_string()));
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+
+        Ok(bytes)
+    }
+
+    // Resizes the image to the width and re-encodes it, which also strips any metadata such as location from the original.
+    // Images with transparency are encoded as PNG and everything else as JPEG. GIFs are left alone so they keep their animation.
+    fn resize_image(bytes: Vec<u8>, width: Option<u32>) -> Result<Vec<u8>, (StatusCode, String)> {
+        let not_an_image = || (StatusCode::UNSUPPORTED_MEDIA_TYPE, "This isn't a supported image".to_string());
+
+        let format = image::guess_format(&bytes).map_err(|_| not_an_image())?;
+        let Some(width) = width.filter(|_| format != ImageFormat::Gif) else {
+            return Ok(bytes);
+        };
+
+        let mut limits = image::io::Limits::default();
+        limits.max_image_width = Some(MAX_IMAGE_DIMENSION);
+        limits.max_image_height = Some(MAX_IMAGE_DIMENSION);
+
+        let mut reader = ImageReader::with_format(Cursor::new(bytes), format);
+        reader.limits(limits);
+        let image = reader.decode().map_err(|_| not_an_image())?;
+
+        let image = if image.width() > width {
+            image.resize(width, u32::MAX, image::imageops::FilterType::Lanczos3)
+        } else {
+            image
+        };
+
+        let mut output = Cursor::new(Vec::new());
+        let result = if image.color().has_alpha() {
+            image.write_to(&mut output, ImageOutputFormat::Png)
+        } else {
+            DynamicImage::ImageRgb8(image.to_rgb8()).write_to(&mut output, ImageOutputFormat::Jpeg(85))
+        };
+        result.map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, format!("Error encoding image: {err}")))?;
+
+        Ok(output.into_inner())
+    }
+
+    // Only the formats the proxy can recognise are ever served, so nothing like an SVG with a script in it comes from the Wolfrs domain
+    fn image_response(bytes: Vec<u8>) -> Response {
+        let Some(content_type) = image::guess_format(&bytes).ok().map(|format| format.to_mime_type()) else {
+            return (StatusCode::UNSUPPORTED_MEDIA_TYPE, "This isn't a supported image").into_response();
+        };
+
+        (
+            [
+                (header::CONTENT_TYPE, content_type),
+                (header::CACHE_CONTROL, CACHE_CONTROL),
+                (header::X_CONTENT_TYPE_OPTIONS, "nosniff"),
+                (header::CONTENT_SECURITY_POLICY, "default-src 'none'; sandbox"),
+            ],
+            bytes,
+        )
+            .into_response()
+    }
+
+    // Set while the cache is being trimmed, so images cached at the same time don't each start their own pass over it
+    static EVICTING: AtomicBool = AtomicBool::new(false);
+
+    // Removes the images that were cached longest ago until the cache fits in its size limit again
+    async fn evict_cached_images(cache_dir: PathBuf, max_bytes: u64) {
+        if EVICTING.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        if let Err(err) = evict_oldest(&cache_dir, max_bytes).await {
+            log::warn!("Couldn't trim the image proxy cache: {}", err);
+        }
+        EVICTING.store(false, Ordering::Release);
+    }
+
+    async fn evict_oldest(cache_dir: &Path, max_bytes: u64) -> std::io::Result<()> {
+        let mut files = Vec::new();
+        let mut total_bytes = 0;
+        let mut entries = tokio::fs::read_dir(cache_dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let metadata = entry.metadata().await?;
+            // Temporary files are still being written, so they're left for the request that is writing them
+            if !metadata.is_file() || entry.path().extension().is_some_and(|extension| extension == "tmp") {
+                continue;
+            }
+            total_bytes += metadata.len();
+            files.push((metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH), metadata.len(), entry.path()));
+        }
+        if total_bytes <= max_bytes {
+            return Ok(());
+        }
+
+        files.sort_unstable_by_key(|(modified, ..)| *modified);
+        for (_, size, path) in files {
+            if total_bytes <= max_bytes {
+                break;
+            }
+            match tokio::fs::remove_file(&path).await {
+                Ok(()) => total_bytes -= size,
+                // Another request may have already replaced or removed it
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => total_bytes -= size,
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(())
+    }
+
+    // Serves a remote image from the disk cache, fetching, resizing, and caching it first if it hasn't been requested before
+    pub async fn image_proxy(Query(query): Query<ImageProxyQuery>) -> Response {
+        if !image_proxy_enabled() {
+            return StatusCode::NOT_FOUND.into_response();
+        }
+
+        // Widths that weren't made by proxied_image_url are rounded the same way, so they share the same cache files
+        let query = ImageProxyQuery {
+            width: query.width.map(proxy_width),
+            ..query
+        };
+        let path = cache_path(&query);
+
+        if let Ok(bytes) = tokio::fs::read(&path).await {
+            return image_response(bytes);
+        }
+
+        let bytes = match fetch_image(&query.url).await {
+            Ok(bytes) => bytes,
+            Err(err) => return err.into_response(),
+        };
+
+        // Decoding and encoding images is slow enough that it shouldn't hold up the async runtime
+        let width = query.width;
+        let resized = tokio::task::spawn_blocking(move || resize_image(bytes, width))
+            .await
+            .unwrap_or_else(|err| Err((StatusCode::INTERNAL_SERVER_ERROR, err.to_string())));
+        let bytes = match resized {
+            Ok(bytes) => bytes,
+            Err(err) => return err.into_response(),
+        };
+
+        // The image is written to a temporary file and then renamed, so a request for it never reads a half written file.
+        // A failure to cache isn't fatal, the image is still sent.
+        if let Some(cache_dir) = path.parent() {
+            let temporary_path = path.with_extension("tmp");
+            let cached = async {
+                tokio::fs::create_dir_all(cache_dir).await?;
+                tokio::fs::write(&temporary_path, &bytes).await?;
+                tokio::fs::rename(&temporary_path, &path).await
+            }
+            .await;
+            match cached {
+                Ok(()) => {
+                    let max_bytes = get_instance_details().unwrap().image_proxy_cache_size_mb * 1024 * 1024;
+                    tokio::spawn(evict_cached_images(cache_dir.to_path_buf(), max_bytes));
+                }
+                Err(err) => log::warn!("Couldn't cache proxied image {}: {}", query.url, err),
+            }
+        }
+
+        image_response(bytes)
+    }
+}}
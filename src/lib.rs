@@ -1,10 +1,15 @@
 use cfg_if::cfg_if;
 pub mod api;
+pub mod api_cache;
 pub mod app;
 pub mod components;
 pub mod error_template;
 pub mod fileserv;
+pub mod image_proxy;
 pub mod pages;
+pub mod pictrs;
+pub mod sitemap;
+pub mod timestamps;
 
 cfg_if! { if #[cfg(feature = "hydrate")] {
     use leptos::*;
@@ -1,15 +1,49 @@
 #[cfg(feature = "ssr")]
 #[tokio::main]
 async fn main() {
-    use axum::{routing::post, Router};
+    use axum::{
+        extract::DefaultBodyLimit,
+        routing::{delete, get, post},
+        Router,
+    };
     use leptos::*;
     use leptos_axum::{generate_route_list, LeptosRoutes};
+    use wolfrs::api_cache::api_cache_metrics;
     use wolfrs::app::*;
+    use wolfrs::components::instance::{instance_config_path, load_instance_details};
     use wolfrs::fileserv::file_and_error_handler;
+    use wolfrs::image_proxy::image_proxy;
+    use wolfrs::pictrs::{delete_image_proxy, upload_image_proxy, MAX_UPLOAD_SIZE};
+    use wolfrs::sitemap::{robots, sitemap};
 
     // Controls logging for developer terminal.
     simple_logger::init_with_level(log::Level::Warn).expect("couldn't initialize logging");
 
+    // The instance's configuration is loaded before anything else, so a mistake in it stops the server with a clear error
+    if let Err(err) = load_instance_details() {
+        log::error!("{}", err);
+        std::process::exit(1);
+    }
+
+    // Sending SIGHUP reloads the configuration without restarting the server, keeping the old one if the new one has an error
+    #[cfg(unix)]
+    tokio::spawn(async {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let Ok(mut hangups) = signal(SignalKind::hangup()) else {
+            return;
+        };
+        while hangups.recv().await.is_some() {
+            match load_instance_details() {
+                Ok(()) => log::warn!(
+                    "Reloaded the instance configuration from {}",
+                    instance_config_path()
+                ),
+                Err(err) => log::error!("Kept the old instance configuration: {}", err),
+            }
+        }
+    });
+
     // Setting get_configuration(None) means we'll be using cargo-leptos's env values
     // For deployment these variables are:
     // <https://github.com/leptos-rs/start-axum#executing-a-server-on-a-remote-machine-without-the-toolchain>
@@ -23,6 +57,23 @@ async fn main() {
     // build our application with a route
     let app = Router::new()
         .route("/", post(leptos_axum::handle_server_fns))
+        // Image uploads are proxied to the instance's pictrs so that the auth cookie applies
+        .route(
+            "/pictrs/image",
+            post(upload_image_proxy).layer(DefaultBodyLimit::max(MAX_UPLOAD_SIZE)),
+        )
+        // Deleting is a DELETE rather than pictrs' own GET, so another site can't delete someone's images by linking to it
+        .route(
+            "/pictrs/image/delete/:delete_token/:file",
+            delete(delete_image_proxy),
+        )
+        // Remote images are loaded through here when the image proxy is turned on in Instance.toml
+        .route("/image_proxy", get(image_proxy))
+        // Hit and miss counts for the cache of anonymous API responses, when they are turned on in Instance.toml
+        .route("/metrics/api_cache", get(api_cache_metrics))
+        // For search engines, listing the instance's newest posts and top communities, or keeping them out of private instances
+        .route("/sitemap.xml", get(sitemap))
+        .route("/robots.txt", get(robots))
         .leptos_routes(&leptos_options, routes, |cx| view! { cx, <App/> })
         .fallback(file_and_error_handler)
         .with_state(leptos_options);
@@ -0,0 +1,192 @@
+use leptos::*;
+
+use crate::api::admin::{ban_person, get_banned_persons};
+use crate::api::structs::*;
+use crate::api::*;
+use crate::components::admin::{AdminAction, AdminActionInput, AdminActionModal};
+use crate::components::i18n::{t, translate};
+
+// TODO - banned_users.rs:
+// Show the ban reason from the mod log alongside each user
+// Improve styling, especially on mobile
+
+/// The admin-only page listing every user banned from the site, with the option to unban them.
+#[component]
+pub fn BannedUsers(cx: Scope) -> impl IntoView {
+    // Variable that holds the returned BannedPersonsResponse from the API
+    let banned = create_resource(
+        cx,
+        || (),
+        move |_| async move {
+            // This constructs the proper API URL for GetBannedPersons
+            let url_constructor = ApiUrlConstructor {
+                endpoint: api_endpoints::GetEndpoint::GET_BANNED_PERSONS.to_string(),
+                id: None,
+                params: None,
+            };
+
+            // This assembles the GetBannedPersons request form, which only works with the auth token of an admin
+            let get_form = GetBannedPersons {
+                auth: get_auth_token(cx)?,
+            };
+
+            get_banned_persons(cx, &api_url_builder(cx, url_constructor, get_form))
+                .await
+                .ok()
+        },
+    );
+
+    let err_msg = t(cx, "error-loading-banned-users");
+
+    view! { cx,
+        <div class="container overflow-hidden">
+            <Transition fallback=move || {
+                // Handles the loading screen while waiting for a reply from the API
+                view! { cx,
+                    <div class="d-flex align-items-center">
+                        <h1>
+                            {t(cx, "loading")}
+                        </h1>
+                        <div class="spinner-grow ms-auto" role="status" aria-hidden="true"></div>
+                    </div>
+                }
+            }>
+                {move || {
+                    banned
+                        .read(cx)
+                        .map(|res| match res {
+                            None => {
+                                view! { cx, <div>{err_msg}</div> }
+                            }
+                            Some(res) => {
+
+                                view! { cx,
+                                    <div class="row">
+                                        <BannedUsersList banned=res.banned/>
+                                    </div>
+                                }
+                            }
+                        })
+                }}
+
+            </Transition>
+        </div>
+    }
+}
+
+// The table of banned users
+#[component]
+fn BannedUsersList(cx: Scope, banned: Vec<PersonView>) -> impl IntoView {
+    view! { cx,
+        <div class="col-md-12">
+            <div class="container">
+                <table class="table table-dark table-striped">
+                    <thead>
+                        <tr>
+                            <th colspan="3">
+                                <h3>{t(cx, "banned-title")}</h3>
+                            </th>
+                        </tr>
+                        <tr>
+                            <th scope="col">{t(cx, "banned-name")}</th>
+                            <th scope="col">{t(cx, "banned-expires")}</th>
+                            <th scope="col">{t(cx, "admin-unban")}</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {banned
+                            .into_iter()
+                            .map(|item| view! { cx, <BannedUsersListItem person=item.person/> })
+                            .collect_view(cx)}
+                    </tbody>
+                </table>
+            </div>
+        </div>
+    }
+}
+
+// A single banned user, with the button for unbanning them
+#[component]
+fn BannedUsersListItem(cx: Scope, person: Person) -> impl IntoView {
+    let person_id = person.id;
+
+    // Checks to see if a user has an avatar set, if not it assigns a default one
+    let avatar = match person.avatar {
+        Some(_) => person.avatar,
+        _ => Option::Some("/static/default_assets/default-profile.png".to_string()),
+    };
+
+    let unban_action: AdminAction = create_action(cx, move |input: &AdminActionInput| {
+        let input = input.clone();
+        async move {
+            let url_constructor = ApiUrlConstructor {
+                endpoint: api_endpoints::PostEndpoint::BAN_PERSON.to_string(),
+                id: None,
+                params: None,
+            };
+
+            let form = BanPerson {
+                auth: get_auth_token(cx).unwrap_or_default(),
+                ban: false,
+                expires: None,
+                person_id,
+                reason: input.reason,
+                remove_data: None,
+            };
+
+            ban_person(cx, &api_post_url_builder(url_constructor), &form)
+                .await
+                .map(|_| translate(cx, "admin-user-unbanned"))
+                .map_err(|err| err.to_string())
+        }
+    });
+
+    let person_link = if person.local {
+        format!("/user/{}", person.name)
+    } else {
+        let url_regex = regex::Regex::new(r"https://(.*)/u/(.*)").unwrap();
+        let external_person_link = match url_regex.captures(&person.actor_id) {
+            Some(external_person_link) => external_person_link,
+            None => {
+                log::warn!("url regex error: {:?}", &person.actor_id);
+                url_regex
+                    .captures(&person.actor_id)
+                    .unwrap_or_else(|| url_regex.captures("https://error.com/u/error").unwrap())
+            }
+        };
+
+        format!("/user/{}@{}", person.name, &external_person_link[1])
+    };
+
+    let modal_id = format!("unban-person-{}", person_id);
+
+    view! { cx,
+        <tr>
+            <td>
+                <a href=person_link>
+                    <img src=avatar alt="" width="32" height="32" class="rounded"/>
+                    "  "
+                    {person.name.clone()}
+                </a>
+            </td>
+            <td>{person.ban_expires.unwrap_or_else(|| translate(cx, "banned-never"))}</td>
+            <td>
+                <button
+                    type="button"
+                    class="btn btn-outline-secondary btn-sm"
+                    data-bs-toggle="modal"
+                    data-bs-target=format!("#{}", modal_id)
+                >
+                    {t(cx, "admin-unban")}
+                </button>
+                <AdminActionModal
+                    modal_id=modal_id
+                    title=translate(cx, "admin-unban-user")
+                    warning=translate(cx, "banned-unban-warning")
+                    confirm_label=translate(cx, "admin-unban")
+                    action=unban_action
+                />
+            </td>
+        </tr>
+    }
+}
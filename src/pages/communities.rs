@@ -1,17 +1,176 @@
 use leptos::*;
 use leptos_router::*;
+use serde::Serialize;
 
 use crate::api::community::list_communities;
+use crate::api::search::search;
 use crate::api::structs::*;
 use crate::api::*;
+use crate::components::i18n::{t, translate, translate_with};
+use crate::components::instance::get_instance_details;
+use crate::components::nsfw::*;
 use crate::components::pagination::Pagination;
+use crate::components::seo::{site_url, PageHead, PageMeta};
+use crate::components::subscribe_button::SubscribeButton;
+use crate::image_proxy::proxied_image;
 
 // TODO - communities.rs:
 // Improve styling to deal with the small overflow that happens with mobile views
-// Add more sorting options for Total Subs, MAU's, Posts, etc
-// Add functionality for filtering by subscribed, local, and all
 // Potentially move logic from the page to an actual component to maintain seperation of page from components (Optional)
 
+// The directory of communities. The listing, sorting, NSFW toggle, and text filter are all kept in the query string, so a filtered directory can be linked to.
+
+// The columns the directory can be sorted by
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+enum CommunitySort {
+    #[default]
+    Subscribers,
+    MonthlyActiveUsers,
+    Posts,
+    Comments,
+    New,
+}
+
+impl CommunitySort {
+    fn query_value(&self) -> &'static str {
+        match self {
+            CommunitySort::Subscribers => "subscribers",
+            CommunitySort::MonthlyActiveUsers => "mau",
+            CommunitySort::Posts => "posts",
+            CommunitySort::Comments => "comments",
+            CommunitySort::New => "new",
+        }
+    }
+
+    fn from_query_value(value: &str) -> Option<Self> {
+        [
+            CommunitySort::Subscribers,
+            CommunitySort::MonthlyActiveUsers,
+            CommunitySort::Posts,
+            CommunitySort::Comments,
+            CommunitySort::New,
+        ]
+        .into_iter()
+        .find(|sort| sort.query_value() == value)
+    }
+
+    // Lemmy sorts communities by subscribers for TopAll and by monthly active users for TopMonth.
+    // It can't sort them by posts or comments, so those are fetched by subscribers and sorted within the page.
+    fn api_sort(&self) -> SortType {
+        match self {
+            CommunitySort::MonthlyActiveUsers => SortType::TopMonth,
+            CommunitySort::New => SortType::New,
+            CommunitySort::Subscribers | CommunitySort::Posts | CommunitySort::Comments => {
+                SortType::TopAll
+            }
+        }
+    }
+
+    fn sort_page(&self, communities: &mut [CommunityView]) {
+        match self {
+            CommunitySort::Posts => communities.sort_by(|a, b| b.counts.posts.cmp(&a.counts.posts)),
+            CommunitySort::Comments => {
+                communities.sort_by(|a, b| b.counts.comments.cmp(&a.counts.comments))
+            }
+            _ => {}
+        }
+    }
+}
+
+// Everything that decides which communities are shown
+#[derive(Clone, Debug, PartialEq)]
+struct DirectoryFilters {
+    listing: ListingType,
+    sort: CommunitySort,
+    show_nsfw: bool,
+    search: String,
+}
+
+#[derive(Serialize)]
+struct DirectoryQuery<'a> {
+    #[serde(rename = "type")]
+    listing: &'static str,
+    sort: &'static str,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    nsfw: bool,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    q: &'a str,
+}
+
+impl DirectoryFilters {
+    fn from_query(query: &ParamsMap) -> Self {
+        DirectoryFilters {
+            listing: query
+                .get("type")
+                .and_then(|listing| ListingType::from_str(listing))
+                .unwrap_or(ListingType::Local),
+            sort: query
+                .get("sort")
+                .and_then(|sort| CommunitySort::from_query_value(sort))
+                .unwrap_or_default(),
+            show_nsfw: nsfw_enabled() && query.get("nsfw").is_some_and(|nsfw| nsfw == "true"),
+            search: query.get("q").map(|q| q.trim().to_string()).unwrap_or_default(),
+        }
+    }
+
+    // Changing any filter goes back to the first page, so the page number is left out
+    fn href(&self) -> String {
+        serde_html_form::to_string(DirectoryQuery {
+            listing: self.listing.to_str(),
+            sort: self.sort.query_value(),
+            nsfw: self.show_nsfw,
+            q: &self.search,
+        })
+        .map(|query| format!("?{}", query))
+        .unwrap_or_default()
+    }
+
+    fn with_listing(&self, listing: ListingType) -> Self {
+        DirectoryFilters {
+            listing,
+            ..self.clone()
+        }
+    }
+
+    fn with_sort(&self, sort: CommunitySort) -> Self {
+        DirectoryFilters {
+            sort,
+            ..self.clone()
+        }
+    }
+
+    fn with_nsfw(&self, show_nsfw: bool) -> Self {
+        DirectoryFilters {
+            show_nsfw,
+            ..self.clone()
+        }
+    }
+}
+
+// The first line of a community's description without its markdown, cut down to fit in the table
+fn description_excerpt(description: &Option<String>) -> String {
+    let excerpt_length = 120;
+    let line = description
+        .as_deref()
+        .unwrap_or_default()
+        .lines()
+        .map(|line| line.trim_start_matches(['#', '>', '*', '-', ' ']).trim())
+        .find(|line| !line.is_empty())
+        .unwrap_or_default()
+        .replace(['*', '_', '`'], "");
+
+    if line.chars().count() <= excerpt_length {
+        return line;
+    }
+
+    // Cuts at the last space so words aren't split in half
+    let truncated = line.chars().take(excerpt_length).collect::<String>();
+    let truncated = truncated
+        .rsplit_once(' ')
+        .map_or(truncated.as_str(), |(words, _)| words);
+    format!("{}...", truncated.trim_end())
+}
+
 #[component]
 pub fn Communities(cx: Scope) -> impl IntoView {
     let query = use_query_map(cx);
@@ -20,42 +179,166 @@ pub fn Communities(cx: Scope) -> impl IntoView {
             .with(|q| q.get("page").and_then(|page| page.parse::<i32>().ok()))
             .unwrap_or(1)
     };
+    let filters = move || query.with(DirectoryFilters::from_query);
 
-    // Variable that holds the returned ListCommunitiesResponse from the API
-    let communities = create_resource(cx, page, move |page| async move {
-        // This constructs the proper API URL for GetPosts
-        let url_constructor = ApiUrlConstructor {
-            endpoint: api_endpoints::GetEndpoint::LIST_COMMUNITIES.to_string(),
-            id: None,
-            params: None,
-        };
+    let logged_in = get_auth_token(cx).is_some();
+
+    // Variable that holds the communities for the current filters and page
+    let updater = move || (page(), filters());
+    let communities = create_resource(cx, updater, move |(page, filters)| async move {
+        // The Subscribed listing needs to know who is asking, and it fills in the subscribe buttons for everything else
+        let auth = get_auth_token(cx);
+
+        // Lemmy's community list can't be filtered by text, so the filter is done with a search instead
+        let mut communities = if filters.search.is_empty() {
+            let url_constructor = ApiUrlConstructor {
+                endpoint: api_endpoints::GetEndpoint::LIST_COMMUNITIES.to_string(),
+                id: None,
+                params: None,
+            };
 
-        // This assembles the ListCommunities request form
-        let get_form = ListCommunities {
-            auth: None,
-            limit: Some(50),
-            page: Some(page),
-            show_nsfw: None,
-            sort: Some(SortType::TopMonth),
-            type_: Some(ListingType::Local),
+            // This assembles the ListCommunities request form
+            let get_form = ListCommunities {
+                auth,
+                limit: Some(50),
+                page: Some(page),
+                show_nsfw: Some(filters.show_nsfw),
+                sort: Some(filters.sort.api_sort()),
+                type_: Some(filters.listing),
+            };
+
+            // This is where the API is called for ListCommunities and the ListCommunitiesResponse is returned
+            list_communities(cx, &api_url_builder(cx, url_constructor, get_form))
+                .await
+                .ok()?
+                .communities
+        } else {
+            let url_constructor = ApiUrlConstructor {
+                endpoint: api_endpoints::GetEndpoint::SEARCH.to_string(),
+                id: None,
+                params: None,
+            };
+
+            let get_form = Search {
+                auth,
+                community_id: None,
+                community_name: None,
+                creator_id: None,
+                limit: Some(50),
+                listing_type: Some(filters.listing),
+                page: Some(page),
+                q: filters.search.clone(),
+                sort: Some(filters.sort.api_sort()),
+                type_: Some(SearchType::Communities),
+            };
+
+            // Search leaves NSFW communities in for users who allow them, so the toggle is applied here
+            search(cx, &api_url_builder(cx, url_constructor, get_form))
+                .await
+                .ok()?
+                .communities
+                .into_iter()
+                .filter(|item| filters.show_nsfw || !item.community.nsfw)
+                .collect()
         };
 
-        // This is where the API is called for ListCommunities and the ListCommunitiesResponse is returned
-        list_communities(cx, &api_url_builder(cx, url_constructor, get_form))
-            .await
-            .ok()
+        filters.sort.sort_page(&mut communities);
+        Some(communities)
     });
 
-    let err_msg = "Error loading this post: ";
+    let err_msg = t(cx, "error-loading-communities");
+
+    // The filters are left out of the canonical link, so every sort and page of the list counts as the one page
+    let instance_name = get_instance_details().unwrap().name;
+    let meta = PageMeta::for_page(
+        translate(cx, "communities-title"),
+        translate_with(cx, "communities-description", &[("name", instance_name.into())]),
+        "/communities",
+        &site_url(),
+    );
 
     view! { cx,
+        <PageHead meta=meta/>
         <div class="container overflow-hidden">
+            <div class="card my-3">
+                <div class="card-header">
+                    <div class="row g-2 align-items-center">
+                        <div class="col-md-5">
+                            <ul class="nav nav-tabs card-header-tabs">
+                                {[ListingType::Local, ListingType::All, ListingType::Subscribed]
+                                    .into_iter()
+                                    .map(|listing| {
+                                        let disabled = listing == ListingType::Subscribed && !logged_in;
+                                        let label = listing.to_str();
+                                        let tab_listing = listing;
+                                        view! { cx,
+                                            <li class="nav-item">
+                                                <a
+                                                    class="nav-link"
+                                                    class:active=move || filters().listing == tab_listing
+                                                    class:disabled=disabled
+                                                    aria-disabled=disabled
+                                                    href=move || filters().with_listing(listing).href()
+                                                >
+                                                    {label}
+                                                </a>
+                                            </li>
+                                        }
+                                    })
+                                    .collect_view(cx)}
+                            </ul>
+                        </div>
+                        <div class="col-md-5">
+                            // A plain GET form, so the filter also works before the page has loaded its WASM
+                            <Form method="GET" action="">
+                                <input type="hidden" name="type" value=move || filters().listing.to_str()/>
+                                <input type="hidden" name="sort" value=move || filters().sort.query_value()/>
+                                {move || filters().show_nsfw.then(|| view! { cx, <input type="hidden" name="nsfw" value="true"/> })}
+                                <div class="input-group input-group-sm">
+                                    <input
+                                        type="search"
+                                        class="form-control"
+                                        name="q"
+                                        placeholder=t(cx, "communities-filter-placeholder")
+                                        aria-label=t(cx, "communities-filter")
+                                        prop:value=move || filters().search
+                                    />
+                                    <button type="submit" class="btn btn-secondary" aria-label=t(cx, "search")>
+                                        <i aria-hidden="true" class="bi bi-search"></i>
+                                    </button>
+                                </div>
+                            </Form>
+                        </div>
+                        <div class="col-md-2 d-flex justify-content-end">
+                            {nsfw_enabled()
+                                .then(|| {
+                                    view! { cx,
+                                        <a
+                                            class="btn btn-sm btn-outline-secondary"
+                                            class:active=move || filters().show_nsfw
+                                            href=move || {
+                                                let filters = filters();
+                                                filters.with_nsfw(!filters.show_nsfw).href()
+                                            }
+                                        >
+                                            <i aria-hidden="true" class=move || {
+                                                if filters().show_nsfw { "bi bi-check-square" } else { "bi bi-square" }
+                                            }></i>
+                                            " "
+                                            {t(cx, "communities-nsfw")}
+                                        </a>
+                                    }
+                                })}
+                        </div>
+                    </div>
+                </div>
+            </div>
             <Transition fallback=move || {
                 // Handles the loading screen while waiting for a reply from the API
                 view! { cx,
                     <div class="d-flex align-items-center">
                         <h1>
-                            Loading...
+                            {t(cx, "loading")}
                         </h1>
                         <div class="spinner-grow ms-auto" role="status" aria-hidden="true"></div>
                     </div>
@@ -66,13 +349,13 @@ pub fn Communities(cx: Scope) -> impl IntoView {
                         .read(cx)
                         .map(|res| match res {
                             None => {
-                                view! { cx, <div>{format!("{err_msg}")}</div> }
+                                view! { cx, <div>{err_msg}</div> }
                             }
                             Some(res) => {
 
                                 view! { cx,
                                     <div class="row">
-                                        <CommunitiesList communities=res.communities/>
+                                        <CommunitiesList communities=res filters=Signal::derive(cx, filters)/>
                                     </div>
                                 }
                             }
@@ -87,32 +370,45 @@ pub fn Communities(cx: Scope) -> impl IntoView {
 }
 
 #[component]
-pub fn CommunitiesList(cx: Scope, communities: Vec<CommunityView>) -> impl IntoView {
+fn CommunitiesList(
+    cx: Scope,
+    communities: Vec<CommunityView>,
+    filters: Signal<DirectoryFilters>,
+) -> impl IntoView {
+    // A column header that sorts the directory when clicked, with an arrow on the column it is sorted by.
+    // The label is the id of its message in the locale files.
+    let sort_header = move |label: &'static str, sort: CommunitySort| {
+        view! { cx,
+            <th class="text-center text-nowrap" scope="col">
+                <a
+                    class="link-body-emphasis link-underline link-underline-opacity-0"
+                    href=move || filters.get().with_sort(sort).href()
+                >
+                    {t(cx, label)}
+                    {move || (filters.get().sort == sort).then(|| view! { cx, <i aria-hidden="true" class="bi bi-arrow-down-short"></i> })}
+                </a>
+            </th>
+        }
+    };
+
     view! { cx,
         <div class="col-md-12">
-            <div class="container">
-                <table class="table table-dark table-striped">
+            <div class="container table-responsive">
+                <table class="table table-striped align-middle">
                     <thead>
                         <tr>
-                            <th colspan="3">
-                                <h3>"Communites List"</h3>
+                            <th colspan="7">
+                                <h3>{t(cx, "communities-title")}</h3>
                             </th>
                         </tr>
                         <tr>
-                            <th scope="col">"Name"</th>
-                            <th class="text-center" scope="col">
-                                "Subscribers"
-                            </th>
-                            <th class="text-center" scope="col">
-                                "Monthly Active Users"
-                            </th>
-                            <th class="text-center" scope="col">
-                                "Posts"
-                            </th>
-                            <th class="text-center" scope="col">
-                                "Comments"
-                            </th>
-                            <th scope="col">"Subscription"</th>
+                            <th scope="col">{t(cx, "communities-name")}</th>
+                            {sort_header("communities-subscribers", CommunitySort::Subscribers)}
+                            {sort_header("communities-monthly-active-users", CommunitySort::MonthlyActiveUsers)}
+                            {sort_header("communities-posts", CommunitySort::Posts)}
+                            {sort_header("communities-comments", CommunitySort::Comments)}
+                            {sort_header("communities-new", CommunitySort::New)}
+                            <th scope="col">{t(cx, "communities-subscription")}</th>
                         </tr>
                     </thead>
                     <CommunitiesListItem communities=communities/>
@@ -124,10 +420,24 @@ pub fn CommunitiesList(cx: Scope, communities: Vec<CommunityView>) -> impl IntoV
 
 #[component]
 pub fn CommunitiesListItem(cx: Scope, communities: Vec<CommunityView>) -> impl IntoView {
+    if communities.is_empty() {
+        return view! { cx,
+            <tbody>
+                <tr>
+                    <td colspan="7" class="text-center text-body-secondary">
+                        {t(cx, "communities-no-matches")}
+                    </td>
+                </tr>
+            </tbody>
+        };
+    }
+
     view! { cx,
         <tbody>
             {communities
                 .into_iter()
+                // Instances that don't allow NSFW content leave those communities out of the list entirely
+                .filter(|item| !is_nsfw_forbidden(item.community.nsfw))
                 .map(|item| {
                     let community_avatar = match item.community.icon {
                         Some(_) => item.community.icon,
@@ -135,21 +445,39 @@ pub fn CommunitiesListItem(cx: Scope, communities: Vec<CommunityView>) -> impl I
                             Option::Some("/static/default_assets/default-community.png".to_string())
                         }
                     };
+                    let community_avatar = proxied_image(community_avatar, Some(64));
+                    let excerpt = description_excerpt(&item.community.description);
+                    let published = item
+                        .community
+                        .published
+                        .split('T')
+                        .next()
+                        .unwrap_or_default()
+                        .to_string();
 
                     view! { cx,
                         <tr>
                             <td>
-                                <A href=format!("/community/{}", item.community.name)>
-                                    <img
-                                        src=community_avatar
-                                        alt="mdo"
-                                        width="32"
-                                        height="32"
-                                        class="rounded"
-                                    />
-                                    "  "
-                                    {format!("{}", item.community.title)}
-                                </A>
+                                <div class="d-flex align-items-center gap-2">
+                                    <NsfwBlur nsfw=item.community.nsfw compact=true>
+                                        <img
+                                            src=community_avatar.clone()
+                                            alt=""
+                                            width="32"
+                                            height="32"
+                                            class="rounded flex-shrink-0"
+                                            loading="lazy"
+                                        />
+                                    </NsfwBlur>
+                                    <div>
+                                        <A href=format!("/community/{}", item.community.name)>
+                                            {item.community.title.clone()}
+                                        </A>
+                                        {item.community.nsfw.then(|| view! { cx, <NsfwBadge/> })}
+                                        {(!excerpt.is_empty())
+                                            .then(|| view! { cx, <div class="small text-body-secondary">{excerpt}</div> })}
+                                    </div>
+                                </div>
                             </td>
                             <td class="text-center">{format!("{}", item.counts.subscribers)}</td>
                             <td class="text-center">
@@ -157,8 +485,14 @@ pub fn CommunitiesListItem(cx: Scope, communities: Vec<CommunityView>) -> impl I
                             </td>
                             <td class="text-center">{format!("{}", item.counts.posts)}</td>
                             <td class="text-center">{format!("{}", item.counts.comments)}</td>
-                            // Needs to be dynamic and allow for subscribing from here?
-                            <td>{format!("{:?}", item.subscribed)}</td>
+                            <td class="text-center text-nowrap">{published}</td>
+                            <td>
+                                <SubscribeButton
+                                    community_id=item.community.id
+                                    subscribed=item.subscribed
+                                    small=true
+                                />
+                            </td>
                         </tr>
                     }
                 })
@@ -1,11 +1,19 @@
 use leptos::*;
 use leptos_router::*;
 
+use crate::api::community::get_community;
 use crate::api::structs::router_endpoints;
-use crate::components::{feed::Feed, sidecards::community::Sidecard};
+use crate::api::structs::*;
+use crate::api::*;
+use crate::components::{
+    community_header::CommunityHeader,
+    feed::Feed,
+    i18n::t,
+    seo::{site_url, PageHead, PageMeta},
+    sidecards::community::Sidecard,
+};
 
 // TODO - community.rs:
-// Sidecard component still needs to be built
 // Real styling with Bootstrap
 // Ensure mobile layout works as expected
 
@@ -13,18 +21,62 @@ use crate::components::{feed::Feed, sidecards::community::Sidecard};
 
 #[component]
 pub fn Community(cx: Scope) -> impl IntoView {
+    let params = use_params_map(cx);
+    let community_name = move || {
+        params
+            .with(|params| params.get("community_name").cloned())
+            .unwrap_or_default()
+    };
+
+    // Fetched once for the header and the sidecard, and again only when moving to another community
+    let community = create_resource(cx, community_name, move |community_name| async move {
+        let url_constructor = ApiUrlConstructor {
+            endpoint: api_endpoints::GetEndpoint::GET_COMMUNITY.to_string(),
+            id: None,
+            params: None,
+        };
+
+        // The auth token fills in whether the user is subscribed
+        let get_form = GetCommunity {
+            auth: get_auth_token(cx),
+            id: None,
+            name: Some(community_name),
+        };
+
+        get_community(cx, &api_url_builder(cx, url_constructor, get_form))
+            .await
+            .ok()
+    });
+
     view! { cx,
         <div class="container overflow-hidden">
+            <br/>
+            <Transition fallback=move || ()>
+                {move || {
+                    community
+                        .read(cx)
+                        .flatten()
+                        .map(|community| {
+                            let meta = PageMeta::for_community(
+                                &community.community_view.community,
+                                &site_url(),
+                            );
+                            view! { cx,
+                                <PageHead meta=meta/>
+                                <CommunityHeader community=community/>
+                            }
+                        })
+                }}
+            </Transition>
             <div class="row gx-4">
                 // Feed Column
                 <div class="col-md-9">
-                    <br/>
                     <Transition fallback=move || {
                         // Handles the loading screen while waiting for a reply from the API
                         view! { cx,
                             <div class="d-flex align-items-center">
                                 <h1>
-                                    Loading...
+                                    {t(cx, "loading")}
                                 </h1>
                                 <div
                                     class="spinner-grow ms-auto"
@@ -40,13 +92,12 @@ pub fn Community(cx: Scope) -> impl IntoView {
 
                 // Sidecard Column
                 <div class="col-12 col-md-3">
-                    <br/>
                     <Transition fallback=move || {
                         // Handles the loading screen while waiting for a reply from the API
                         view! { cx,
                             <div class="d-flex align-items-center">
                                 <h1>
-                                    Loading...
+                                    {t(cx, "loading")}
                                 </h1>
                                 <div
                                     class="spinner-grow ms-auto"
@@ -56,7 +107,7 @@ pub fn Community(cx: Scope) -> impl IntoView {
                             </div>
                         }
                     }>
-                        <Sidecard/>
+                        <Sidecard sidebar=community/>
                     </Transition>
                 </div>
             </div>
@@ -0,0 +1,408 @@
+use leptos::*;
+
+use crate::api::custom_emoji::{create_custom_emoji, delete_custom_emoji, edit_custom_emoji};
+use crate::api::structs::*;
+use crate::api::*;
+use crate::components::admin::get_admin_user;
+use crate::components::custom_emoji::{
+    group_by_category, refetch_custom_emojis, use_custom_emojis,
+};
+use crate::components::i18n::{t, translate};
+use crate::components::image_upload::ImageUpload;
+
+// TODO - custom_emojis.rs:
+// Improve styling, especially on mobile
+
+// The values entered into the custom emoji form, which are shared between creating and editing an emoji
+#[derive(Clone, Debug, Default)]
+struct CustomEmojiInput {
+    shortcode: String,
+    image_url: String,
+    alt_text: String,
+    category: String,
+    keywords: String,
+}
+
+// Keywords are entered as a comma separated list, and sent to Lemmy as a list of words
+fn split_keywords(keywords: &str) -> Vec<String> {
+    keywords
+        .split(',')
+        .map(|keyword| keyword.trim().to_string())
+        .filter(|keyword| !keyword.is_empty())
+        .collect()
+}
+
+/// The admin-only page for adding, editing, and deleting the site's custom emojis.
+#[component]
+pub fn ManageCustomEmojis(cx: Scope) -> impl IntoView {
+    let admin_user = get_admin_user(cx);
+
+    let err_msg = t(cx, "error-custom-emojis-admins-only");
+
+    view! { cx,
+        <div class="container overflow-hidden">
+            <Transition fallback=move || {
+                // Handles the loading screen while waiting for a reply from the API
+                view! { cx,
+                    <div class="d-flex align-items-center">
+                        <h1>
+                            {t(cx, "loading")}
+                        </h1>
+                        <div class="spinner-grow ms-auto" role="status" aria-hidden="true"></div>
+                    </div>
+                }
+            }>
+                {move || {
+                    admin_user
+                        .read(cx)
+                        .map(|admin| match admin {
+                            None => view! { cx, <div>{err_msg}</div> }.into_view(cx),
+                            Some(_) => {
+                                view! { cx,
+                                    <div class="row">
+                                        <div class="col-md-12">
+                                            <br/>
+                                            <div class="card">
+                                                <div class="card-header">
+                                                    <h5 class="card-title">{t(cx, "emojis-add-title")}</h5>
+                                                </div>
+                                                <div class="card-body">
+                                                    <CustomEmojiForm/>
+                                                </div>
+                                            </div>
+                                            <br/>
+                                            <CustomEmojiCategories/>
+                                        </div>
+                                    </div>
+                                }
+                                    .into_view(cx)
+                            }
+                        })
+                }}
+
+            </Transition>
+        </div>
+    }
+}
+
+// A table of the existing custom emojis for each category
+#[component]
+fn CustomEmojiCategories(cx: Scope) -> impl IntoView {
+    move || {
+        let custom_emojis = use_custom_emojis(cx);
+        if custom_emojis.is_empty() {
+            return view! { cx, <p>{t(cx, "emojis-none")}</p> }
+                .into_view(cx);
+        }
+
+        group_by_category(custom_emojis)
+            .into_iter()
+            .map(|(category, custom_emojis)| {
+                view! { cx,
+                    <table class="table table-dark table-striped align-middle">
+                        <thead>
+                            <tr>
+                                <th colspan="5">
+                                    <h3>{category}</h3>
+                                </th>
+                            </tr>
+                            <tr>
+                                <th scope="col">{t(cx, "emojis-emoji")}</th>
+                                <th scope="col">{t(cx, "emojis-shortcode")}</th>
+                                <th scope="col">{t(cx, "emojis-alt-text")}</th>
+                                <th scope="col">{t(cx, "emojis-keywords")}</th>
+                                <th scope="col">{t(cx, "emojis-manage")}</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {custom_emojis
+                                .into_iter()
+                                .map(|custom_emoji| view! { cx, <CustomEmojiRow custom_emoji=custom_emoji/> })
+                                .collect_view(cx)}
+                        </tbody>
+                    </table>
+                }
+            })
+            .collect_view(cx)
+    }
+}
+
+// A single custom emoji, with the buttons for editing and deleting it
+#[component]
+fn CustomEmojiRow(cx: Scope, custom_emoji: CustomEmojiView) -> impl IntoView {
+    let (editing, set_editing) = create_signal(cx, false);
+    let (confirm_delete, set_confirm_delete) = create_signal(cx, false);
+
+    let emoji_id = custom_emoji.custom_emoji.id;
+
+    let delete_action = create_action(cx, move |_: &()| async move {
+        let url_constructor = ApiUrlConstructor {
+            endpoint: api_endpoints::PostEndpoint::DELETE_CUSTOM_EMOJI.to_string(),
+            id: None,
+            params: None,
+        };
+
+        let form = DeleteCustomEmoji {
+            auth: get_auth_token(cx).unwrap_or_default(),
+            id: emoji_id,
+        };
+
+        let result = delete_custom_emoji(cx, &api_post_url_builder(url_constructor), &form)
+            .await
+            .map_err(|err| err.to_string());
+        if result.is_ok() {
+            refetch_custom_emojis(cx);
+        }
+        result
+    });
+    let delete_result = delete_action.value();
+
+    let keywords = custom_emoji
+        .keywords
+        .iter()
+        .map(|keyword| keyword.keyword.clone())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    view! { cx,
+        <tr>
+            <td>
+                <img
+                    src=custom_emoji.custom_emoji.image_url.clone()
+                    alt=custom_emoji.custom_emoji.alt_text.clone()
+                    width="32"
+                    height="32"
+                />
+            </td>
+            <td>{format!(":{}:", custom_emoji.custom_emoji.shortcode)}</td>
+            <td>{custom_emoji.custom_emoji.alt_text.clone()}</td>
+            <td>{keywords}</td>
+            <td>
+                <button
+                    type="button"
+                    class="btn btn-outline-secondary btn-sm me-1"
+                    on:click=move |_| set_editing.update(|editing| *editing = !*editing)
+                >
+                    {t(cx, "edit")}
+                </button>
+                {move || {
+                    if confirm_delete.get() {
+                        view! { cx,
+                            <button
+                                type="button"
+                                class="btn btn-danger btn-sm"
+                                on:click=move |_| delete_action.dispatch(())
+                            >
+                                {t(cx, "confirm-delete")}
+                            </button>
+                        }
+                    } else {
+                        view! { cx,
+                            <button
+                                type="button"
+                                class="btn btn-outline-danger btn-sm"
+                                on:click=move |_| set_confirm_delete.set(true)
+                            >
+                                {t(cx, "delete")}
+                            </button>
+                        }
+                    }
+                }}
+                {move || match delete_result.get() {
+                    Some(Err(error)) => {
+                        view! { cx, <div class="alert alert-warning mt-2 mb-0">{error}</div> }
+                            .into_view(cx)
+                    }
+                    _ => ().into_view(cx),
+                }}
+            </td>
+        </tr>
+        {move || {
+            if editing.get() {
+                let custom_emoji = custom_emoji.clone();
+                view! { cx,
+                    <tr>
+                        <td colspan="5">
+                            <CustomEmojiForm custom_emoji=custom_emoji/>
+                        </td>
+                    </tr>
+                }
+                    .into_view(cx)
+            } else {
+                ().into_view(cx)
+            }
+        }}
+    }
+}
+
+// The form for creating a new custom emoji, or editing an existing one if it is given
+#[component]
+fn CustomEmojiForm(
+    cx: Scope,
+    #[prop(optional)] custom_emoji: Option<CustomEmojiView>,
+) -> impl IntoView {
+    let emoji_id = custom_emoji.as_ref().map(|view| view.custom_emoji.id);
+    let initial = custom_emoji
+        .map(|view| CustomEmojiInput {
+            shortcode: view.custom_emoji.shortcode,
+            image_url: view.custom_emoji.image_url,
+            alt_text: view.custom_emoji.alt_text,
+            category: view.custom_emoji.category,
+            keywords: view
+                .keywords
+                .into_iter()
+                .map(|keyword| keyword.keyword)
+                .collect::<Vec<_>>()
+                .join(", "),
+        })
+        .unwrap_or_default();
+
+    let (shortcode, set_shortcode) = create_signal(cx, initial.shortcode);
+    let image_url = create_rw_signal(cx, initial.image_url);
+    let (alt_text, set_alt_text) = create_signal(cx, initial.alt_text);
+    let (category, set_category) = create_signal(cx, initial.category);
+    let (keywords, set_keywords) = create_signal(cx, initial.keywords);
+
+    let save_action = create_action(cx, move |input: &CustomEmojiInput| {
+        let input = input.clone();
+        async move {
+            let auth = get_auth_token(cx).unwrap_or_default();
+
+            let result = match emoji_id {
+                Some(id) => {
+                    let url_constructor = ApiUrlConstructor {
+                        endpoint: api_endpoints::PutEndpoint::EDIT_CUSTOM_EMOJI.to_string(),
+                        id: None,
+                        params: None,
+                    };
+
+                    let form = EditCustomEmoji {
+                        alt_text: input.alt_text,
+                        auth,
+                        category: input.category,
+                        id,
+                        image_url: input.image_url,
+                        keywords: split_keywords(&input.keywords),
+                    };
+
+                    edit_custom_emoji(cx, &api_post_url_builder(url_constructor), &form)
+                        .await
+                        .map(|_| translate(cx, "emojis-saved"))
+                }
+                None => {
+                    let url_constructor = ApiUrlConstructor {
+                        endpoint: api_endpoints::PostEndpoint::CREATE_CUSTOM_EMOJI.to_string(),
+                        id: None,
+                        params: None,
+                    };
+
+                    let form = CreateCustomEmoji {
+                        alt_text: input.alt_text,
+                        auth,
+                        category: input.category,
+                        image_url: input.image_url,
+                        keywords: split_keywords(&input.keywords),
+                        shortcode: input.shortcode.trim().trim_matches(':').to_string(),
+                    };
+
+                    create_custom_emoji(cx, &api_post_url_builder(url_constructor), &form)
+                        .await
+                        .map(|_| translate(cx, "emojis-added"))
+                }
+            };
+
+            if result.is_ok() {
+                refetch_custom_emojis(cx);
+            }
+            result.map_err(|err| err.to_string())
+        }
+    });
+    let pending = save_action.pending();
+    let result = save_action.value();
+
+    view! { cx,
+        <form on:submit=move |ev| {
+            ev.prevent_default();
+            save_action
+                .dispatch(CustomEmojiInput {
+                    shortcode: shortcode.get(),
+                    image_url: image_url.get(),
+                    alt_text: alt_text.get(),
+                    category: category.get(),
+                    keywords: keywords.get(),
+                })
+        }>
+            <div class="row g-2">
+                <div class="col-md-4">
+                    <label class="form-label">{t(cx, "emojis-shortcode")}</label>
+                    <input
+                        type="text"
+                        class="form-control"
+                        placeholder="wolf_howl"
+                        required
+                        // Lemmy doesn't allow the shortcode of an existing emoji to be changed
+                        disabled=emoji_id.is_some()
+                        prop:value=move || shortcode.get()
+                        on:input=move |ev| set_shortcode.set(event_target_value(&ev))
+                    />
+                </div>
+                <div class="col-md-8">
+                    <label class="form-label">{t(cx, "emojis-image-url")}</label>
+                    <ImageUpload text=image_url>
+                        <input
+                            type="url"
+                            class="form-control"
+                            placeholder="https://"
+                            required
+                            prop:value=move || image_url.get()
+                            on:input=move |ev| image_url.set(event_target_value(&ev))
+                        />
+                    </ImageUpload>
+                </div>
+                <div class="col-md-4">
+                    <label class="form-label">{t(cx, "emojis-category")}</label>
+                    <input
+                        type="text"
+                        class="form-control"
+                        required
+                        prop:value=move || category.get()
+                        on:input=move |ev| set_category.set(event_target_value(&ev))
+                    />
+                </div>
+                <div class="col-md-4">
+                    <label class="form-label">{t(cx, "emojis-alt-text")}</label>
+                    <input
+                        type="text"
+                        class="form-control"
+                        required
+                        prop:value=move || alt_text.get()
+                        on:input=move |ev| set_alt_text.set(event_target_value(&ev))
+                    />
+                </div>
+                <div class="col-md-4">
+                    <label class="form-label">{t(cx, "emojis-keywords-input")}</label>
+                    <input
+                        type="text"
+                        class="form-control"
+                        prop:value=move || keywords.get()
+                        on:input=move |ev| set_keywords.set(event_target_value(&ev))
+                    />
+                </div>
+            </div>
+            <button type="submit" class="btn btn-primary mt-3" disabled=move || pending.get()>
+                {if emoji_id.is_some() { t(cx, "emojis-save") } else { t(cx, "emojis-add") }}
+            </button>
+            {move || match result.get() {
+                Some(Ok(message)) => {
+                    view! { cx, <div class="alert alert-success mt-3">{message}</div> }
+                        .into_view(cx)
+                }
+                Some(Err(error)) => {
+                    view! { cx, <div class="alert alert-warning mt-3">{error}</div> }
+                        .into_view(cx)
+                }
+                None => ().into_view(cx),
+            }}
+        </form>
+    }
+}
@@ -1,44 +1,263 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
 use leptos::*;
 use leptos_router::*;
+use serde::{Deserialize, Serialize};
 
+use crate::api::community::list_communities;
 use crate::api::federation::get_federated_instances;
 use crate::api::structs::*;
 use crate::api::*;
+use crate::components::i18n::{t, t_count, translate, translate_with};
+use crate::components::nsfw::*;
+use crate::components::pagination::{PageCursors, Pagination};
+use crate::components::subscribe_button::SubscribeButton;
+use crate::image_proxy::proxied_image;
+use crate::components::time_ago::TimeAgo;
+use crate::timestamps::seconds_since;
 
 // TODO - federated_instances.rs:
 // Improve styling to deal with the small overflow that happens with the tables between shifting from 2 columns to 1 column
 // Potentially move logic from the page to an actual component to maintain seperation of page from components (Optional)
+// Show users from an instance on its page as well as communities
+
+// The directory of instances this one federates with. The search, sorting, and filters are kept in the query string, so a filtered directory can be linked to.
+
+// Whether the federated instances or the blocked ones are shown
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+enum InstanceList {
+    #[default]
+    Federated,
+    Blocked,
+}
+
+impl InstanceList {
+    fn query_value(&self) -> &'static str {
+        match self {
+            InstanceList::Federated => "federated",
+            InstanceList::Blocked => "blocked",
+        }
+    }
+}
+
+// The columns the directory can be sorted by
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+enum InstanceSort {
+    #[default]
+    Domain,
+    Software,
+    Version,
+    LastSeen,
+}
+
+impl InstanceSort {
+    const ALL: [InstanceSort; 4] = [
+        InstanceSort::Domain,
+        InstanceSort::Software,
+        InstanceSort::Version,
+        InstanceSort::LastSeen,
+    ];
+
+    fn label(&self, cx: Scope) -> String {
+        match self {
+            InstanceSort::Domain => translate(cx, "instances-domain"),
+            InstanceSort::Software => translate(cx, "instances-software"),
+            InstanceSort::Version => translate(cx, "instances-version"),
+            InstanceSort::LastSeen => translate(cx, "instances-last-seen"),
+        }
+    }
+
+    fn query_value(&self) -> &'static str {
+        match self {
+            InstanceSort::Domain => "domain",
+            InstanceSort::Software => "software",
+            InstanceSort::Version => "version",
+            InstanceSort::LastSeen => "seen",
+        }
+    }
+}
+
+// Everything that decides which instances are shown
+#[derive(Clone, Debug, Default, PartialEq)]
+struct InstanceFilters {
+    list: InstanceList,
+    sort: InstanceSort,
+    // Only instances running this software, by its normalised name
+    software: Option<String>,
+    search: String,
+}
+
+#[derive(Serialize)]
+struct InstanceQuery<'a> {
+    list: &'static str,
+    sort: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    software: Option<&'a str>,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    q: &'a str,
+}
+
+impl InstanceFilters {
+    fn from_query(query: &ParamsMap) -> Self {
+        InstanceFilters {
+            list: match query.get("list").map(String::as_str) {
+                Some("blocked") => InstanceList::Blocked,
+                _ => InstanceList::Federated,
+            },
+            sort: query
+                .get("sort")
+                .and_then(|sort| InstanceSort::ALL.into_iter().find(|s| s.query_value() == sort.as_str()))
+                .unwrap_or_default(),
+            software: query.get("software").cloned().filter(|software| !software.is_empty()),
+            search: query.get("q").map(|q| q.trim().to_lowercase()).unwrap_or_default(),
+        }
+    }
+
+    fn href(&self) -> String {
+        serde_html_form::to_string(InstanceQuery {
+            list: self.list.query_value(),
+            sort: self.sort.query_value(),
+            software: self.software.as_deref(),
+            q: &self.search,
+        })
+        .map(|query| format!("?{}", query))
+        .unwrap_or_default()
+    }
+
+    fn with_list(&self, list: InstanceList) -> Self {
+        InstanceFilters {
+            list,
+            software: None,
+            ..self.clone()
+        }
+    }
+
+    fn with_sort(&self, sort: InstanceSort) -> Self {
+        InstanceFilters {
+            sort,
+            ..self.clone()
+        }
+    }
+
+    fn with_software(&self, software: Option<String>) -> Self {
+        InstanceFilters {
+            software,
+            ..self.clone()
+        }
+    }
+
+    // Filters and sorts the instances in one list
+    fn apply(&self, instances: Vec<Instance>) -> Vec<Instance> {
+        let mut instances = instances
+            .into_iter()
+            .filter(|instance| {
+                self.software
+                    .as_ref()
+                    .map_or(true, |software| &software_name(instance) == software)
+            })
+            .filter(|instance| self.search.is_empty() || instance.domain.to_lowercase().contains(&self.search))
+            .collect::<Vec<_>>();
+
+        instances.sort_by(|a, b| a.domain.cmp(&b.domain));
+        match self.sort {
+            InstanceSort::Domain => {}
+            InstanceSort::Software => instances.sort_by_key(software_name),
+            // Versions are compared number by number, so 0.18.10 comes after 0.18.9
+            InstanceSort::Version => instances.sort_by_key(|instance| Reverse(version_key(instance))),
+            // The most recently seen instances come first, and ones that were never seen come last
+            InstanceSort::LastSeen => instances.sort_by_key(|instance| last_seen(instance).unwrap_or(i64::MAX)),
+        }
+
+        instances
+    }
+}
+
+// The software an instance runs, in lowercase so "Lemmy" and "lemmy" are grouped together
+fn software_name(instance: &Instance) -> String {
+    instance
+        .software
+        .as_deref()
+        .map(|software| software.trim().to_lowercase())
+        .filter(|software| !software.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn version_key(instance: &Instance) -> Vec<u32> {
+    instance
+        .version
+        .as_deref()
+        .unwrap_or_default()
+        .split(|c: char| !c.is_ascii_digit())
+        .filter_map(|part| part.parse().ok())
+        .collect()
+}
+
+// When Lemmy last heard from an instance, which is when it was first seen if it was never updated
+fn last_seen_time(instance: &Instance) -> String {
+    instance
+        .updated
+        .clone()
+        .unwrap_or_else(|| instance.published.clone())
+}
+
+// How many seconds ago Lemmy last heard from an instance
+fn last_seen(instance: &Instance) -> Option<i64> {
+    seconds_since(&last_seen_time(instance))
+}
+
+// The dot next to the last seen time. Instances that haven't been heard from in a while may be down or gone.
+fn freshness_class(seconds: Option<i64>) -> &'static str {
+    match seconds {
+        Some(seconds) if seconds < 86400 => "text-success",
+        Some(seconds) if seconds < 7 * 86400 => "text-warning",
+        _ => "text-danger",
+    }
+}
+
+// The instances that are federated with, which is the allowlist on instances that have one and the linked instances otherwise
+fn federated_list(instances: &FederatedInstances) -> &Vec<Instance> {
+    if instances.allowed.is_empty() {
+        &instances.linked
+    } else {
+        &instances.allowed
+    }
+}
+
+fn fetch_federated_instances(cx: Scope) -> Resource<(), Option<FederatedInstances>> {
+    create_resource(
+        cx,
+        || (),
+        move |_| async move {
+            // This constructs the proper API URL for GetFederatedInstances
+            let url_constructor = ApiUrlConstructor {
+                endpoint: api_endpoints::GetEndpoint::GET_FEDERATED_INSTANCES.to_string(),
+                id: None,
+                params: None,
+            };
+
+            // This assembles the GetFederatedInstances request form
+            let get_form = GetFederatedInstances { auth: None };
+
+            // This is where the API is called for GetFederatedInstances and the GetFederatedInstancesResponse is returned
+            get_federated_instances(cx, &api_url_builder(cx, url_constructor, get_form))
+                .await
+                .ok()
+                .and_then(|res| res.federated_instances)
+        },
+    )
+}
 
 /// The component box for federated instances page.
 #[component]
 pub fn FederatedInstancesList(cx: Scope) -> impl IntoView {
-    // Technically not necessary for this page, but not sure how to use `create_resource` without it?
     let query = use_query_map(cx);
-    let page = move || {
-        query
-            .with(|q| q.get("page").and_then(|page| page.parse::<i32>().ok()))
-            .unwrap_or(1)
-    };
+    let filters = move || query.with(InstanceFilters::from_query);
 
-    // Variable that holds the returned GetFederatedInstancesResponse from the API
-    let instances = create_resource(cx, page, move |_page| async move {
-        // This constructs the proper API URL for GetFederatedInstances
-        let url_constructor = ApiUrlConstructor {
-            endpoint: api_endpoints::GetEndpoint::GET_FEDERATED_INSTANCES.to_string(),
-            id: None,
-            params: None,
-        };
+    // Variable that holds the returned FederatedInstances from the API, which are filtered and sorted here since Lemmy returns them all at once
+    let instances = fetch_federated_instances(cx);
 
-        // This assembles the GetFederatedInstances request form
-        let get_form = GetFederatedInstances { auth: None };
-
-        // This is where the API is called for GetFederatedInstances and the GetFederatedInstancesResponse is returned
-        get_federated_instances(cx, &api_url_builder(cx, url_constructor, get_form))
-            .await
-            .ok()
-    });
-
-    let err_msg = "Error loading this page: ";
+    let err_msg = t(cx, "error-loading-page");
 
     view! { cx,
         <div class="container overflow-hidden">
@@ -47,7 +266,7 @@ pub fn FederatedInstancesList(cx: Scope) -> impl IntoView {
                 view! { cx,
                     <div class="d-flex align-items-center">
                         <h1>
-                            Loading...
+                            {t(cx, "loading")}
                         </h1>
                         <div class="spinner-grow ms-auto" role="status" aria-hidden="true"></div>
                     </div>
@@ -58,18 +277,13 @@ pub fn FederatedInstancesList(cx: Scope) -> impl IntoView {
                         .read(cx)
                         .map(|res| match res {
                             None => {
-                                view! { cx, <div>{format!("{err_msg}")}</div> }
+                                view! { cx, <div>{err_msg}</div> }.into_view(cx)
                             }
                             Some(res) => {
-
                                 view! { cx,
-                                    <div class="row">
-                                        <FederatedInstancesTables instances=res
-                                            .federated_instances
-                                            .unwrap()
-                                            .into()/>
-                                    </div>
+                                    <FederatedInstancesDirectory instances=res filters=filters()/>
                                 }
+                                    .into_view(cx)
                             }
                         })
                 }}
@@ -79,85 +293,185 @@ pub fn FederatedInstancesList(cx: Scope) -> impl IntoView {
     }
 }
 
-// The component for crafting the two core tables for the federated instances items
+// The tabs, software summary, search, and table of instances
 #[component]
-fn FederatedInstancesTables(
+fn FederatedInstancesDirectory(
     cx: Scope,
-    instances: MaybeSignal<FederatedInstances>,
+    instances: FederatedInstances,
+    filters: InstanceFilters,
 ) -> impl IntoView {
+    let list = match filters.list {
+        InstanceList::Federated => federated_list(&instances).clone(),
+        InstanceList::Blocked => instances.blocked.clone(),
+    };
+
+    // How many instances run each piece of software, most common first
+    let mut software_counts = BTreeMap::<String, usize>::new();
+    for instance in &list {
+        *software_counts.entry(software_name(instance)).or_default() += 1;
+    }
+    let mut software_counts = software_counts.into_iter().collect::<Vec<_>>();
+    software_counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    let tab = |label: String, list: InstanceList, count: usize| {
+        view! { cx,
+            <li class="nav-item">
+                <a
+                    class="nav-link"
+                    class:active={filters.list == list}
+                    href=filters.with_list(list).href()
+                >
+                    {label}
+                    " "
+                    <span class="badge text-bg-secondary">{count}</span>
+                </a>
+            </li>
+        }
+    };
+
+    let shown = filters.apply(list);
+
+    // The search form's children are rendered by Form, so they get their own copies of the filters
+    let list_value = filters.list.query_value();
+    let sort_value = filters.sort.query_value();
+    let software_value = filters.software.clone();
+    let search_value = filters.search.clone();
+
     view! { cx,
-        <div class="col-lg-6 col-md-12">
-            <div class="container">
-                <table class="table table-dark table-striped">
-                    <thead>
-                        <tr>
-                            <th colspan="3">
-                                <h3>"Federated Instances"</h3>
-                            </th>
-                        </tr>
-                        <tr>
-                            <th scope="col">"Domain Name"</th>
-                            <th scope="col">"Software"</th>
-                            <th scope="col">"Version"</th>
-                        </tr>
-                    </thead>
-                    // Check to see if the instance is using an allowed list or just has linked instances, as these should be mutually exclusive
-                    {if instances.get().allowed.is_empty() {
-                        view! { cx, <Federated federated_instances=instances.get().linked/> }
-                    } else {
-                        view! { cx, <Federated federated_instances=instances.get().allowed/> }
-                    }}
-
-                </table>
+        <div class="card my-3">
+            <div class="card-header">
+                <div class="row g-2 align-items-center">
+                    <div class="col-md-6">
+                        <ul class="nav nav-tabs card-header-tabs">
+                            {tab(translate(cx, "instances-federated"), InstanceList::Federated, federated_list(&instances).len())}
+                            {tab(translate(cx, "instances-blocked"), InstanceList::Blocked, instances.blocked.len())}
+                        </ul>
+                    </div>
+                    <div class="col-md-6">
+                        // A plain GET form, so searching also works before the page has loaded its WASM
+                        <Form method="GET" action="">
+                            <input type="hidden" name="list" value=list_value/>
+                            <input type="hidden" name="sort" value=sort_value/>
+                            {software_value
+                                .clone()
+                                .map(|software| view! { cx, <input type="hidden" name="software" value=software/> })}
+                            <div class="input-group input-group-sm">
+                                <input
+                                    type="search"
+                                    class="form-control"
+                                    name="q"
+                                    placeholder=t(cx, "instances-search-placeholder")
+                                    aria-label=t(cx, "instances-search")
+                                    value=search_value.clone()
+                                />
+                                <button type="submit" class="btn btn-secondary" aria-label=t(cx, "search")>
+                                    <i aria-hidden="true" class="bi bi-search"></i>
+                                </button>
+                            </div>
+                        </Form>
+                    </div>
+                </div>
             </div>
-        </div>
-        <div class="col-lg-6 col-md-12">
-            <div class="container">
-                <table class="table table-dark table-striped">
-                    <thead>
-                        <tr>
-                            <th colspan="3">
-                                <h3>"Blocked Instances"</h3>
-                            </th>
-                        </tr>
-                        <tr>
-                            <th scope="col">"Domain Name"</th>
-                            <th scope="col">"Software"</th>
-                            <th scope="col">"Version"</th>
-                        </tr>
-                    </thead>
-                    <Blocked blocked_instances=instances.get().blocked/>
-                </table>
+            <div class="card-body d-flex flex-wrap gap-2">
+                <a
+                    class="btn btn-sm btn-outline-secondary"
+                    class:active=filters.software.is_none()
+                    href=filters.with_software(None).href()
+                >
+                    {t(cx, "instances-all-software")}
+                </a>
+                {software_counts
+                    .into_iter()
+                    .map(|(software, count)| {
+                        view! { cx,
+                            <a
+                                class="btn btn-sm btn-outline-secondary text-capitalize"
+                                class:active={filters.software.as_ref() == Some(&software)}
+                                href=filters.with_software(Some(software.clone())).href()
+                            >
+                                {software.clone()}
+                                " "
+                                <span class="badge text-bg-secondary">{count}</span>
+                            </a>
+                        }
+                    })
+                    .collect_view(cx)}
             </div>
         </div>
+        <div class="table-responsive">
+            <table class="table table-striped align-middle">
+                <thead>
+                    <tr>
+                        {InstanceSort::ALL
+                            .into_iter()
+                            .map(|sort| {
+                                view! { cx,
+                                    <th scope="col" class="text-nowrap">
+                                        <a
+                                            class="link-body-emphasis link-underline link-underline-opacity-0"
+                                            href=filters.with_sort(sort).href()
+                                        >
+                                            {move || sort.label(cx)}
+                                            {(filters.sort == sort)
+                                                .then(|| view! { cx, <i aria-hidden="true" class="bi bi-arrow-down-short"></i> })}
+                                        </a>
+                                    </th>
+                                }
+                            })
+                            .collect_view(cx)}
+                    </tr>
+                </thead>
+                <InstanceRows instances=shown/>
+            </table>
+        </div>
     }
 }
 
-// The component for building the federated instances table for allows and linked items
+// The rows of the instances table
 #[component]
-fn Federated(cx: Scope, federated_instances: Vec<Instance>) -> impl IntoView {
-    let mut sorted_instances = federated_instances;
-
-    sorted_instances.sort_by(|a, b| a.domain.cmp(&b.domain));
+fn InstanceRows(cx: Scope, instances: Vec<Instance>) -> impl IntoView {
+    if instances.is_empty() {
+        return view! { cx,
+            <tbody>
+                <tr>
+                    <td colspan="4" class="text-center text-body-secondary">
+                        {t(cx, "instances-no-matches")}
+                    </td>
+                </tr>
+            </tbody>
+        };
+    }
 
     view! { cx,
         <tbody>
-            {sorted_instances
+            {instances
                 .into_iter()
                 .map(|item| {
+                    let seen = last_seen(&item);
+                    let seen_time = last_seen_time(&item);
+                    let domain = item.domain.clone();
                     view! { cx,
                         <tr>
                             <td>
+                                <A href=format!("/instances/{}", item.domain)>{domain.clone()}</A>
+                                " "
                                 <a
+                                    class="link-secondary"
                                     rel="external"
                                     href=format!("https://{}", item.domain)
                                     target="_blank"
+                                    title=t(cx, "instances-visit")
                                 >
-                                    {format!("{}", item.domain)}
+                                    <i aria-hidden="true" class="bi bi-box-arrow-up-right"></i>
                                 </a>
                             </td>
-                            <td>{format!("{}", item.software.unwrap_or_default())}</td>
-                            <td>{format!("{}", item.version.unwrap_or_default())}</td>
+                            <td class="text-capitalize">{software_name(&item)}</td>
+                            <td>{item.version.unwrap_or_default()}</td>
+                            <td class="text-nowrap">
+                                <i aria-hidden="true" class=format!("bi bi-circle-fill small {}", freshness_class(seen))></i>
+                                " "
+                                <TimeAgo time=seen_time/>
+                            </td>
                         </tr>
                     }
                 })
@@ -166,35 +480,252 @@ fn Federated(cx: Scope, federated_instances: Vec<Instance>) -> impl IntoView {
     }
 }
 
-// The component for building the federated instances table for blocked items
+// How many communities are asked for from each page of Lemmy's list, and how many pages are looked through for one page here
+const COMMUNITY_LIST_LIMIT: i32 = 50;
+const MAX_COMMUNITY_LIST_PAGES: usize = 10;
+
+// The communities from an instance that were found for one page
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct InstanceCommunitiesPage {
+    communities: Vec<CommunityView>,
+    // The page of Lemmy's list the next page carries on from, or None once the list has ended
+    next_page: Option<i32>,
+}
+
+// The page for a single federated instance, with its details and the communities from it that this instance knows about
 #[component]
-fn Blocked(cx: Scope, blocked_instances: Vec<Instance>) -> impl IntoView {
-    let mut sorted_instances = blocked_instances;
+pub fn FederatedInstance(cx: Scope) -> impl IntoView {
+    let params = use_params_map(cx);
+    let domain = move || {
+        params
+            .with(|params| params.get("domain").cloned())
+            .unwrap_or_default()
+            .to_lowercase()
+    };
 
-    sorted_instances.sort_by(|a, b| a.domain.cmp(&b.domain));
+    let query = use_query_map(cx);
+    let page = move || {
+        query
+            .with(|q| q.get("page").and_then(|page| page.parse::<i32>().ok()))
+            .unwrap_or(1)
+    };
+
+    // The page of Lemmy's community list this page carries on from, which is where the page before it stopped
+    let cursor = move || query.with(|q| q.get("cursor").and_then(|cursor| cursor.parse::<i32>().ok()));
+
+    let instances = fetch_federated_instances(cx);
+
+    // Lemmy can't list communities by instance, so the list of all known communities is looked through a page at a time for the ones hosted on this domain.
+    // Pages of the list with none of them are skipped, up to MAX_COMMUNITY_LIST_PAGES at a time, so a page here is only empty when the list has ended or nothing turned up.
+    let communities = create_resource(
+        cx,
+        move || (domain(), page(), cursor()),
+        move |(domain, page, cursor)| async move {
+            let host = format!("https://{}/", domain);
+            // Pages that were never visited, such as one from a shared link, start from the same page of the list
+            let mut list_page = cursor.unwrap_or(page);
+
+            for _ in 0..MAX_COMMUNITY_LIST_PAGES {
+                let url_constructor = ApiUrlConstructor {
+                    endpoint: api_endpoints::GetEndpoint::LIST_COMMUNITIES.to_string(),
+                    id: None,
+                    params: None,
+                };
+
+                let get_form = ListCommunities {
+                    auth: get_auth_token(cx),
+                    limit: Some(COMMUNITY_LIST_LIMIT),
+                    page: Some(list_page),
+                    show_nsfw: None,
+                    sort: Some(SortType::TopAll),
+                    type_: Some(ListingType::All),
+                };
+
+                let listed = list_communities(cx, &api_url_builder(cx, url_constructor, get_form))
+                    .await
+                    .ok()?
+                    .communities;
+                let ended = listed.len() < COMMUNITY_LIST_LIMIT as usize;
+                let communities = listed
+                    .into_iter()
+                    .filter(|item| item.community.actor_id.to_lowercase().starts_with(&host))
+                    .filter(|item| !is_nsfw_forbidden(item.community.nsfw))
+                    .collect::<Vec<_>>();
+                list_page += 1;
+
+                if ended || !communities.is_empty() {
+                    return Some(InstanceCommunitiesPage {
+                        communities,
+                        next_page: (!ended).then_some(list_page),
+                    });
+                }
+            }
+
+            Some(InstanceCommunitiesPage {
+                communities: Vec::new(),
+                next_page: Some(list_page),
+            })
+        },
+    );
+
+    // Next carries on from where this page stopped, and Previous goes back to where the page before it started
+    let cursors = PageCursors::new(
+        cx,
+        Signal::derive(cx, move || {
+            communities
+                .read(cx)
+                .flatten()
+                .and_then(|communities| communities.next_page)
+                .map(|next_page| next_page.to_string())
+        }),
+    );
+    create_effect(cx, move |_| {
+        if let Some(cursor) = cursor() {
+            cursors.pages.update(|pages| {
+                pages.insert(page(), cursor.to_string());
+            });
+        }
+    });
 
     view! { cx,
-        <tbody>
-            {sorted_instances
+        <div class="container overflow-hidden">
+            <div class="my-3">
+                <A href="/instances">
+                    <i aria-hidden="true" class="bi bi-arrow-left"></i>
+                    " "
+                    {t(cx, "instances-all")}
+                </A>
+            </div>
+            <Transition fallback=move || view! { cx, <div class="spinner-grow" role="status"></div> }>
+                {move || {
+                    instances
+                        .read(cx)
+                        .map(|res| {
+                            let domain = domain();
+                            let found = res.and_then(|instances| {
+                                let blocked = instances.blocked.iter().any(|item| item.domain == domain);
+                                federated_list(&instances)
+                                    .iter()
+                                    .chain(instances.blocked.iter())
+                                    .find(|item| item.domain == domain)
+                                    .cloned()
+                                    .map(|item| (item, blocked))
+                            });
+                            match found {
+                                None => view! { cx,
+                                    <h2>{domain}</h2>
+                                    <p class="text-body-secondary">{t(cx, "instances-not-federated")}</p>
+                                }
+                                    .into_view(cx),
+                                Some((item, blocked)) => {
+                                    let seen = last_seen(&item);
+                                    view! { cx,
+                                        <h2>
+                                            {item.domain.clone()}
+                                            {blocked.then(|| view! { cx, <span class="badge text-bg-danger ms-2 fs-6">{t(cx, "instances-blocked-badge")}</span> })}
+                                        </h2>
+                                        <dl class="row">
+                                            <dt class="col-sm-3">{t(cx, "instances-software")}</dt>
+                                            <dd class="col-sm-9 text-capitalize">{software_name(&item)}</dd>
+                                            <dt class="col-sm-3">{t(cx, "instances-version")}</dt>
+                                            <dd class="col-sm-9">{item.version.clone().unwrap_or_else(|| translate(cx, "instances-unknown-version"))}</dd>
+                                            <dt class="col-sm-3">{t(cx, "instances-first-seen")}</dt>
+                                            <dd class="col-sm-9"><TimeAgo time=item.published.clone()/></dd>
+                                            <dt class="col-sm-3">{t(cx, "instances-last-seen")}</dt>
+                                            <dd class="col-sm-9">
+                                                <i aria-hidden="true" class=format!("bi bi-circle-fill small {}", freshness_class(seen))></i>
+                                                " "
+                                                <TimeAgo time=last_seen_time(&item)/>
+                                            </dd>
+                                        </dl>
+                                        <a class="btn btn-secondary" rel="external" href=format!("https://{}", item.domain) target="_blank">
+                                            {translate_with(cx, "instances-visit-domain", &[("domain", item.domain.clone().into())])}
+                                            " "
+                                            <i aria-hidden="true" class="bi bi-box-arrow-up-right"></i>
+                                        </a>
+                                    }
+                                        .into_view(cx)
+                                }
+                            }
+                        })
+                }}
+            </Transition>
+            <h3 class="mt-4">{t(cx, "instances-known-communities")}</h3>
+            <Transition fallback=move || view! { cx, <div class="spinner-grow" role="status"></div> }>
+                {move || {
+                    communities
+                        .read(cx)
+                        .map(|res| match res {
+                            None => view! { cx, <div>{t(cx, "instances-error-communities")}</div> }.into_view(cx),
+                            Some(res) if res.communities.is_empty() => {
+                                let message = match res.next_page {
+                                    Some(_) => t(cx, "instances-no-communities-looked-through"),
+                                    None if page() > 1 => t(cx, "instances-no-more-communities"),
+                                    None => t(cx, "instances-no-communities"),
+                                };
+                                view! { cx, <p class="text-body-secondary">{message}</p> }.into_view(cx)
+                            }
+                            Some(res) => view! { cx, <InstanceCommunities communities=res.communities/> }.into_view(cx),
+                        })
+                }}
+            </Transition>
+            <Pagination cursors=cursors/>
+        </div>
+    }
+}
+
+// The communities from one instance, as a list with their icons and subscribe buttons
+#[component]
+fn InstanceCommunities(cx: Scope, communities: Vec<CommunityView>) -> impl IntoView {
+    view! { cx,
+        <ul class="list-group mb-3">
+            {communities
                 .into_iter()
                 .map(|item| {
+                    let community_avatar = match item.community.icon {
+                        Some(_) => item.community.icon,
+                        _ => Option::Some("/static/default_assets/default-community.png".to_string()),
+                    };
+                    let community_avatar = proxied_image(community_avatar, Some(64));
+                    let domain = item
+                        .community
+                        .actor_id
+                        .trim_start_matches("https://")
+                        .split('/')
+                        .next()
+                        .unwrap_or_default()
+                        .to_string();
+
                     view! { cx,
-                        <tr>
-                            <td>
-                                <a
-                                    rel="external"
-                                    href=format!("https://{}", item.domain)
-                                    target="_blank"
-                                >
-                                    {format!("{}", item.domain)}
-                                </a>
-                            </td>
-                            <td>{format!("{}", item.software.unwrap_or_default())}</td>
-                            <td>{format!("{}", item.version.unwrap_or_default())}</td>
-                        </tr>
+                        <li class="list-group-item d-flex align-items-center gap-2">
+                            <NsfwBlur nsfw=item.community.nsfw compact=true>
+                                <img
+                                    src=community_avatar.clone()
+                                    alt=""
+                                    width="32"
+                                    height="32"
+                                    class="rounded"
+                                    loading="lazy"
+                                />
+                            </NsfwBlur>
+                            <A
+                                class="flex-grow-1"
+                                href=format!("/community/{}@{}", item.community.name, domain)
+                            >
+                                {item.community.title.clone()}
+                            </A>
+                            <span class="small text-body-secondary text-nowrap">
+                                {t_count(cx, "subscribers-count", item.counts.subscribers.into())}
+                            </span>
+                            <SubscribeButton
+                                community_id=item.community.id
+                                subscribed=item.subscribed
+                                small=true
+                            />
+                        </li>
                     }
                 })
                 .collect_view(cx)}
-        </tbody>
+        </ul>
     }
 }
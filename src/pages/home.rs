@@ -2,12 +2,24 @@ use leptos::*;
 
 use crate::api::structs::router_endpoints;
 use crate::components::feed::Feed;
+use crate::components::i18n::t;
+use crate::components::instance::get_instance_details;
+use crate::components::seo::{site_url, PageHead, PageMeta};
 use crate::components::sidecards::instance::Sidecard;
 
 /// Renders the home page of the Lemmy instance, which is the feed and sidebar screen.
 #[component]
 pub fn Home(cx: Scope) -> impl IntoView {
+    // The front page has no title of its own, so it is titled with the instance's name and slogan
+    let meta = PageMeta::for_page(
+        String::new(),
+        get_instance_details().unwrap().slogan,
+        "/",
+        &site_url(),
+    );
+
     view! { cx,
+        <PageHead meta=meta/>
         <div class="container overflow-hidden">
             <div class="row gx-4">
                 // Feed Column
@@ -18,7 +30,7 @@ pub fn Home(cx: Scope) -> impl IntoView {
                         view! { cx,
                             <div class="d-flex align-items-center">
                                 <h1>
-                                    Loading...
+                                    {t(cx, "loading")}
                                 </h1>
                                 <div
                                     class="spinner-grow ms-auto"
@@ -40,7 +52,7 @@ pub fn Home(cx: Scope) -> impl IntoView {
                         view! { cx,
                             <div class="d-flex align-items-center">
                                 <h1>
-                                    Loading...
+                                    {t(cx, "loading")}
                                 </h1>
                                 <div
                                     class="spinner-grow ms-auto"
@@ -1,7 +1,9 @@
+pub mod banned_users;
 pub mod communities;
 pub mod community;
 pub mod create_community;
 pub mod create_post;
+pub mod custom_emojis;
 pub mod federated_instances;
 pub mod home;
 pub mod login;
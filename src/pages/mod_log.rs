@@ -5,10 +5,12 @@ use serde_json::Value;
 use crate::api::modlog::get_mod_log;
 use crate::api::structs::*;
 use crate::api::*;
+use crate::components::i18n::t;
+use crate::components::time_ago::TimeAgo;
+use crate::timestamps::Timestamp;
 
 // TODO - mod_log.rs:
 // Actually implement the mod log functionality
-// Figure out how to implement a unified mod log in chronological format
 // Build in sorting based on categories
 // Figure out good limit for mod log actions per page (My guess is 10, which works out to 100 actions per page)
 
@@ -49,7 +51,7 @@ pub fn ModLog(cx: Scope) -> impl IntoView {
             .ok()
     });
 
-    let err_msg = "Error loading this post: ";
+    let err_msg = t(cx, "error-loading-post");
 
     view! { cx,
         <div class="container overflow-hidden">
@@ -58,7 +60,7 @@ pub fn ModLog(cx: Scope) -> impl IntoView {
                 view! { cx,
                     <div class="d-flex align-items-center">
                         <h1>
-                            Loading...
+                            {t(cx, "loading")}
                         </h1>
                         <div class="spinner-grow ms-auto" role="status" aria-hidden="true"></div>
                     </div>
@@ -69,7 +71,7 @@ pub fn ModLog(cx: Scope) -> impl IntoView {
                         .read(cx)
                         .map(|res| match res {
                             None => {
-                                view! { cx, <div>{format!("{err_msg}")}</div> }
+                                view! { cx, <div>{err_msg}</div> }
                             }
                             Some(res) => {
 
@@ -85,7 +87,7 @@ pub fn ModLog(cx: Scope) -> impl IntoView {
             </Transition>
 
             // Pagination for the mod log
-            <nav aria-label="Feed Page Navigation">
+            <nav aria-label=t(cx, "page-navigation")>
                 <ul class="pagination justify-content-center">
                     {move || {
                         if page() > 1 {
@@ -140,81 +142,91 @@ fn ModLogList(cx: Scope, items: MaybeSignal<GetModlogResponse>) -> impl IntoView
 
 // The WIP component for displaying modlog actions as individual items
 #[component]
-fn ModLogItem(cx: Scope, items: Vec<String>) -> impl IntoView {
+fn ModLogItem(cx: Scope, items: Vec<(String, String)>) -> impl IntoView {
     view! { cx,
         {items
             .into_iter()
-            .map(|item| view! { cx, <li>{format!("{:?}", item)}</li> })
+            .map(|(when, item)| {
+                view! { cx,
+                    <li>
+                        <TimeAgo time=when/>
+                        " "
+                        {item}
+                    </li>
+                }
+            })
             .collect_view(cx)}
     }
 }
 
-// The function for (trying) to sort the modlog actions into a unified, chronological list
-// This feels like a really scuffed way to sort a struct of Vectors...
-// If someone knows a better way to do this, please feel free to improve it. It needs to be sorted by the `when_` field for each vector in the struct
-fn mod_log_sorter(items: GetModlogResponse) -> Vec<String> {
+// Sorts the modlog actions into a unified, chronological list, newest first, by the `when_` field of each action.
+// Each entry is the time of the action and the action itself.
+fn mod_log_sorter(items: GetModlogResponse) -> Vec<(String, String)> {
     let mut sorted_modlog = Vec::new();
 
     for item in items.added {
-        sorted_modlog.push(format!("{:?}", item));
+        sorted_modlog.push((item.mod_add.when_.clone(), format!("{:?}", item)));
     }
 
     for item in items.added_to_community {
-        sorted_modlog.push(format!("{:?}", item));
+        sorted_modlog.push((item.mod_add_community.when_.clone(), format!("{:?}", item)));
     }
 
     for item in items.admin_purged_comments {
-        sorted_modlog.push(format!("{:?}", item));
+        sorted_modlog.push((item.admin_purge_comment.when_.clone(), format!("{:?}", item)));
     }
 
     for item in items.admin_purged_communities {
-        sorted_modlog.push(format!("{:?}", item));
+        sorted_modlog.push((item.admin_purge_community.when_.clone(), format!("{:?}", item)));
     }
 
     for item in items.admin_purged_persons {
-        sorted_modlog.push(format!("{:?}", item));
+        sorted_modlog.push((item.admin_purge_person.when_.clone(), format!("{:?}", item)));
     }
 
     for item in items.admin_purged_posts {
-        sorted_modlog.push(format!("{:?}", item));
+        sorted_modlog.push((item.admin_purge_post.when_.clone(), format!("{:?}", item)));
     }
 
     for item in items.banned {
-        sorted_modlog.push(format!("{:?}", item));
+        sorted_modlog.push((item.mod_ban.when_.clone(), format!("{:?}", item)));
     }
 
     for item in items.banned_from_community {
-        sorted_modlog.push(format!("{:?}", item));
+        sorted_modlog.push((item.mod_ban_from_community.when_.clone(), format!("{:?}", item)));
     }
 
     for item in items.featured_posts {
-        sorted_modlog.push(format!("{:?}", item));
+        sorted_modlog.push((item.mod_feature_post.when_.clone(), format!("{:?}", item)));
     }
 
     for item in items.hidden_communities {
-        sorted_modlog.push(format!("{:?}", item));
+        sorted_modlog.push((item.mod_hide_community.when_.clone(), format!("{:?}", item)));
     }
 
     for item in items.locked_posts {
-        sorted_modlog.push(format!("{:?}", item));
+        sorted_modlog.push((item.mod_lock_post.when_.clone(), format!("{:?}", item)));
     }
 
     for item in items.removed_comments {
-        sorted_modlog.push(format!("{:?}", item));
+        sorted_modlog.push((item.mod_remove_comment.when_.clone(), format!("{:?}", item)));
     }
 
     for item in items.removed_communities {
-        sorted_modlog.push(format!("{:?}", item));
+        sorted_modlog.push((item.mod_remove_community.when_.clone(), format!("{:?}", item)));
     }
 
     for item in items.removed_posts {
-        sorted_modlog.push(format!("{:?}", item));
+        sorted_modlog.push((item.mod_remove_post.when_.clone(), format!("{:?}", item)));
     }
 
     for item in items.transferred_to_community {
-        sorted_modlog.push(format!("{:?}", item));
+        sorted_modlog.push((item.mod_transfer_community.when_.clone(), format!("{:?}", item)));
     }
 
-    // Not sorted...
+    // Actions whose time can't be parsed go at the end
+    sorted_modlog.sort_by_key(|(when, _)| {
+        std::cmp::Reverse(Timestamp::parse(when).map(|when| when.unix()))
+    });
     sorted_modlog
 }
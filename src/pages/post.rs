@@ -4,7 +4,12 @@ use leptos_router::*;
 use crate::api::posts::get_post;
 use crate::api::structs::*;
 use crate::api::*;
-use crate::components::{post_view::PostView, sidecards::post::Sidecard};
+use crate::components::{
+    i18n::t,
+    post_view::PostView,
+    seo::{site_url, PageHead, PageMeta},
+    sidecards::post::Sidecard,
+};
 
 // TODO - post.rs:
 // Sidecard component still needs to be built
@@ -47,6 +52,14 @@ pub fn Post(cx: Scope) -> impl IntoView {
     });
 
     view! { cx,
+        // The title, description, and link preview of the post, once it has loaded
+        <Transition fallback=move || ()>
+            {move || {
+                post.read(cx)
+                    .flatten()
+                    .map(|post| view! { cx, <PageHead meta=PageMeta::for_post(&post.post_view, &site_url())/> })
+            }}
+        </Transition>
         <div class="container overflow-hidden">
             <div class="row gx-4">
                 // Feed Column
@@ -56,7 +69,7 @@ pub fn Post(cx: Scope) -> impl IntoView {
                         view! { cx,
                             <div class="d-flex align-items-center">
                                 <h1>
-                                    Loading...
+                                    {t(cx, "loading")}
                                 </h1>
                                 <div
                                     class="spinner-grow ms-auto"
@@ -78,7 +91,7 @@ pub fn Post(cx: Scope) -> impl IntoView {
                         view! { cx,
                             <div class="d-flex align-items-center">
                                 <h1>
-                                    Loading...
+                                    {t(cx, "loading")}
                                 </h1>
                                 <div
                                     class="spinner-grow ms-auto"
@@ -1,18 +1,145 @@
 use leptos::*;
 use leptos_router::*;
+use serde::Serialize;
 
-use crate::api::structs::router_endpoints;
 use crate::api::structs::*;
 use crate::api::user::get_person_details;
 use crate::api::*;
-use crate::components::{feed::Feed, sidecards::user::Sidecard};
+use crate::components::comments::CommentItem;
+use crate::components::feed::FeedItem;
+use crate::components::i18n::{t, translate, translate_sort};
+use crate::components::nsfw::is_nsfw_forbidden;
+use crate::components::pagination::Pagination;
+use crate::components::seo::{site_url, PageHead, PageMeta};
+use crate::components::site::use_my_user;
+use crate::components::sidecards::user::Sidecard;
 
 // TODO - user.rs:
 // Sidecard component still needs to be built
 // Real styling with Bootstrap
 // Ensure mobile layout works as expected
+// Add tabs for upvoted and downvoted posts once Lemmy supports them
 
-// The main page for viewing a user and their feed.
+// The main page for viewing a user, with tabs for their posts and comments.
+// The tab, sorting, and page are all kept in the query string (?view=&sort=&page=), so every view of a profile can be linked to.
+
+// The tabs at the top of a user's profile
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+enum ProfileTab {
+    // The user's posts and comments together, newest first
+    #[default]
+    Overview,
+    Posts,
+    Comments,
+    // The posts and comments the user has saved, which only they can see
+    Saved,
+}
+
+impl ProfileTab {
+    const ALL: [ProfileTab; 4] = [
+        ProfileTab::Overview,
+        ProfileTab::Posts,
+        ProfileTab::Comments,
+        ProfileTab::Saved,
+    ];
+
+    fn label(&self, cx: Scope) -> String {
+        match self {
+            ProfileTab::Overview => translate(cx, "profile-overview"),
+            ProfileTab::Posts => translate(cx, "profile-posts"),
+            ProfileTab::Comments => translate(cx, "profile-comments"),
+            ProfileTab::Saved => translate(cx, "profile-saved"),
+        }
+    }
+
+    fn query_value(&self) -> &'static str {
+        match self {
+            ProfileTab::Overview => "overview",
+            ProfileTab::Posts => "posts",
+            ProfileTab::Comments => "comments",
+            ProfileTab::Saved => "saved",
+        }
+    }
+
+    fn from_query_value(value: &str) -> Option<Self> {
+        ProfileTab::ALL
+            .into_iter()
+            .find(|tab| tab.query_value() == value)
+    }
+}
+
+// The sorts Lemmy supports for a user's posts and comments
+const PROFILE_SORTS: [SortType; 8] = [
+    SortType::New,
+    SortType::Old,
+    SortType::TopDay,
+    SortType::TopWeek,
+    SortType::TopMonth,
+    SortType::TopYear,
+    SortType::TopAll,
+    SortType::Hot,
+];
+
+#[derive(Serialize)]
+struct ProfileQuery {
+    view: &'static str,
+    sort: &'static str,
+}
+
+// Changing the tab or sorting goes back to the first page, so the page number is left out
+fn profile_href(tab: ProfileTab, sort: &SortType) -> String {
+    serde_html_form::to_string(ProfileQuery {
+        view: tab.query_value(),
+        sort: sort.to_str(),
+    })
+    .map(|query| format!("?{}", query))
+    .unwrap_or_default()
+}
+
+// A post or comment on the Overview tab
+#[derive(Clone, Debug)]
+enum ProfileItem {
+    Post(PostView),
+    Comment(CommentView),
+}
+
+impl ProfileItem {
+    fn published(&self) -> &str {
+        match self {
+            ProfileItem::Post(post) => &post.post.published,
+            ProfileItem::Comment(comment) => &comment.comment.published,
+        }
+    }
+
+    fn score(&self) -> i32 {
+        match self {
+            ProfileItem::Post(post) => post.counts.upvotes - post.counts.downvotes,
+            ProfileItem::Comment(comment) => comment.counts.score,
+        }
+    }
+}
+
+// Puts the posts and comments from a page into one list, ordered the same way Lemmy ordered each of them.
+// Lemmy's timestamps sort the same way as text, so they don't need to be parsed.
+fn interleave(posts: Vec<PostView>, comments: Vec<CommentView>, sort: &SortType) -> Vec<ProfileItem> {
+    let mut items = posts
+        .into_iter()
+        .map(ProfileItem::Post)
+        .chain(comments.into_iter().map(ProfileItem::Comment))
+        .collect::<Vec<_>>();
+
+    match sort {
+        SortType::Old => items.sort_by(|a, b| a.published().cmp(b.published())),
+        SortType::New => items.sort_by(|a, b| b.published().cmp(a.published())),
+        _ => items.sort_by(|a, b| {
+            b.score()
+                .cmp(&a.score())
+                .then_with(|| b.published().cmp(a.published()))
+        }),
+    }
+
+    items
+}
 
 #[component]
 pub fn User(cx: Scope) -> impl IntoView {
@@ -22,6 +149,16 @@ pub fn User(cx: Scope) -> impl IntoView {
             .with(|q| q.get("page").and_then(|page| page.parse::<i32>().ok()))
             .unwrap_or(1)
     };
+    let tab = move || {
+        query
+            .with(|q| q.get("view").and_then(|view| ProfileTab::from_query_value(view)))
+            .unwrap_or_default()
+    };
+    let sort = move || {
+        query
+            .with(|q| q.get("sort").and_then(|sort| SortType::from_str(sort)))
+            .unwrap_or(SortType::New)
+    };
 
     let params = use_params_map(cx);
     let username = move || {
@@ -32,6 +169,13 @@ pub fn User(cx: Scope) -> impl IntoView {
             .unwrap()
     };
 
+    // Only the user themselves can see the Saved tab
+    let is_own_profile = move || {
+        use_my_user(cx)
+            .map(|my_user| my_user.local_user_view.person.name == username())
+            .unwrap_or_default()
+    };
+
     let sidebar = create_resource(cx, page, move |page| async move {
         // This constructs the proper API URL for GetPosts or GetPersonDetails
         let url_constructor = ApiUrlConstructor {
@@ -56,29 +200,163 @@ pub fn User(cx: Scope) -> impl IntoView {
             .ok()
     });
 
+    // Variable that holds the posts and comments for the current tab, sorting, and page
+    let updater = move || (username(), tab(), sort(), page());
+    let content = create_resource(cx, updater, move |updater| async move {
+        let url_constructor = ApiUrlConstructor {
+            endpoint: api_endpoints::GetEndpoint::GET_PERSON_DETAILS.to_string(),
+            id: None,
+            params: None,
+        };
+
+        // The auth token is needed for the Saved tab, since Lemmy only returns the saved posts and comments of the user making the request
+        let get_form = GetPersonDetails {
+            auth: get_auth_token(cx),
+            community_id: None,
+            limit: Some(20),
+            page: Some(updater.3),
+            person_id: None,
+            saved_only: Some(updater.1 == ProfileTab::Saved),
+            sort: Some(updater.2),
+            username: Some(updater.0),
+        };
+
+        get_person_details(cx, &api_url_builder(cx, url_constructor, get_form))
+            .await
+            .ok()
+    });
+
+    let err_msg = t(cx, "error-loading-profile");
+
     view! { cx,
+        // The title, description, and link preview of the profile, which is the same for every tab and page
+        <Transition fallback=move || ()>
+            {move || {
+                sidebar
+                    .read(cx)
+                    .flatten()
+                    .map(|details| {
+                        view! { cx,
+                            <PageHead meta=PageMeta::for_person(&details.person_view.person, &site_url())/>
+                        }
+                    })
+            }}
+        </Transition>
         <div class="container overflow-hidden">
             <div class="row gx-4">
                 // Feed Column
                 <div class="col-md-9">
                     <br/>
-                    <Transition fallback=move || {
-                        // Handles the loading screen while waiting for a reply from the API
-                        view! { cx,
-                            <div class="d-flex align-items-center">
-                                <h1>
-                                    Loading...
-                                </h1>
-                                <div
-                                    class="spinner-grow ms-auto"
-                                    role="status"
-                                    aria-hidden="true"
-                                ></div>
+                    <div class="card text-left">
+                        <div class="card-header">
+                            <div class="row">
+                                <div class="col-sm-8">
+                                    <ul class="nav nav-tabs card-header-tabs">
+                                        {ProfileTab::ALL
+                                            .into_iter()
+                                            .map(|profile_tab| {
+                                                view! { cx,
+                                                    <li
+                                                        class="nav-item"
+                                                        class:d-none=move || {
+                                                            profile_tab == ProfileTab::Saved && !is_own_profile()
+                                                        }
+                                                    >
+                                                        <a
+                                                            class="nav-link"
+                                                            class:active=move || tab() == profile_tab
+                                                            href=move || profile_href(profile_tab, &sort())
+                                                        >
+                                                            {move || profile_tab.label(cx)}
+                                                        </a>
+                                                    </li>
+                                                }
+                                            })
+                                            .collect_view(cx)}
+                                    </ul>
+                                </div>
+                                <div class="col-sm-4 d-flex justify-content-end">
+                                    <div class="dropstart text-end">
+                                        <button
+                                            class="btn btn-secondary dropdown-toggle"
+                                            style="margin-bottom: -0.50rem; margin-top: -0.25rem;"
+                                            type="button"
+                                            data-bs-toggle="dropdown"
+                                            aria-expanded="false"
+                                        >
+                                            {move || translate_sort(cx, &sort())}
+                                        </button>
+                                        <ul class="dropdown-menu">
+                                            {PROFILE_SORTS
+                                                .into_iter()
+                                                .map(|profile_sort| {
+                                                    let label = profile_sort.clone();
+                                                    view! { cx,
+                                                        <li>
+                                                            <a
+                                                                class="dropdown-item"
+                                                                href=move || profile_href(tab(), &profile_sort)
+                                                            >
+                                                                {move || translate_sort(cx, &label)}
+                                                            </a>
+                                                        </li>
+                                                    }
+                                                })
+                                                .collect_view(cx)}
+                                        </ul>
+                                    </div>
+                                </div>
                             </div>
-                        }
-                    }>
-                        <Feed endpoint=router_endpoints::RouterEndpoint::USER/>
-                    </Transition>
+                        </div>
+                        <div class="card-body">
+                            <Transition fallback=move || {
+                                // Handles the loading screen while waiting for a reply from the API
+                                view! { cx,
+                                    <div class="d-flex align-items-center">
+                                        <h1>
+                                            {t(cx, "loading")}
+                                        </h1>
+                                        <div
+                                            class="spinner-grow ms-auto"
+                                            role="status"
+                                            aria-hidden="true"
+                                        ></div>
+                                    </div>
+                                }
+                            }>
+                                {move || {
+                                    content
+                                        .read(cx)
+                                        .map(|res| match res {
+                                            None => view! { cx, <div>{err_msg}</div> }.into_view(cx),
+                                            Some(_) if tab() == ProfileTab::Saved && !is_own_profile() => {
+                                                view! { cx,
+                                                    <p class="text-body-secondary">
+                                                        {t(cx, "profile-saved-private")}
+                                                    </p>
+                                                }
+                                                    .into_view(cx)
+                                            }
+                                            Some(res) => {
+                                                let posts = res
+                                                    .posts
+                                                    .into_iter()
+                                                    // Instances that don't allow NSFW content leave those posts out entirely
+                                                    .filter(|post| !is_nsfw_forbidden(post.post.nsfw || post.community.nsfw))
+                                                    .collect::<Vec<_>>();
+                                                let items = match tab() {
+                                                    ProfileTab::Posts => posts.into_iter().map(ProfileItem::Post).collect(),
+                                                    ProfileTab::Comments => res.comments.into_iter().map(ProfileItem::Comment).collect(),
+                                                    ProfileTab::Overview | ProfileTab::Saved => interleave(posts, res.comments, &sort()),
+                                                };
+                                                view! { cx, <ProfileItems items=items/> }.into_view(cx)
+                                            }
+                                        })
+                                }}
+                            </Transition>
+                        </div>
+                        <Pagination/>
+                    </div>
                 </div>
 
                 // Sidecard Column
@@ -89,7 +367,7 @@ pub fn User(cx: Scope) -> impl IntoView {
                         view! { cx,
                             <div class="d-flex align-items-center">
                                 <h1>
-                                    Loading...
+                                    {t(cx, "loading")}
                                 </h1>
                                 <div
                                     class="spinner-grow ms-auto"
@@ -107,3 +385,52 @@ pub fn User(cx: Scope) -> impl IntoView {
         </div>
     }
 }
+
+// The posts and comments on the current tab
+#[component]
+fn ProfileItems(cx: Scope, items: Vec<ProfileItem>) -> impl IntoView {
+    if items.is_empty() {
+        return view! { cx, <p class="text-body-secondary">{t(cx, "profile-empty")}</p> }.into_view(cx);
+    }
+
+    items
+        .into_iter()
+        .map(|item| match item {
+            ProfileItem::Post(post) => {
+                view! { cx, <FeedItem post_view=MaybeSignal::Static(post)/> }.into_view(cx)
+            }
+            ProfileItem::Comment(comment) => {
+                view! { cx, <ProfileComment comment=comment/> }.into_view(cx)
+            }
+        })
+        .collect_view(cx)
+}
+
+// A comment on a profile, with the post it was made on so it can be read in context
+#[component]
+fn ProfileComment(cx: Scope, comment: CommentView) -> impl IntoView {
+    let context_link = format!("/post/{}#comment-{}", comment.post.id, comment.comment.id);
+    let post_title = comment.post.name.clone();
+    let community_name = comment.community.name.clone();
+
+    view! { cx,
+        <div class="mb-3">
+            <div class="small text-body-secondary mb-1">
+                <i aria-hidden="true" class="bi bi-chat-left-text"></i>
+                " "
+                {t(cx, "profile-comment-on")}
+                " "
+                <a href=context_link.clone()>{post_title}</a>
+                " "
+                {t(cx, "profile-comment-in")}
+                " "
+                {community_name}
+                " · "
+                <a href=context_link class="link-secondary">
+                    {t(cx, "profile-comment-context")}
+                </a>
+            </div>
+            <CommentItem comment_item=MaybeSignal::Static(comment)/>
+        </div>
+    }
+}
@@ -0,0 +1,82 @@
+use cfg_if::cfg_if;
+
+cfg_if! { if #[cfg(feature = "ssr")] {
+    use axum::{
+        body::Bytes,
+        extract::Path,
+        http::{header, HeaderMap, StatusCode},
+        response::IntoResponse,
+    };
+    use crate::api::cookie_from_cookies;
+    use crate::api::structs::api_endpoints;
+    use crate::api_cache::http_client;
+    use crate::components::instance::*;
+
+    // The largest image that can be uploaded through the proxy, which matches the default limit of pictrs
+    pub const MAX_UPLOAD_SIZE: usize = 25 * 1024 * 1024;
+
+    // The URL of the pictrs proxy on the Lemmy instance
+    fn instance_pictrs_url() -> String {
+        format!(
+            "{}/{}",
+            get_instance_details().unwrap().url,
+            api_endpoints::PostEndpoint::UPLOAD_IMAGE
+        )
+    }
+
+    // Pictrs on the instance only accepts uploads with the `jwt` cookie, which is only sent by the browser to the Wolfrs domain
+    fn jwt_cookie(headers: &HeaderMap) -> Option<String> {
+        let cookies = headers.get(header::COOKIE)?.to_str().ok()?;
+        cookie_from_cookies(cookies, "jwt").map(|token| format!("jwt={}", token))
+    }
+
+    // Turns the reply from the instance into a response for the browser, keeping the status code and body
+    async fn into_proxy_response(response: reqwest::Result<reqwest::Response>) -> (StatusCode, String) {
+        match response {
+            Ok(response) => {
+                let status = StatusCode::from_u16(response.status().as_u16())
+                    .unwrap_or(StatusCode::BAD_GATEWAY);
+                (status, response.text().await.unwrap_or_default())
+            }
+            Err(err) => (StatusCode::BAD_GATEWAY, format!("Error reaching pictrs: {err}")),
+        }
+    }
+
+    // Forwards a multipart image upload to pictrs on the instance, along with the auth cookie
+    pub async fn upload_image_proxy(headers: HeaderMap, body: Bytes) -> impl IntoResponse {
+        let Some(cookie) = jwt_cookie(&headers) else {
+            return (StatusCode::UNAUTHORIZED, "You need to be logged in to upload images".to_string());
+        };
+
+        // The content type holds the multipart boundary, so it has to be passed along as is
+        let content_type = headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+
+        let response = http_client()
+            .post(instance_pictrs_url())
+            .header(reqwest::header::CONTENT_TYPE, content_type)
+            .header(reqwest::header::COOKIE, cookie)
+            .body(body)
+            .send()
+            .await;
+
+        into_proxy_response(response).await
+    }
+
+    // Forwards the deletion of an uploaded image to pictrs on the instance, which only takes it as a GET
+    pub async fn delete_image_proxy(
+        Path((delete_token, file)): Path<(String, String)>,
+        headers: HeaderMap,
+    ) -> impl IntoResponse {
+        let mut request = http_client()
+            .get(format!("{}/delete/{}/{}", instance_pictrs_url(), delete_token, file));
+        if let Some(cookie) = jwt_cookie(&headers) {
+            request = request.header(reqwest::header::COOKIE, cookie);
+        }
+
+        into_proxy_response(request.send().await).await
+    }
+}}
@@ -0,0 +1,192 @@
+use cfg_if::cfg_if;
+
+use crate::timestamps::Timestamp;
+
+// TODO - sitemap.rs:
+// List older posts and the rest of the communities through a sitemap index, instead of only the newest and top ones
+
+// Search engines find the instance's pages through /sitemap.xml, which lists the front page, the communities list,
+// the instance's own top communities, and its newest posts. /robots.txt points them at it.
+// Content from other instances is left out, since it belongs in the sitemap of the instance it came from.
+
+// How many posts and communities the sitemap lists
+pub const SITEMAP_LIMIT: i32 = 50;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SitemapEntry {
+    pub url: String,
+    // When the page last changed, in the W3C date format sitemaps use
+    pub last_modified: Option<String>,
+}
+
+impl SitemapEntry {
+    pub fn new(url: String, published: &str, updated: Option<&str>) -> Self {
+        let last_modified = updated
+            .and_then(Timestamp::parse)
+            .or_else(|| Timestamp::parse(published))
+            .map(|timestamp| timestamp.to_iso());
+        SitemapEntry { url, last_modified }
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+pub fn sitemap_xml(entries: &[SitemapEntry]) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    for entry in entries {
+        xml.push_str("  <url>\n");
+        xml.push_str(&format!("    <loc>{}</loc>\n", escape_xml(&entry.url)));
+        if let Some(last_modified) = &entry.last_modified {
+            xml.push_str(&format!("    <lastmod>{}</lastmod>\n", escape_xml(last_modified)));
+        }
+        xml.push_str("  </url>\n");
+    }
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+// Private instances ask every crawler to stay out, anything else is open to them and told where the sitemap is
+pub fn robots_txt(site_url: &str, private_instance: bool) -> String {
+    if private_instance {
+        "User-agent: *\nDisallow: /\n".to_string()
+    } else {
+        format!(
+            "User-agent: *\nAllow: /\n\nSitemap: {}/sitemap.xml\n",
+            site_url.trim_end_matches('/')
+        )
+    }
+}
+
+cfg_if! { if #[cfg(feature = "ssr")] {
+    use anyhow::{anyhow, Result};
+    use axum::{
+        http::{header, StatusCode},
+        response::{IntoResponse, Response},
+    };
+
+    use crate::api::structs::*;
+    use crate::api_cache::cached_get;
+    use crate::components::instance::*;
+    use crate::components::seo::absolute_url;
+
+    // Crawlers fetch these often, so they can cache them for as long as the newest posts stay about the same
+    const CACHE_CONTROL: &str = "public, max-age=3600";
+
+    async fn get_anonymous<T: serde::de::DeserializeOwned>(endpoint: &str, query: &str) -> Result<T> {
+        let instance = get_instance_details().map_err(|err| anyhow!(err.to_string()))?;
+        let url = format!("{}/api/{}/{}?{}", instance.url, instance.api_version, endpoint, query);
+        Ok(serde_json::from_str(&cached_get(&url, false).await?)?)
+    }
+
+    async fn is_private_instance() -> Result<bool> {
+        let site: GetSiteResponse = get_anonymous("site", "").await?;
+        Ok(site.site_view.local_site.private_instance)
+    }
+
+    async fn sitemap_entries(site_url: &str) -> Result<Vec<SitemapEntry>> {
+        let posts = serde_html_form::to_string(GetPosts {
+            limit: Some(SITEMAP_LIMIT),
+            sort: Some(SortType::New),
+            type_: Some(ListingType::Local),
+            ..Default::default()
+        })?;
+        let posts: GetPostsResponse = get_anonymous("post/list", &posts).await?;
+        let communities = serde_html_form::to_string(ListCommunities {
+            limit: Some(SITEMAP_LIMIT),
+            show_nsfw: Some(false),
+            sort: Some(SortType::TopAll),
+            type_: Some(ListingType::Local),
+            ..Default::default()
+        })?;
+        let communities: ListCommunitiesResponse = get_anonymous("community/list", &communities).await?;
+
+        let mut entries = vec![
+            SitemapEntry { url: absolute_url(site_url, "/"), last_modified: None },
+            SitemapEntry { url: absolute_url(site_url, "/communities"), last_modified: None },
+        ];
+        // NSFW pages ask search engines not to index them, so they aren't listed either
+        entries.extend(
+            communities
+                .communities
+                .iter()
+                .map(|community_view| &community_view.community)
+                .filter(|community| !community.nsfw && !community.removed && !community.deleted)
+                .map(|community| {
+                    SitemapEntry::new(
+                        absolute_url(site_url, &format!("/community/{}", community.name)),
+                        &community.published,
+                        community.updated.as_deref(),
+                    )
+                }),
+        );
+        entries.extend(
+            posts
+                .posts
+                .iter()
+                .filter(|post_view| !(post_view.post.nsfw || post_view.community.nsfw))
+                .map(|post_view| {
+                    SitemapEntry::new(
+                        absolute_url(site_url, &format!("/post/{}", post_view.post.id)),
+                        &post_view.post.published,
+                        post_view.post.updated.as_deref(),
+                    )
+                }),
+        );
+
+        Ok(entries)
+    }
+
+    pub async fn sitemap() -> Response {
+        let site_url = match get_instance_details() {
+            Ok(instance) => instance.site_url().to_string(),
+            Err(err) => return (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response(),
+        };
+        match is_private_instance().await {
+            Ok(false) => {}
+            Ok(true) => return StatusCode::NOT_FOUND.into_response(),
+            Err(err) => return (StatusCode::BAD_GATEWAY, err.to_string()).into_response(),
+        }
+
+        match sitemap_entries(&site_url).await {
+            Ok(entries) => (
+                [
+                    (header::CONTENT_TYPE, "application/xml; charset=utf-8"),
+                    (header::CACHE_CONTROL, CACHE_CONTROL),
+                ],
+                sitemap_xml(&entries),
+            )
+                .into_response(),
+            Err(err) => (StatusCode::BAD_GATEWAY, err.to_string()).into_response(),
+        }
+    }
+
+    pub async fn robots() -> Response {
+        let site_url = match get_instance_details() {
+            Ok(instance) => instance.site_url().to_string(),
+            Err(err) => return (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response(),
+        };
+        // Crawlers treat a robots.txt that can't be fetched as disallowing everything for a while, so a private instance is never let in by mistake
+        let private_instance = match is_private_instance().await {
+            Ok(private_instance) => private_instance,
+            Err(err) => return (StatusCode::SERVICE_UNAVAILABLE, err.to_string()).into_response(),
+        };
+
+        (
+            [
+                (header::CONTENT_TYPE, "text/plain; charset=utf-8"),
+                (header::CACHE_CONTROL, CACHE_CONTROL),
+            ],
+            robots_txt(&site_url, private_instance),
+        )
+            .into_response()
+    }
+}}
@@ -0,0 +1,174 @@
+// Lemmy sends timestamps as ISO 8601 text, which is UTC even when there is no offset on the end, such as 2023-07-01T12:34:56.123456.
+// They are parsed here instead of pulling a date library into the WASM. Components show them with the TimeAgo component in time_ago.rs, which translates them.
+
+// The current time as a unix timestamp in seconds, from the server's clock or the browser's
+pub fn now_unix() -> i64 {
+    cfg_if::cfg_if! { if #[cfg(feature = "ssr")] {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|duration| duration.as_secs() as i64)
+            .unwrap_or_default()
+    } else {
+        (js_sys::Date::now() / 1000.0) as i64
+    }}
+}
+
+// The number of days from 1970-01-01 to a date, from Howard Hinnant's days_from_civil
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146097 + day_of_era - 719468
+}
+
+// The date from a number of days since 1970-01-01, from Howard Hinnant's civil_from_days
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400;
+
+    (if month <= 2 { year + 1 } else { year }, month, day)
+}
+
+// A moment in time, which is stored as a unix timestamp in seconds
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(i64);
+
+impl Timestamp {
+    pub fn from_unix(seconds: i64) -> Self {
+        Timestamp(seconds)
+    }
+
+    pub fn now() -> Self {
+        Timestamp(now_unix())
+    }
+
+    pub fn unix(&self) -> i64 {
+        self.0
+    }
+
+    // Parses a timestamp from Lemmy, or returns None if it isn't one
+    pub fn parse(timestamp: &str) -> Option<Self> {
+        parse_timestamp(timestamp).map(Timestamp)
+    }
+
+    // The date and time in UTC, as (year, month, day, hour, minute, second)
+    fn utc_parts(&self) -> (i64, i64, i64, i64, i64, i64) {
+        let (year, month, day) = civil_from_days(self.0.div_euclid(86400));
+        let time = self.0.rem_euclid(86400);
+
+        (year, month, day, time / 3600, time % 3600 / 60, time % 60)
+    }
+
+    // The timestamp in the format HTML's datetime attribute expects, such as 2023-07-01T12:34:56Z
+    pub fn to_iso(&self) -> String {
+        let (year, month, day, hour, minute, second) = self.utc_parts();
+        format!("{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}Z")
+    }
+
+    // A readable date and time in UTC, for when the reader's time zone isn't known, such as when rendering on the server
+    pub fn to_utc_string(&self) -> String {
+        let (year, month, day, hour, minute, _) = self.utc_parts();
+        format!("{year:04}-{month:02}-{day:02} {hour:02}:{minute:02} UTC")
+    }
+
+    // How many seconds before now the timestamp is, which is never negative even if the clocks disagree
+    pub fn seconds_before(&self, now: Timestamp) -> i64 {
+        (now.0 - self.0).max(0)
+    }
+
+    // How long before now the timestamp is
+    pub fn age_at(&self, now: Timestamp) -> Age {
+        Age::from_seconds(self.seconds_before(now))
+    }
+}
+
+fn parse_timestamp(timestamp: &str) -> Option<i64> {
+    let (date, time) = timestamp.trim().split_once(['T', ' '])?;
+
+    let mut date_parts = date.splitn(3, '-').map(|part| part.parse::<i64>().ok());
+    let (year, month, day) = (date_parts.next()??, date_parts.next()??, date_parts.next()??);
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    // Anything after the seconds is either a fraction, which is dropped, or the offset from UTC
+    let clock_length = time.find(|c: char| !(c.is_ascii_digit() || c == ':')).unwrap_or(time.len());
+    let (clock, rest) = time.split_at(clock_length);
+    let mut clock_parts = clock.splitn(3, ':').map(|part| part.parse::<i64>().ok());
+    let (hour, minute) = (clock_parts.next()??, clock_parts.next()??);
+    let second = clock_parts.next().flatten().unwrap_or_default();
+
+    let zone = rest.trim_start_matches(|c: char| c == '.' || c.is_ascii_digit());
+    let offset = match zone {
+        // Lemmy leaves the offset out of timestamps that are in UTC
+        "" | "Z" | "z" => 0,
+        _ => parse_offset(zone)?,
+    };
+
+    Some(days_from_civil(year, month, day) * 86400 + hour * 3600 + minute * 60 + second - offset)
+}
+
+// The offset from UTC at the end of a timestamp in seconds, written as +HH:MM or +HHMM
+fn parse_offset(zone: &str) -> Option<i64> {
+    let (sign, offset) = match zone.strip_prefix('+') {
+        Some(offset) => (1, offset),
+        None => (-1, zone.strip_prefix('-')?),
+    };
+    if !offset.is_ascii() {
+        return None;
+    }
+    let (hours, minutes) = match offset.len() {
+        4 => offset.split_at(2),
+        5 if &offset[2..3] == ":" => (&offset[..2], &offset[3..]),
+        _ => return None,
+    };
+    if !hours.chars().chain(minutes.chars()).all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let (hours, minutes) = (hours.parse::<i64>().ok()?, minutes.parse::<i64>().ok()?);
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+
+    Some(sign * (hours * 3600 + minutes * 60))
+}
+
+// How many seconds ago a timestamp from Lemmy was
+pub fn seconds_since(timestamp: &str) -> Option<i64> {
+    Timestamp::parse(timestamp).map(|time| time.seconds_before(Timestamp::now()))
+}
+
+// How long ago something was, in the largest unit that fits
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Age {
+    JustNow,
+    Minutes(i64),
+    Hours(i64),
+    Days(i64),
+    Months(i64),
+    Years(i64),
+}
+
+impl Age {
+    pub fn from_seconds(seconds: i64) -> Self {
+        match seconds {
+            s if s < 60 => Age::JustNow,
+            s if s < 3600 => Age::Minutes(s / 60),
+            s if s < 86400 => Age::Hours(s / 3600),
+            s if s < 30 * 86400 => Age::Days(s / 86400),
+            s if s < 365 * 86400 => Age::Months(s / (30 * 86400)),
+            s => Age::Years(s / (365 * 86400)),
+        }
+    }
+}
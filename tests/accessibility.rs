@@ -0,0 +1,141 @@
+// Checks the HTML the server renders for the most common accessibility mistakes: images without alt text,
+// icons that screen readers would read out, and buttons, links, and inputs with nothing to announce.
+// Run with `cargo test --features ssr`, since the components are only rendered to HTML on the server.
+
+use leptos::*;
+use leptos_router::*;
+use regex::Regex;
+
+use wolfrs::components::a11y::{SkipLink, MAIN_CONTENT_ID};
+use wolfrs::components::feed::FeedItem;
+use wolfrs::components::feed_layout::FeedLayout;
+use wolfrs::components::footer::Footer;
+use wolfrs::components::header::Header;
+use wolfrs::components::i18n::provide_i18n;
+use wolfrs::components::shortcuts::provide_shortcuts;
+use wolfrs::components::theme::provide_theme;
+use wolfrs::components::time_ago::provide_clock;
+
+mod common;
+use common::sample_post;
+
+// Renders a view the way the App would, with the contexts the header and feed need
+fn render(view: impl FnOnce(Scope) -> View + 'static) -> String {
+    leptos::ssr::render_to_string(move |cx| {
+        provide_context(
+            cx,
+            RouterIntegrationContext::new(ServerIntegration {
+                path: "http://localhost/".to_string(),
+            }),
+        );
+        provide_theme(cx);
+        provide_i18n(cx);
+        provide_clock(cx);
+        provide_shortcuts(cx);
+
+        view! { cx, <Router>{view(cx)}</Router> }
+    })
+    .to_string()
+}
+
+// The text inside an element that a screen reader would announce, counting the alt text of images and leaving out anything hidden from it
+fn accessible_text(inner_html: &str) -> String {
+    let hidden = Regex::new(r#"(?s)<(\w+)[^>]*aria-hidden="true"[^>]*>.*?</\w+>"#).unwrap();
+    let alt = Regex::new(r#"<img[^>]*\balt="([^"]*)"[^>]*>"#).unwrap();
+    let tag = Regex::new(r"(?s)<!--.*?-->|<[^>]+>").unwrap();
+
+    let text = hidden.replace_all(inner_html, "");
+    let text = alt.replace_all(&text, " $1 ");
+    tag.replace_all(&text, "").trim().to_string()
+}
+
+// Leptos renders empty attributes, such as alt="", without a value
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    Regex::new(&format!(r#"\s{}(?:="([^"]*)")?[\s/>]"#, name))
+        .unwrap()
+        .captures(tag)
+        .map(|captures| captures.get(1).map_or("", |value| value.as_str()))
+}
+
+// Every problem found in the HTML, described so a failing test says what to fix
+fn accessibility_problems(html: &str) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    for image in Regex::new(r"<img\b[^>]*>").unwrap().find_iter(html) {
+        let image = image.as_str();
+        match attribute(image, "alt") {
+            None => problems.push(format!("image without alt text: {}", image)),
+            Some(alt) if ["mdo", "image", "logo"].contains(&alt.to_lowercase().as_str()) => {
+                problems.push(format!("image with placeholder alt text: {}", image))
+            }
+            _ => {}
+        }
+    }
+
+    for icon in Regex::new(r"<i\b[^>]*>").unwrap().find_iter(html) {
+        if attribute(icon.as_str(), "aria-hidden") != Some("true") {
+            problems.push(format!("icon that isn't hidden from screen readers: {}", icon.as_str()));
+        }
+    }
+
+    let controls = Regex::new(r"(?s)<(a|button)\b([^>]*)>(.*?)</(?:a|button)>").unwrap();
+    for control in controls.captures_iter(html) {
+        let has_label = ["aria-label", "title"]
+            .iter()
+            .any(|name| attribute(&control[2], name).is_some_and(|label| !label.trim().is_empty()));
+        if !has_label && accessible_text(&control[3]).is_empty() {
+            problems.push(format!("{} without an accessible name: {}", &control[1], &control[0]));
+        }
+    }
+
+    let labels = Regex::new(r#"<label[^>]*\bfor="([^"]*)""#).unwrap();
+    let labelled_ids = labels
+        .captures_iter(html)
+        .map(|captures| captures[1].to_string())
+        .collect::<Vec<_>>();
+    for input in Regex::new(r"<(?:input|select|textarea)\b[^>]*>").unwrap().find_iter(html) {
+        let input = input.as_str();
+        let labelled = attribute(input, "type") == Some("hidden")
+            || attribute(input, "aria-label").is_some()
+            || attribute(input, "id").is_some_and(|id| labelled_ids.iter().any(|label| label == id));
+        if !labelled {
+            problems.push(format!("input without a label: {}", input));
+        }
+    }
+
+    problems
+}
+
+#[test]
+fn the_checker_catches_common_mistakes() {
+    let problems = accessibility_problems(
+        r#"<img src="a.png" alt="mdo"><a href="/"><i class="bi bi-bell"></i></a><input type="search">"#,
+    );
+    assert_eq!(problems.len(), 4, "{:#?}", problems);
+}
+
+#[test]
+fn the_header_and_footer_are_accessible() {
+    let html = render(|cx| view! { cx, <Header/><Footer/> }.into_view(cx));
+    assert_eq!(accessibility_problems(&html), Vec::<String>::new());
+}
+
+#[test]
+fn posts_are_accessible_in_every_layout() {
+    for layout in [FeedLayout::List, FeedLayout::Compact, FeedLayout::Cards] {
+        let html = render(move |cx| {
+            view! { cx, <FeedItem post_view=MaybeSignal::Static(sample_post()) layout=layout/> }
+                .into_view(cx)
+        });
+
+        assert!(html.contains("data-feed-item"), "{:?} posts can't be reached with j and k", layout);
+        assert_eq!(accessibility_problems(&html), Vec::<String>::new(), "{:?}", layout);
+    }
+}
+
+#[test]
+fn the_skip_link_jumps_to_the_main_content() {
+    let html = render(|cx| view! { cx, <SkipLink/> }.into_view(cx));
+    assert!(html.contains(&format!("href=\"#{}\"", MAIN_CONTENT_ID)));
+    assert_eq!(accessibility_problems(&html), Vec::<String>::new());
+}
@@ -0,0 +1,41 @@
+// Shared by the integration tests, which each include it with `mod common;`
+
+use wolfrs::api::structs::*;
+
+// A local, pinned image post with a few votes and comments
+pub fn sample_post() -> PostView {
+    PostView {
+        post: Post {
+            id: 7,
+            name: "Wolves <3".to_string(),
+            body: Some("Wolves **live** in packs.".to_string()),
+            url: Some("https://example.com/wolf.png".to_string()),
+            published: "2023-06-01T12:00:00".to_string(),
+            updated: Some("2023-06-02T12:00:00".to_string()),
+            ap_id: "https://wolfrs.example/post/7".to_string(),
+            local: true,
+            featured_local: true,
+            ..Default::default()
+        },
+        creator: Person {
+            name: "alice".to_string(),
+            actor_id: "https://wolfrs.example/u/alice".to_string(),
+            local: true,
+            ..Default::default()
+        },
+        community: Community {
+            name: "wolves".to_string(),
+            title: "Wolves".to_string(),
+            actor_id: "https://wolfrs.example/c/wolves".to_string(),
+            local: true,
+            ..Default::default()
+        },
+        counts: PostAggregates {
+            upvotes: 10,
+            downvotes: 2,
+            comments: Some(3),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
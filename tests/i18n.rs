@@ -0,0 +1,100 @@
+// Tests for picking the language of the UI, and for keeping the locale files in step with the code.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use wolfrs::components::i18n::{negotiate_locale, Locale};
+
+#[test]
+fn negotiates_the_best_supported_language() {
+    assert_eq!(negotiate_locale("fr-CH, fr;q=0.9, en;q=0.8, *;q=0.5"), Some(Locale::French));
+    assert_eq!(negotiate_locale("de-DE, en-GB;q=0.7, fr;q=0.3"), Some(Locale::English));
+    assert_eq!(negotiate_locale("en;q=0.5, fr;q=0.9"), Some(Locale::French));
+    assert_eq!(negotiate_locale("fr;q=0, en"), Some(Locale::English));
+    assert_eq!(negotiate_locale("de, ja"), None);
+    assert_eq!(negotiate_locale(""), None);
+}
+
+#[test]
+fn matches_lemmy_interface_languages() {
+    assert_eq!(Locale::from_code("fr"), Some(Locale::French));
+    assert_eq!(Locale::from_code("en_GB"), Some(Locale::English));
+    assert_eq!(Locale::from_code("browser"), None);
+}
+
+// The ids of the messages in a locale's Fluent files, which are the lines that start with an id and =
+fn message_ids(locale: &str) -> HashSet<String> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("locales").join(locale);
+    let mut ids = HashSet::new();
+
+    for entry in fs::read_dir(dir).unwrap() {
+        let ftl = fs::read_to_string(entry.unwrap().path()).unwrap();
+        ids.extend(ftl.lines().filter_map(|line| {
+            let (id, _) = line.split_once('=')?;
+            let id = id.trim_end();
+            (!id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'))
+                .then(|| id.to_string())
+        }));
+    }
+
+    ids
+}
+
+#[test]
+fn every_locale_has_a_folder() {
+    for locale in Locale::ALL {
+        assert!(
+            !message_ids(locale.code()).is_empty(),
+            "locales/{} has no messages",
+            locale.code()
+        );
+    }
+}
+
+// Translations can leave messages out, since they fall back to English, but every message they have must be one the UI uses
+#[test]
+fn translations_only_have_english_messages() {
+    let english = message_ids("en");
+
+    for locale in Locale::ALL {
+        let unknown = message_ids(locale.code())
+            .into_iter()
+            .filter(|id| !english.contains(id))
+            .collect::<Vec<_>>();
+        assert!(
+            unknown.is_empty(),
+            "locales/{} has messages English doesn't: {:?}",
+            locale.code(),
+            unknown
+        );
+    }
+}
+
+// Every message looked up by the code has to be in the English files, otherwise the page would show its id
+#[test]
+fn english_has_every_message_the_code_uses() {
+    let english = message_ids("en");
+    let lookup = regex::Regex::new(r#"(?:\bt|t_count|translate|translate_with)\(cx, "([a-z0-9-]+)""#).unwrap();
+    let mut missing = Vec::new();
+
+    let mut dirs = vec![Path::new(env!("CARGO_MANIFEST_DIR")).join("src")];
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else if path.extension().is_some_and(|extension| extension == "rs") {
+                let source = fs::read_to_string(&path).unwrap();
+                missing.extend(
+                    lookup
+                        .captures_iter(&source)
+                        .map(|captures| captures[1].to_string())
+                        .filter(|id| !english.contains(id)),
+                );
+            }
+        }
+    }
+
+    assert!(missing.is_empty(), "locales/en is missing {:?}", missing);
+}
@@ -0,0 +1,21 @@
+// Tests for the configuration the server renders into every page for the browser.
+
+use wolfrs::components::instance::{instance_details_json, Instance};
+
+#[test]
+fn page_leaves_out_server_settings() {
+    let json = instance_details_json();
+
+    for setting in [
+        "image_proxy_cache_dir",
+        "image_proxy_cache_size_mb",
+        "api_cache_ttls",
+        "api_cache_metrics",
+    ] {
+        assert!(!json.contains(setting), "{} is in the page: {}", setting, json);
+    }
+    // The browser still reads it back as an Instance, with the server settings left at their defaults
+    let instance = serde_json::from_str::<Instance>(&json).unwrap();
+    assert!(!instance.name.is_empty());
+    assert!(instance.api_cache_ttls.is_empty());
+}
@@ -0,0 +1,59 @@
+// Golden file tests for the Markdown renderer.
+// Each .md file in tests/fixtures/markdown is rendered and compared against the .html file next to it.
+// Run with BLESS=1 to write the current output as the expected output after an intentional change.
+
+use std::fs;
+use std::path::Path;
+
+use wolfrs::api::structs::{CustomEmoji, CustomEmojiView};
+use wolfrs::components::markdown::render_markdown;
+
+fn custom_emojis() -> Vec<CustomEmojiView> {
+    vec![CustomEmojiView {
+        custom_emoji: CustomEmoji {
+            alt_text: "A howling wolf".to_string(),
+            category: "Wolves".to_string(),
+            id: 1,
+            image_url: "https://pawb.social/pictrs/image/wolf_howl.png".to_string(),
+            local_site_id: 1,
+            published: "2023-07-01T00:00:00".to_string(),
+            shortcode: "wolf_howl".to_string(),
+            updated: None,
+        },
+        keywords: Vec::new(),
+    }]
+}
+
+// Mentions of the instance's own communities are linked differently, so the tests pin the instance instead of using the url in Instance.toml
+const INSTANCE_URL: &str = "https://pawb.social";
+
+#[test]
+fn markdown_matches_fixtures() {
+    // Overrides take priority over Instance.toml, and this is the only test here so nothing has loaded the configuration yet
+    std::env::set_var("WOLFRS_LEMMY_URL", INSTANCE_URL);
+
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/markdown");
+    let bless = std::env::var("BLESS").is_ok();
+
+    let mut checked = 0;
+    for entry in fs::read_dir(&fixtures).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().and_then(|extension| extension.to_str()) != Some("md") {
+            continue;
+        }
+
+        let markdown = fs::read_to_string(&path).unwrap();
+        let actual = render_markdown(&markdown, &custom_emojis());
+        let expected_path = path.with_extension("html");
+
+        if bless {
+            fs::write(&expected_path, &actual).unwrap();
+        } else {
+            let expected = fs::read_to_string(&expected_path).unwrap_or_default();
+            assert_eq!(actual, expected, "rendered output of {:?} changed", path);
+        }
+        checked += 1;
+    }
+
+    assert!(checked > 0, "no fixtures found in {:?}", fixtures);
+}
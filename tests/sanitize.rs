@@ -0,0 +1,213 @@
+// Known XSS payloads that must come out of the Markdown renderer and the sanitizer inert.
+// The renderer and sanitizer are plain Rust with no SSR or hydrate specific code, so these cover both.
+// Run with `cargo test --features ssr` and `cargo test --features hydrate`.
+
+use wolfrs::api::structs::{CustomEmoji, CustomEmojiView};
+use wolfrs::components::markdown::render_markdown;
+use wolfrs::components::sanitize::sanitize_html;
+
+// Markdown that tries to get script into the page through the renderer
+const MARKDOWN_PAYLOADS: &[&str] = &[
+    "<script>alert(1)</script>",
+    "<img src=x onerror=alert(1)>",
+    "<svg onload=alert(1)>",
+    "<iframe src=\"javascript:alert(1)\"></iframe>",
+    "<details open ontoggle=alert(1)>",
+    "<a href=\"javascript:alert(1)\">click</a>",
+    "[click](javascript:alert(1))",
+    "[click](JaVaScRiPt:alert(1))",
+    "[click](java&#115;cript:alert(1))",
+    "[click](&#x6A;avascript:alert(1))",
+    "[click](javascript&colon;alert(1))",
+    "[click](vbscript:msgbox(1))",
+    "[click](data:text/html;base64,PHNjcmlwdD5hbGVydCgxKTwvc2NyaXB0Pg==)",
+    "[click](\" onmouseover=\"alert(1))",
+    "![image](javascript:alert(1))",
+    "![image](https://example.com/a.png\"onerror=\"alert(1))",
+    "<javascript:alert(1)>",
+    "[ref]\n\n[ref]: javascript:alert(1)",
+    "::: spoiler <img src=x onerror=alert(1)>\nhidden\n:::",
+    "::: spoiler title\n<script>alert(1)</script>\n:::",
+    "^<img src=x onerror=alert(1)>^ and ~<script>alert(1)</script>~",
+    "!<script>alert(1)</script>@example.com and @\"onmouseover=alert(1)@example.com",
+    "```\n</code></pre><script>alert(1)</script>\n```",
+];
+
+// HTML given straight to the sanitizer, as if something had slipped through the renderer
+const HTML_PAYLOADS: &[&str] = &[
+    "<script>alert(1)</script>",
+    "<SCRIPT SRC=https://example.com/xss.js></SCRIPT>",
+    "<scr<script>ipt>alert(1)</script>",
+    "<img src=x onerror=alert(1)>",
+    "<img src=\"x\" onerror=\"alert(1)\" />",
+    "<img src=\"javascript:alert(1)\">",
+    "<img src=\"data:image/svg+xml;base64,PHN2ZyBvbmxvYWQ9YWxlcnQoMSk+\">",
+    "<a href=\"javascript:alert(1)\" onclick=\"alert(1)\">click</a>",
+    "<a href=\" jav&#x09;ascript:alert(1)\">click</a>",
+    "<a href=\"&#106;&#97;&#118;&#97;&#115;&#99;&#114;&#105;&#112;&#116;&#58;alert(1)\">click</a>",
+    "<a href='javascript&colon;alert(1)'>click</a>",
+    "<a href=javascript:alert(1)>click</a>",
+    "<a href=\"data:text/html,<script>alert(1)</script>\">click</a>",
+    "<a href=\"https://example.com\" target=\"_self\" rel=\"opener\">click</a>",
+    "<p style=\"background:url(javascript:alert(1))\">styled</p>",
+    "<p class=\"position-fixed top-0 start-0 w-100 h-100\">overlay</p>",
+    "<h1 id=\"__proto__\">clobber</h1>",
+    "<input type=\"image\" src=\"x\" onerror=\"alert(1)\">",
+    "<form action=\"javascript:alert(1)\"><button>go</button></form>",
+    "<svg><script>alert(1)</script></svg>",
+    "<math><mtext><table><mglyph><style><img src=x onerror=alert(1)>",
+    "<iframe srcdoc=\"<script>alert(1)</script>\"></iframe>",
+    "<object data=\"javascript:alert(1)\"></object>",
+    "<embed src=\"javascript:alert(1)\">",
+    "<meta http-equiv=\"refresh\" content=\"0;url=javascript:alert(1)\">",
+    "<base href=\"javascript:alert(1)//\">",
+    "<!--<img src=x onerror=alert(1)>-->",
+    "<img src=x onerror=alert(1)",
+    "<a href=\"https://example.com\" title=\"x\" onmouseover=\"alert(1)\">hover</a>",
+    "<details open ontoggle=\"alert(1)\"><summary>x</summary></details>",
+];
+
+// Decodes character references the same way a browser would before checking the output, so encoded payloads can't hide
+fn decode(html: &str) -> String {
+    let entity_regex = regex::Regex::new(r"&#([xX]?)([0-9a-fA-F]+);?").unwrap();
+    entity_regex
+        .replace_all(html, |captures: &regex::Captures| {
+            let radix = if captures[1].is_empty() { 10 } else { 16 };
+            u32::from_str_radix(&captures[2], radix)
+                .ok()
+                .and_then(char::from_u32)
+                .map(|character| character.to_string())
+                .unwrap_or_default()
+        })
+        .replace("&colon;", ":")
+        .to_lowercase()
+}
+
+// Checks that there are no tags that can run script, no event handler attributes, and no dangerous URLs
+fn assert_inert(payload: &str, output: &str) {
+    let tag_regex = regex::Regex::new(r"<[a-z][^>]*>").unwrap();
+    let decoded = decode(output);
+
+    // Attribute values are emptied before checking the attribute names, so escaped text inside of a value isn't mistaken for an attribute
+    let value_regex = regex::Regex::new(r#""[^"]*""#).unwrap();
+
+    for tag in tag_regex.find_iter(&output.to_lowercase()) {
+        let tag = value_regex.replace_all(tag.as_str(), "\"\"");
+        let tag = tag.as_ref();
+        let name = tag[1..]
+            .split(|c: char| c.is_whitespace() || c == '>' || c == '/')
+            .next()
+            .unwrap_or_default();
+
+        assert!(
+            !matches!(
+                name,
+                "script"
+                    | "svg"
+                    | "math"
+                    | "iframe"
+                    | "object"
+                    | "embed"
+                    | "style"
+                    | "form"
+                    | "meta"
+                    | "base"
+                    | "button"
+                    | "link"
+            ),
+            "{:?} rendered a <{}> tag: {}",
+            payload,
+            name,
+            output
+        );
+        assert!(
+            !regex::Regex::new(r"\son[a-z]+\s*=").unwrap().is_match(tag),
+            "{:?} rendered an event handler: {}",
+            payload,
+            output
+        );
+        assert!(
+            !tag.contains("style="),
+            "{:?} rendered a style attribute: {}",
+            payload,
+            output
+        );
+    }
+
+    let url_regex = regex::Regex::new(r#"(href|src|action|data)\s*=\s*"\s*([a-z]+):"#).unwrap();
+    for captures in url_regex.captures_iter(&decoded) {
+        assert!(
+            matches!(&captures[2], "http" | "https" | "mailto"),
+            "{:?} rendered a {} URL: {}",
+            payload,
+            &captures[2],
+            output
+        );
+    }
+}
+
+#[test]
+fn markdown_payloads_render_inert() {
+    for payload in MARKDOWN_PAYLOADS {
+        assert_inert(payload, &render_markdown(payload, &[]));
+    }
+}
+
+#[test]
+fn html_payloads_are_sanitized() {
+    for payload in HTML_PAYLOADS {
+        assert_inert(payload, &sanitize_html(payload));
+    }
+}
+
+#[test]
+fn custom_emojis_with_malicious_urls_render_inert() {
+    let custom_emojis = [
+        "javascript:alert(1)",
+        "\" onerror=\"alert(1)",
+        "data:image/svg+xml,<svg onload=alert(1)>",
+    ]
+    .iter()
+    .enumerate()
+    .map(|(id, image_url)| CustomEmojiView {
+        custom_emoji: CustomEmoji {
+            alt_text: "\"><script>alert(1)</script>".to_string(),
+            category: "Evil".to_string(),
+            id: id as i32,
+            image_url: image_url.to_string(),
+            local_site_id: 1,
+            published: "2023-07-01T00:00:00".to_string(),
+            shortcode: format!("evil{}", id),
+            updated: None,
+        },
+        keywords: Vec::new(),
+    })
+    .collect::<Vec<_>>();
+
+    let markdown = ":evil0: :evil1: :evil2:";
+    assert_inert(markdown, &render_markdown(markdown, &custom_emojis));
+}
+
+#[test]
+fn links_are_given_rel_and_target() {
+    let html = render_markdown(
+        "[external](https://example.com) and [local](/c/wolves)",
+        &[],
+    );
+
+    assert!(html.contains(
+        "<a href=\"https://example.com\" rel=\"nofollow noopener ugc\" target=\"_blank\">external</a>"
+    ));
+    assert!(html.contains("<a href=\"/c/wolves\" rel=\"nofollow noopener ugc\">local</a>"));
+}
+
+#[test]
+fn rel_and_target_from_the_input_are_replaced() {
+    let html =
+        sanitize_html("<a href=\"https://example.com\" target=\"_self\" rel=\"opener\">x</a>");
+
+    assert_eq!(
+        html,
+        "<a href=\"https://example.com\" rel=\"nofollow noopener ugc\" target=\"_blank\">x</a>"
+    );
+}
@@ -0,0 +1,100 @@
+// Tests for the titles, descriptions, link previews, and structured data pages give search engines, and for the sitemap
+
+use wolfrs::components::seo::*;
+use wolfrs::sitemap::{robots_txt, sitemap_xml, SitemapEntry};
+
+mod common;
+use common::sample_post;
+
+const SITE_URL: &str = "https://wolfrs.example";
+
+#[test]
+fn titles_end_with_the_instance_name() {
+    assert_eq!(page_title("A post", "Wolfrs", "A Lemmy UI"), "A post - Wolfrs");
+    assert_eq!(page_title("", "Wolfrs", "A Lemmy UI"), "Wolfrs: A Lemmy UI");
+    assert_eq!(page_title(" ", "Wolfrs", ""), "Wolfrs");
+}
+
+#[test]
+fn summaries_are_plain_text_cut_at_a_word() {
+    assert_eq!(
+        plain_text_summary("Wolves **live** in [packs](https://example.com) & dens", 160),
+        "Wolves live in packs & dens"
+    );
+    let summary = plain_text_summary("Wolves live in packs, and packs hunt together.", 24);
+    assert_eq!(summary, "Wolves live in packs…");
+    assert!(summary.chars().count() <= 24);
+}
+
+#[test]
+fn robots_keep_nsfw_and_private_pages_out_of_search_results() {
+    assert_eq!(robots_policy(false, false), None);
+    assert_eq!(robots_policy(true, false), Some("noindex"));
+    assert_eq!(robots_policy(false, true), Some("noindex, nofollow"));
+}
+
+#[test]
+fn posts_describe_themselves() {
+    let meta = PageMeta::for_post(&sample_post(), SITE_URL);
+    assert_eq!(meta.title, "Wolves <3");
+    assert_eq!(meta.description, "Wolves live in packs.");
+    assert_eq!(meta.canonical_url, "https://wolfrs.example/post/7");
+    assert_eq!(meta.image.as_deref(), Some("https://example.com/wolf.png"));
+    assert_eq!(meta.section.as_deref(), Some("Wolves"));
+}
+
+#[test]
+fn remote_posts_point_at_their_original() {
+    let mut post_view = sample_post();
+    post_view.post.local = false;
+    post_view.post.ap_id = "https://lemmy.example/post/42".to_string();
+
+    let meta = PageMeta::for_post(&post_view, SITE_URL);
+    assert_eq!(meta.canonical_url, "https://lemmy.example/post/42");
+}
+
+#[test]
+fn nsfw_posts_have_no_preview_image() {
+    let mut post_view = sample_post();
+    post_view.community.nsfw = true;
+
+    let meta = PageMeta::for_post(&post_view, SITE_URL);
+    assert!(meta.nsfw);
+    assert_eq!(meta.image, None);
+    assert!(!meta.json_ld.unwrap().contains("\"image\""));
+}
+
+#[test]
+fn structured_data_cant_close_its_script() {
+    let json_ld = post_json_ld(&sample_post(), "https://wolfrs.example/post/7");
+    assert!(json_ld.contains("\"DiscussionForumPosting\""));
+    assert!(json_ld.contains("\"datePublished\":\"2023-06-01T12:00:00Z\""));
+    assert!(!json_ld.contains('<'));
+    assert!(json_ld.contains("Wolves \\u003c3"));
+}
+
+#[test]
+fn sitemaps_escape_urls() {
+    let xml = sitemap_xml(&[
+        SitemapEntry {
+            url: "https://wolfrs.example/".to_string(),
+            last_modified: None,
+        },
+        SitemapEntry::new(
+            "https://wolfrs.example/community/a&b".to_string(),
+            "2023-06-01T12:00:00",
+            None,
+        ),
+    ]);
+    assert!(xml.contains("<loc>https://wolfrs.example/</loc>"));
+    assert!(xml.contains("<loc>https://wolfrs.example/community/a&amp;b</loc>"));
+    assert!(xml.contains("<lastmod>2023-06-01T12:00:00Z</lastmod>"));
+    assert_eq!(xml.matches("<url>").count(), 2);
+}
+
+#[test]
+fn private_instances_disallow_crawlers() {
+    assert_eq!(robots_txt(SITE_URL, true), "User-agent: *\nDisallow: /\n");
+    assert!(robots_txt("https://wolfrs.example/", false)
+        .contains("Sitemap: https://wolfrs.example/sitemap.xml"));
+}
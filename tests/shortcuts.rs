@@ -0,0 +1,36 @@
+// Tests for working out which keyboard shortcut a key press is.
+
+use std::collections::HashSet;
+
+use wolfrs::components::shortcuts::{match_keys, KeyMatch, Shortcut};
+
+#[test]
+fn matches_single_keys() {
+    assert_eq!(match_keys(None, "j"), KeyMatch::Shortcut(Shortcut::NextItem));
+    assert_eq!(match_keys(None, "?"), KeyMatch::Shortcut(Shortcut::Help));
+    assert_eq!(match_keys(None, "Enter"), KeyMatch::Shortcut(Shortcut::CollapseComment));
+    assert_eq!(match_keys(None, "q"), KeyMatch::None);
+}
+
+#[test]
+fn matches_two_key_shortcuts() {
+    assert_eq!(match_keys(None, "g"), KeyMatch::Prefix);
+    assert_eq!(match_keys(Some("g"), "h"), KeyMatch::Shortcut(Shortcut::GoHome));
+    // A key that doesn't finish the shortcut still works on its own
+    assert_eq!(match_keys(Some("g"), "j"), KeyMatch::Shortcut(Shortcut::NextItem));
+    assert_eq!(match_keys(Some("g"), "q"), KeyMatch::None);
+}
+
+#[test]
+fn composer_shortcuts_only_work_in_composers() {
+    assert_eq!(match_keys(None, "Ctrl+Enter"), KeyMatch::None);
+}
+
+#[test]
+fn every_shortcut_has_its_own_keys() {
+    let keys = Shortcut::ALL
+        .iter()
+        .map(|shortcut| shortcut.keys())
+        .collect::<HashSet<_>>();
+    assert_eq!(keys.len(), Shortcut::ALL.len());
+}
@@ -0,0 +1,64 @@
+// Tests for parsing and formatting the timestamps Lemmy sends
+
+use leptos::*;
+
+use wolfrs::components::i18n::translate_age;
+use wolfrs::timestamps::{Age, Timestamp};
+
+#[test]
+fn parses_naive_timestamps_as_utc() {
+    let timestamp = Timestamp::parse("2023-07-01T12:34:56.123456").unwrap();
+    assert_eq!(timestamp.unix(), 1688214896);
+    assert_eq!(timestamp.to_iso(), "2023-07-01T12:34:56Z");
+}
+
+#[test]
+fn applies_offsets() {
+    let utc = Timestamp::parse("2023-07-01T12:34:56Z").unwrap();
+    let offset = Timestamp::parse("2023-07-01T14:34:56+02:00").unwrap();
+    assert_eq!(utc, offset);
+    assert_eq!(Timestamp::parse("2023-07-01T14:34:56.5+0200"), Some(utc));
+    assert_eq!(Timestamp::parse("2023-07-01T10:04:56-02:30"), Some(utc));
+}
+
+#[test]
+fn rejects_offsets_that_arent_hhmm() {
+    assert_eq!(Timestamp::parse("2023-07-01T14:34:56+2"), None);
+    assert_eq!(Timestamp::parse("2023-07-01T14:34:56+02:00junk"), None);
+    assert_eq!(Timestamp::parse("2023-07-01T14:34:56+02:60"), None);
+    assert_eq!(Timestamp::parse("2023-07-01T14:34:56 UTC"), None);
+}
+
+#[test]
+fn rejects_text_that_isnt_a_timestamp() {
+    assert_eq!(Timestamp::parse(""), None);
+    assert_eq!(Timestamp::parse("yesterday"), None);
+    assert_eq!(Timestamp::parse("2023-13-01T00:00:00"), None);
+}
+
+#[test]
+fn ages() {
+    let now = Timestamp::parse("2024-01-01T00:00:00").unwrap();
+    let before = |seconds: i64| Timestamp::from_unix(now.unix() - seconds).age_at(now);
+
+    assert_eq!(before(10), Age::JustNow);
+    assert_eq!(before(5 * 60), Age::Minutes(5));
+    assert_eq!(before(3 * 3600), Age::Hours(3));
+    assert_eq!(before(2 * 86400), Age::Days(2));
+    assert_eq!(before(400 * 86400), Age::Years(1));
+    // Timestamps from the future, from clocks that disagree, aren't shown as negative
+    assert_eq!(before(-60), Age::JustNow);
+    assert_eq!(Age::from_seconds(45 * 86400), Age::Months(1));
+}
+
+// Outside the App there is no language picked, so ages are in English
+#[test]
+fn translates_ages() {
+    let runtime = create_runtime();
+    run_scope(runtime, |cx| {
+        assert_eq!(translate_age(cx, Age::JustNow), "just now");
+        assert_eq!(translate_age(cx, Age::Minutes(5)), "5m ago");
+        assert_eq!(translate_age(cx, Age::Months(1)), "1mo ago");
+    });
+    runtime.dispose();
+}